@@ -3,6 +3,8 @@ use std::path::Path;
 use std::process::Command;
 
 fn main() {
+    prepend_seastar_pkg_config_path();
+
     let seastar = pkg_config::Config::new()
         .statik(true)
         .probe("seastar")
@@ -20,15 +22,21 @@ fn main() {
     // TODO: Remove this after seastar.pc or the pkg-config crate is fixed
     pkg_config::Config::new().statik(true).probe("fmt").unwrap();
 
-    cc::Build::new()
+    let mut build = cc::Build::new();
+    build
         .file("src/cxx.cc")
         .cpp(true)
         .cpp_link_stdlib(None) // linked via link-cplusplus crate
         .flag_if_supported(cxxbridge_flags::STD)
         .flag_if_supported("-std=c++20")
         .includes(&seastar.include_paths)
-        .warnings_into_errors(cfg!(deny_warnings))
-        .compile("cxxbridge1");
+        .warnings_into_errors(cfg!(deny_warnings));
+    if env::var_os("CARGO_FEATURE_SEASTAR_SHIMS_ON_DEMAND").is_some() {
+        build.define("CXXBRIDGE1_SEASTAR_SHIMS_ON_DEMAND", None);
+        // Read by cxx-build, which then has every bridge define the shims.
+        println!("cargo:SEASTAR_SHIMS_ON_DEMAND=1");
+    }
+    build.compile("cxxbridge1");
 
     println!("cargo:rerun-if-changed=src/cxx.cc");
     println!("cargo:rerun-if-changed=include/cxx.h");
@@ -50,6 +58,26 @@ fn main() {
     }
 }
 
+// Lets seastar and fmt be found under a prefix that pkg-config does not know
+// about, without the caller having to rewrite PKG_CONFIG_PATH for the whole
+// build. With SEASTAR_PKG_CONFIG_PATH=/opt/seastar/lib/pkgconfig the probes
+// look in /opt/seastar/lib/pkgconfig first, then in the directories already
+// in PKG_CONFIG_PATH, then in pkg-config's default search path. Left unset or
+// empty, PKG_CONFIG_PATH is not touched and the probes behave as before.
+fn prepend_seastar_pkg_config_path() {
+    println!("cargo:rerun-if-env-changed=SEASTAR_PKG_CONFIG_PATH");
+    let seastar_path = match env::var_os("SEASTAR_PKG_CONFIG_PATH") {
+        Some(path) if !path.is_empty() => path,
+        _ => return,
+    };
+    let mut paths = env::split_paths(&seastar_path).collect::<Vec<_>>();
+    if let Some(path) = env::var_os("PKG_CONFIG_PATH") {
+        paths.extend(env::split_paths(&path));
+    }
+    let path = env::join_paths(paths).expect("invalid SEASTAR_PKG_CONFIG_PATH");
+    env::set_var("PKG_CONFIG_PATH", path);
+}
+
 struct RustVersion {
     version: String,
     minor: u32,
@@ -400,6 +400,9 @@ fn generate_bridge(prj: &Project, build: &mut Build, rust_source_file: &Path) ->
         allow_dot_includes: false,
         cfg_evaluator: Box::new(CargoEnvCfgEvaluator),
         doxygen: CFG.doxygen,
+        // Set by the cxx crate's build script when its seastar-shims-on-demand
+        // feature leaves the primitive seastar pointer shims to the bridges.
+        seastar_shims_on_demand: env::var_os("DEP_CXXBRIDGE1_SEASTAR_SHIMS_ON_DEMAND").is_some(),
         ..Opt::default()
     };
     let generated = gen::generate_from_path(rust_source_file, &opt);
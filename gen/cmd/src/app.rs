@@ -44,7 +44,8 @@ fn app() -> Command {
         .arg(arg_header())
         .arg(arg_help())
         .arg(arg_include())
-        .arg(arg_output());
+        .arg(arg_output())
+        .arg(arg_seastar_shims_on_demand());
     if let Some(version) = option_env!("CARGO_PKG_VERSION") {
         app = app.arg(arg_version()).version(version);
     }
@@ -58,6 +59,7 @@ const HELP: &str = "help";
 const HEADER: &str = "header";
 const INCLUDE: &str = "include";
 const OUTPUT: &str = "output";
+const SEASTAR_SHIMS_ON_DEMAND: &str = "seastar-shims-on-demand";
 const VERSION: &str = "version";
 
 pub(super) fn from_args() -> Opt {
@@ -73,6 +75,7 @@ pub(super) fn from_args() -> Opt {
         .get_one::<String>(CXX_IMPL_ANNOTATIONS)
         .map(String::clone);
     let header = matches.get_flag(HEADER);
+    let seastar_shims_on_demand = matches.get_flag(SEASTAR_SHIMS_ON_DEMAND);
     let include = matches
         .get_many::<String>(INCLUDE)
         .unwrap_or_default()
@@ -116,6 +119,7 @@ pub(super) fn from_args() -> Opt {
         include,
         outputs,
         cfg,
+        seastar_shims_on_demand,
     }
 }
 
@@ -209,6 +213,17 @@ not specified.";
         .help(HELP)
 }
 
+fn arg_seastar_shims_on_demand() -> Arg {
+    const HELP: &str = "\
+Define the shims for seastar pointers to primitive types that the
+bridge refers to. Needed when the cxx crate is built with its
+seastar-shims-on-demand feature, which leaves them out.";
+    Arg::new(SEASTAR_SHIMS_ON_DEMAND)
+        .long(SEASTAR_SHIMS_ON_DEMAND)
+        .num_args(0)
+        .help(HELP)
+}
+
 fn arg_version() -> Arg {
     Arg::new(VERSION)
         .long(VERSION)
@@ -53,6 +53,7 @@ struct Opt {
     include: Vec<Include>,
     outputs: Vec<Output>,
     cfg: Map<String, Set<CfgValue>>,
+    seastar_shims_on_demand: bool,
 }
 
 fn main() {
@@ -97,6 +98,7 @@ fn try_main() -> Result<()> {
         gen_header,
         gen_implementation,
         cfg_evaluator: Box::new(FlagsCfgEvaluator::new(opt.cfg)),
+        seastar_shims_on_demand: opt.seastar_shims_on_demand,
         ..Default::default()
     };
 
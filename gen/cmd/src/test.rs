@@ -40,6 +40,11 @@ Options:
           Path of file to write as output. Output goes to stdout if -o is
           not specified.
 
+      --seastar-shims-on-demand
+          Define the shims for seastar pointers to primitive types that the
+          bridge refers to. Needed when the cxx crate is built with its
+          seastar-shims-on-demand feature, which leaves them out.
+
       --version
           Print version information.
 ";
@@ -28,6 +28,9 @@ pub struct Builtins<'a> {
     pub rust_slice_repr: bool,
     pub relocatable: bool,
     pub relocatable_or_array: bool,
+    pub seastar_ptr: bool,
+    pub seastar_lw_shared_ptr: bool,
+    pub seastar_shared_ptr: bool,
     pub friend_impl: bool,
     pub is_complete: bool,
     pub destroy: bool,
@@ -122,6 +125,27 @@ pub(super) fn write(out: &mut OutFile) {
         include.type_traits = true;
     }
 
+    if builtin.seastar_lw_shared_ptr {
+        include.stdexcept = true;
+        include.type_traits = true;
+        builtin.panic = true;
+        builtin.seastar_ptr = true;
+    }
+
+    if builtin.seastar_shared_ptr {
+        include.algorithm = true;
+        include.new = true;
+        include.type_traits = true;
+        include.utility = true;
+    }
+
+    if builtin.seastar_ptr {
+        include.cstddef = true;
+        include.cstdint = true;
+        include.new = true;
+        include.utility = true;
+    }
+
     if builtin.layout {
         include.type_traits = true;
         include.cstddef = true;
@@ -192,6 +216,17 @@ pub(super) fn write(out: &mut OutFile) {
         ifndef::write(out, builtin.is_complete, "CXXBRIDGE1_IS_COMPLETE");
         ifndef::write(out, builtin.layout, "CXXBRIDGE1_LAYOUT");
         ifndef::write(out, builtin.relocatable, "CXXBRIDGE1_RELOCATABLE");
+        ifndef::write(out, builtin.seastar_ptr, "CXXBRIDGE1_SEASTAR_PTR");
+        ifndef::write(
+            out,
+            builtin.seastar_lw_shared_ptr,
+            "CXXBRIDGE1_SEASTAR_LW_SHARED_PTR",
+        );
+        ifndef::write(
+            out,
+            builtin.seastar_shared_ptr,
+            "CXXBRIDGE1_SEASTAR_SHARED_PTR",
+        );
     }
 
     if builtin.rust_str_new_unchecked {
@@ -60,6 +60,7 @@ pub struct Opt {
     pub(super) allow_dot_includes: bool,
     pub(super) cfg_evaluator: Box<dyn CfgEvaluator>,
     pub(super) doxygen: bool,
+    pub(super) seastar_shims_on_demand: bool,
 }
 
 pub(super) trait CfgEvaluator {
@@ -91,6 +92,7 @@ impl Default for Opt {
             allow_dot_includes: true,
             cfg_evaluator: Box::new(UnsupportedCfgEvaluator),
             doxygen: false,
+            seastar_shims_on_demand: false,
         }
     }
 }
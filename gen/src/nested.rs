@@ -150,6 +150,8 @@ mod tests {
             bounds: Vec::new(),
             semi_token: Token![;](Span::call_site()),
             trusted: false,
+            enable_shared_from_this: None,
+            seastar_upcast: None,
         })
     }
 }
@@ -219,12 +219,16 @@ fn pick_includes_and_builtins(out: &mut OutFile, apis: &[Api]) {
             Type::SharedPtr(_) | Type::WeakPtr(_) => out.include.memory = true,
             Type::SeastarLwSharedPtr(_) => out.include.memory = true,
             Type::SeastarSharedPtr(_) => out.include.memory = true,
+            Type::SeastarFuture(_) => {
+                out.include.memory = true;
+                out.include.utility = true;
+            }
             Type::Str(_) => out.builtin.rust_str = true,
             Type::CxxVector(_) => out.include.vector = true,
             Type::Fn(_) => out.builtin.rust_fn = true,
             Type::SliceRef(_) => out.builtin.rust_slice = true,
             Type::Array(_) => out.include.array = true,
-            Type::Ref(_) | Type::Void(_) | Type::Ptr(_) => {}
+            Type::SeastarCircularBuffer(_) | Type::Ref(_) | Type::Void(_) | Type::Ptr(_) => {}
         }
     }
 }
@@ -324,9 +328,35 @@ fn write_struct<'a>(out: &mut OutFile<'a>, strct: &'a Struct, methods: &[&Extern
     writeln!(out, "  using IsRelocatable = ::std::true_type;");
 
     writeln!(out, "}};");
+    for field in &strct.fields {
+        write_struct_field_layout_assert(out, &field.ty);
+    }
     writeln!(out, "#endif // {}", guard);
 }
 
+fn write_struct_field_layout_assert(out: &mut OutFile, ty: &Type) {
+    // The Rust side of a shared struct lays out these fields as one or two
+    // opaque pointer-sized words.
+    let words = match ty {
+        Type::SeastarLwSharedPtr(_) => "",
+        Type::SeastarSharedPtr(_) => "2 * ",
+        _ => return,
+    };
+    write!(out, "static_assert(sizeof(");
+    write_type(out, ty);
+    writeln!(
+        out,
+        ") == {}sizeof(void *), \"disagrees with the layout in #[cxx::bridge]\");",
+        words,
+    );
+    write!(out, "static_assert(alignof(");
+    write_type(out, ty);
+    writeln!(
+        out,
+        ") == alignof(void *), \"disagrees with the layout in #[cxx::bridge]\");",
+    );
+}
+
 fn write_struct_decl(out: &mut OutFile, ident: &Pair) {
     writeln!(out, "struct {};", ident.cxx);
 }
@@ -802,6 +832,13 @@ fn write_cxx_function_shim<'a>(out: &mut OutFile<'a>, efn: &'a ExternFn) {
     }
     match &efn.ret {
         Some(Type::Ref(_)) => write!(out, "&"),
+        Some(ty @ Type::SeastarFuture(_)) => {
+            // Hand the future over to Rust in a heap allocation, since a
+            // seastar::future is not trivially relocatable.
+            write!(out, "new ");
+            write_type(out, ty);
+            write!(out, "(");
+        }
         Some(Type::Str(_)) if !indirect_return => {
             out.builtin.rust_str_repr = true;
             write!(out, "::rust::impl<::rust::Str>::repr(");
@@ -850,6 +887,7 @@ fn write_cxx_function_shim<'a>(out: &mut OutFile<'a>, efn: &'a ExternFn) {
     match &efn.ret {
         Some(Type::RustBox(_)) => write!(out, ".into_raw()"),
         Some(Type::UniquePtr(_)) => write!(out, ".release()"),
+        Some(Type::SeastarFuture(_)) => write!(out, ")"),
         Some(Type::Str(_)) | Some(Type::SliceRef(_)) if !indirect_return => write!(out, ")"),
         _ => {}
     }
@@ -1061,6 +1099,13 @@ fn write_rust_function_shim_impl(
                 write_type(out, ret);
                 write!(out, "(");
             }
+            Type::SeastarFuture(_) => {
+                // Rust hands over a heap allocated future; move out of it
+                // and let the temporary unique_ptr free the allocation.
+                write!(out, "::std::move(*::std::unique_ptr<");
+                write_type(out, ret);
+                write!(out, ">(");
+            }
             Type::Ref(_) => write!(out, "*"),
             Type::Str(_) => {
                 out.builtin.rust_str_new_unchecked = true;
@@ -1119,6 +1164,8 @@ fn write_rust_function_shim_impl(
         if let Some(ret) = &sig.ret {
             if let Type::RustBox(_) | Type::UniquePtr(_) | Type::Str(_) | Type::SliceRef(_) = ret {
                 write!(out, ")");
+            } else if let Type::SeastarFuture(_) = ret {
+                write!(out, "))");
             }
         }
     }
@@ -1188,6 +1235,10 @@ fn write_extern_return_type_space(out: &mut OutFile, ty: &Option<Type>) {
             write_type_space(out, &ty.inner);
             write!(out, "*");
         }
+        Some(ty @ Type::SeastarFuture(_)) => {
+            write_type_space(out, ty);
+            write!(out, "*");
+        }
         Some(Type::Ref(ty)) => {
             write_type_space(out, &ty.inner);
             if !ty.mutable {
@@ -1258,6 +1309,16 @@ fn write_type(out: &mut OutFile, ty: &Type) {
             write_type(out, &ptr.inner);
             write!(out, ">");
         }
+        Type::SeastarFuture(ty) => {
+            write!(out, "::seastar::future<");
+            write_type(out, &ty.inner);
+            write!(out, ">");
+        }
+        Type::SeastarCircularBuffer(ty) => {
+            write!(out, "::seastar::circular_buffer<");
+            write_type(out, &ty.inner);
+            write!(out, ">");
+        }
         Type::WeakPtr(ptr) => {
             write!(out, "::std::weak_ptr<");
             write_type(out, &ptr.inner);
@@ -1318,23 +1379,27 @@ fn write_type(out: &mut OutFile, ty: &Type) {
 }
 
 fn write_atom(out: &mut OutFile, atom: Atom) {
+    write!(out, "{}", atom_to_cxx(atom));
+}
+
+fn atom_to_cxx(atom: Atom) -> &'static str {
     match atom {
-        Bool => write!(out, "bool"),
-        Char => write!(out, "char"),
-        U8 => write!(out, "::std::uint8_t"),
-        U16 => write!(out, "::std::uint16_t"),
-        U32 => write!(out, "::std::uint32_t"),
-        U64 => write!(out, "::std::uint64_t"),
-        Usize => write!(out, "::std::size_t"),
-        I8 => write!(out, "::std::int8_t"),
-        I16 => write!(out, "::std::int16_t"),
-        I32 => write!(out, "::std::int32_t"),
-        I64 => write!(out, "::std::int64_t"),
-        Isize => write!(out, "::rust::isize"),
-        F32 => write!(out, "float"),
-        F64 => write!(out, "double"),
-        CxxString => write!(out, "::std::string"),
-        RustString => write!(out, "::rust::String"),
+        Bool => "bool",
+        Char => "char",
+        U8 => "::std::uint8_t",
+        U16 => "::std::uint16_t",
+        U32 => "::std::uint32_t",
+        U64 => "::std::uint64_t",
+        Usize => "::std::size_t",
+        I8 => "::std::int8_t",
+        I16 => "::std::int16_t",
+        I32 => "::std::int32_t",
+        I64 => "::std::int64_t",
+        Isize => "::rust::isize",
+        F32 => "float",
+        F64 => "double",
+        CxxString => "::std::string",
+        RustString => "::rust::String",
     }
 }
 
@@ -1351,6 +1416,8 @@ fn write_space_after_type(out: &mut OutFile, ty: &Type) {
         | Type::SharedPtr(_)
         | Type::SeastarLwSharedPtr(_)
         | Type::SeastarSharedPtr(_)
+        | Type::SeastarFuture(_)
+        | Type::SeastarCircularBuffer(_)
         | Type::WeakPtr(_)
         | Type::Str(_)
         | Type::CxxVector(_)
@@ -1424,6 +1491,9 @@ fn write_generic_instantiations(out: &mut OutFile) {
         match *impl_key {
             ImplKey::RustBox(ident) => write_rust_box_extern(out, ident),
             ImplKey::RustVec(ident) => write_rust_vec_extern(out, ident),
+            ImplKey::RustVecSeastarLwSharedPtr(ident) => {
+                write_rust_vec_seastar_lw_shared_ptr_extern(out, ident);
+            }
             ImplKey::UniquePtr(ident) => write_unique_ptr(out, ident),
             ImplKey::SharedPtr(ident) => write_shared_ptr(out, ident),
             ImplKey::SeastarLwSharedPtr(ident) => write_seastar_lw_shared_ptr(out, ident),
@@ -1432,6 +1502,7 @@ fn write_generic_instantiations(out: &mut OutFile) {
             ImplKey::CxxVector(ident) => write_cxx_vector(out, ident),
         }
     }
+    write_seastar_primitive_instantiations(out);
     out.end_block(Block::ExternC);
 
     out.begin_block(Block::Namespace("rust"));
@@ -1440,6 +1511,9 @@ fn write_generic_instantiations(out: &mut OutFile) {
         match *impl_key {
             ImplKey::RustBox(ident) => write_rust_box_impl(out, ident),
             ImplKey::RustVec(ident) => write_rust_vec_impl(out, ident),
+            ImplKey::RustVecSeastarLwSharedPtr(ident) => {
+                write_rust_vec_seastar_lw_shared_ptr_impl(out, ident);
+            }
             _ => {}
         }
     }
@@ -1447,6 +1521,217 @@ fn write_generic_instantiations(out: &mut OutFile) {
     out.end_block(Block::Namespace("rust"));
 }
 
+// The cxx crate ships the shims for seastar pointers to primitive targets.
+// When it is built with the "seastar-shims-on-demand" feature it leaves them
+// out, and every bridge instead defines the ones it refers to. Weak linkage
+// lets several bridges referring to the same target link together. The bodies
+// are the helpers in cxx.h that the cxx crate's own shims call too.
+fn write_seastar_primitive_instantiations(out: &mut OutFile) {
+    if !out.opt.seastar_shims_on_demand {
+        return;
+    }
+
+    let mut lw_shared_ptr = Vec::new();
+    let mut shared_ptr = Vec::new();
+    for ty in out.types.all.iter() {
+        let (targets, inner) = match ty {
+            Type::SeastarLwSharedPtr(ptr) => (&mut lw_shared_ptr, &ptr.inner),
+            Type::SeastarSharedPtr(ptr) => (&mut shared_ptr, &ptr.inner),
+            _ => continue,
+        };
+        let atom = match inner {
+            Type::Ident(ident) => match Atom::from(&ident.rust) {
+                Some(Char) | Some(RustString) | None => continue,
+                Some(atom) => atom,
+            },
+            _ => continue,
+        };
+        if !targets.contains(&atom) {
+            targets.push(atom);
+        }
+    }
+
+    for atom in lw_shared_ptr {
+        out.next_section();
+        write_seastar_lw_shared_ptr_primitive(out, atom);
+    }
+    for atom in shared_ptr {
+        out.next_section();
+        write_seastar_shared_ptr_primitive(out, atom);
+    }
+}
+
+fn seastar_primitive_instance(atom: Atom) -> String {
+    match atom {
+        CxxString => "string".to_owned(),
+        _ => atom.to_string(),
+    }
+}
+
+fn write_seastar_lw_shared_ptr_primitive(out: &mut OutFile, atom: Atom) {
+    let inner = atom_to_cxx(atom);
+    let instance = seastar_primitive_instance(atom);
+
+    out.builtin.maybe_uninit = true;
+    out.builtin.seastar_lw_shared_ptr = true;
+
+    writeln!(
+        out,
+        "__attribute__((weak)) void cxxbridge1$seastar$lw_shared_ptr${}$null(::seastar::lw_shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  ::rust::detail::seastar_ptr_null(ptr);");
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) {} *cxxbridge1$seastar$lw_shared_ptr${}$uninit(::seastar::lw_shared_ptr<{}> *ptr) noexcept {{",
+        inner, instance, inner,
+    );
+    writeln!(
+        out,
+        "  return ::rust::detail::seastar_lw_shared_ptr_uninit<::seastar::enable_lw_shared_from_this<{}>>(ptr, [] {{ return ::seastar::make_lw_shared<::rust::MaybeUninit<{}>>(); }});",
+        inner, inner,
+    );
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) void cxxbridge1$seastar$lw_shared_ptr${}$clone(::seastar::lw_shared_ptr<{}> const &self, ::seastar::lw_shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner, inner,
+    );
+    writeln!(out, "  ::rust::detail::seastar_ptr_clone(self, ptr);");
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) {} const *cxxbridge1$seastar$lw_shared_ptr${}$get(::seastar::lw_shared_ptr<{}> const &self) noexcept {{",
+        inner, instance, inner,
+    );
+    writeln!(out, "  return ::rust::detail::seastar_ptr_get(self);");
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) void cxxbridge1$seastar$lw_to_shared${}(::seastar::lw_shared_ptr<{}> const &self, ::seastar::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner, inner,
+    );
+    writeln!(
+        out,
+        "  ::rust::detail::seastar_lw_shared_ptr_to_shared(self, ptr, []({} const &value) {{ return ::seastar::make_shared<{}>(value); }});",
+        inner, inner,
+    );
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) ::std::size_t cxxbridge1$seastar$lw_shared_ptr${}$use_count(::seastar::lw_shared_ptr<{}> const &self) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  return ::rust::detail::seastar_ptr_use_count(self);");
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) void cxxbridge1$seastar$lw_shared_ptr${}$drop(::seastar::lw_shared_ptr<{}> *self) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  ::rust::detail::seastar_ptr_drop(self);");
+    writeln!(out, "}}");
+    if atom == CxxString {
+        writeln!(
+            out,
+            "__attribute__((weak)) void cxxbridge1$seastar$lw_shared_ptr$string$from_str(::seastar::lw_shared_ptr<::std::string> *ptr, ::std::uint8_t const *s, ::std::size_t len) noexcept {{",
+        );
+        writeln!(
+            out,
+            "  ::rust::detail::seastar_ptr_from_str(ptr, [](char const *data, ::std::size_t size) {{ return ::seastar::make_lw_shared<::std::string>(data, size); }}, s, len);",
+        );
+        writeln!(out, "}}");
+    }
+}
+
+fn write_seastar_shared_ptr_primitive(out: &mut OutFile, atom: Atom) {
+    let inner = atom_to_cxx(atom);
+    let instance = seastar_primitive_instance(atom);
+
+    out.builtin.maybe_uninit = true;
+    out.builtin.seastar_ptr = true;
+
+    writeln!(
+        out,
+        "__attribute__((weak)) void cxxbridge1$seastar$shared_ptr${}$null(::seastar::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  ::rust::detail::seastar_ptr_null(ptr);");
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) {} *cxxbridge1$seastar$shared_ptr${}$uninit(::seastar::shared_ptr<{}> *ptr) noexcept {{",
+        inner, instance, inner,
+    );
+    writeln!(
+        out,
+        "  return ::rust::detail::seastar_ptr_uninit<{}>(ptr, [] {{ return ::seastar::make_shared<::rust::MaybeUninit<{}>>(); }});",
+        inner, inner,
+    );
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) {} *cxxbridge1$seastar$shared_ptr${}$uninit_with_deleter(void (*call)(void *, {} *), void *deleter, ::seastar::shared_ptr<{}> *ptr) noexcept {{",
+        inner, instance, inner, inner,
+    );
+    write_seastar_shared_ptr_with_deleter_body(out, inner);
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) bool cxxbridge1$seastar$shared_ptr${}$take_unique(::seastar::shared_ptr<{}> *self, {} *value) noexcept {{",
+        instance, inner, inner,
+    );
+    write_seastar_shared_ptr_take_unique_body(out, inner);
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) void cxxbridge1$seastar$shared_ptr${}$clone(::seastar::shared_ptr<{}> const &self, ::seastar::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner, inner,
+    );
+    writeln!(out, "  ::rust::detail::seastar_ptr_clone(self, ptr);");
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) {} const *cxxbridge1$seastar$shared_ptr${}$get(::seastar::shared_ptr<{}> const &self) noexcept {{",
+        inner, instance, inner,
+    );
+    writeln!(out, "  return ::rust::detail::seastar_ptr_get(self);");
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) void cxxbridge1$seastar$shared_ptr${}$from_std(::std::shared_ptr<{}> const &src, ::seastar::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner, inner,
+    );
+    write_seastar_shared_ptr_from_std_body(out, inner);
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) ::std::size_t cxxbridge1$seastar$shared_ptr${}$use_count(::seastar::shared_ptr<{}> const &self) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  return ::rust::detail::seastar_ptr_use_count(self);");
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "__attribute__((weak)) void cxxbridge1$seastar$shared_ptr${}$drop(::seastar::shared_ptr<{}> *self) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  ::rust::detail::seastar_ptr_drop(self);");
+    writeln!(out, "}}");
+    if atom == CxxString {
+        writeln!(
+            out,
+            "__attribute__((weak)) void cxxbridge1$seastar$shared_ptr$string$from_str(::seastar::shared_ptr<::std::string> *ptr, ::std::uint8_t const *s, ::std::size_t len) noexcept {{",
+        );
+        writeln!(
+            out,
+            "  ::rust::detail::seastar_ptr_from_str(ptr, [](char const *data, ::std::size_t size) {{ return ::seastar::make_shared<::std::string>(data, size); }}, s, len);",
+        );
+        writeln!(out, "}}");
+    }
+}
+
 fn write_rust_box_extern(out: &mut OutFile, key: NamedImplKey) {
     let resolve = out.types.resolve(&key);
     let inner = resolve.name.to_fully_qualified();
@@ -1473,7 +1758,20 @@ fn write_rust_vec_extern(out: &mut OutFile, key: NamedImplKey) {
     let element = key.rust;
     let inner = element.to_typename(out.types);
     let instance = element.to_mangled(out.types);
+    write_rust_vec_extern_common(out, &inner, &instance);
+}
 
+fn write_rust_vec_seastar_lw_shared_ptr_extern(out: &mut OutFile, key: NamedImplKey) {
+    let element = key.rust;
+    let inner = format!(
+        "::seastar::lw_shared_ptr<{}>",
+        element.to_typename(out.types)
+    );
+    let instance = symbol::join(&[&"seastar_lw_shared_ptr", &element.to_mangled(out.types)]);
+    write_rust_vec_extern_common(out, &inner, &instance);
+}
+
+fn write_rust_vec_extern_common(out: &mut OutFile, inner: &str, instance: &Symbol) {
     out.include.cstddef = true;
 
     writeln!(
@@ -1554,7 +1852,20 @@ fn write_rust_vec_impl(out: &mut OutFile, key: NamedImplKey) {
     let element = key.rust;
     let inner = element.to_typename(out.types);
     let instance = element.to_mangled(out.types);
+    write_rust_vec_impl_common(out, &inner, &instance);
+}
+
+fn write_rust_vec_seastar_lw_shared_ptr_impl(out: &mut OutFile, key: NamedImplKey) {
+    let element = key.rust;
+    let inner = format!(
+        "::seastar::lw_shared_ptr<{}>",
+        element.to_typename(out.types)
+    );
+    let instance = symbol::join(&[&"seastar_lw_shared_ptr", &element.to_mangled(out.types)]);
+    write_rust_vec_impl_common(out, &inner, &instance);
+}
 
+fn write_rust_vec_impl_common(out: &mut OutFile, inner: &str, instance: &Symbol) {
     out.include.cstddef = true;
 
     writeln!(out, "template <>");
@@ -1760,15 +2071,15 @@ fn write_seastar_lw_shared_ptr(out: &mut OutFile, key: NamedImplKey) {
     let ident = key.rust;
     let resolve = out.types.resolve(ident);
     let inner = resolve.name.to_fully_qualified();
-    let instance = resolve.name.to_symbol();
+    let instance = mangle::seastar_lw_shared_ptr(resolve.name);
 
     out.include.new = true;
     out.include.utility = true;
+    out.include.cstddef = true;
 
-    // Some aliases are to opaque types; some are to trivial types. We can't
-    // know at code generation time, so we generate both C++ and Rust side
-    // bindings for a "new" method anyway. But the Rust code can't be called for
-    // Opaque types because the 'new' method is not implemented.
+    // The uninit shim is emitted for every target: besides backing "new" for
+    // types that may be trivial, it provides the storage for emplace_with,
+    // which is how opaque types get constructed in place.
     let can_construct_from_value = out.types.is_maybe_trivial(ident);
 
     writeln!(
@@ -1784,56 +2095,75 @@ fn write_seastar_lw_shared_ptr(out: &mut OutFile, key: NamedImplKey) {
     begin_function_definition(out);
     writeln!(
         out,
-        "void cxxbridge1$lw_shared_ptr${}$null(::seastar::lw_shared_ptr<{}> *ptr) noexcept {{",
+        "void {}$null(::seastar::lw_shared_ptr<{}> *ptr) noexcept {{",
         instance, inner,
     );
     writeln!(out, "  ::new (ptr) ::seastar::lw_shared_ptr<{}>();", inner);
     writeln!(out, "}}");
+    out.builtin.maybe_uninit = true;
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "{} *{}$uninit(::seastar::lw_shared_ptr<{}> *ptr) noexcept {{",
+        inner, instance, inner,
+    );
+    out.builtin.seastar_lw_shared_ptr = true;
+    writeln!(
+        out,
+        "  return ::rust::detail::seastar_lw_shared_ptr_uninit<::seastar::enable_lw_shared_from_this<{}>>(ptr, [] {{ return ::seastar::make_lw_shared<::rust::MaybeUninit<{}>>(); }});",
+        inner, inner,
+    );
+    writeln!(out, "}}");
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void {}$clone(::seastar::lw_shared_ptr<{}> const &self, ::seastar::lw_shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner, inner,
+    );
+    writeln!(
+        out,
+        "  ::new (ptr) ::seastar::lw_shared_ptr<{}>(self);",
+        inner
+    );
+    writeln!(out, "}}");
     if can_construct_from_value {
-        out.builtin.maybe_uninit = true;
         begin_function_definition(out);
         writeln!(
             out,
-            "{} *cxxbridge1$lw_shared_ptr${}$uninit(::seastar::lw_shared_ptr<{}> *ptr) noexcept {{",
-            inner, instance, inner,
-        );
-        writeln!(
-            out,
-            "  {} *uninit = reinterpret_cast<{} *>(new ::rust::MaybeUninit<{}>);",
-            inner, inner, inner,
+            "void {}$to_shared(::seastar::lw_shared_ptr<{}> const &self, ::seastar::shared_ptr<{}> *ptr) noexcept {{",
+            instance, inner, inner,
         );
+        writeln!(out, "  if (self) {{");
         writeln!(
             out,
-            "  ::new (ptr) ::seastar::lw_shared_ptr<{}>(uninit);",
-            inner
+            "    ::new (ptr) ::seastar::shared_ptr<{}>(::seastar::make_shared<{}>(*self));",
+            inner, inner,
         );
-        writeln!(out, "  return uninit;");
+        writeln!(out, "  }} else {{");
+        writeln!(out, "    ::new (ptr) ::seastar::shared_ptr<{}>();", inner);
+        writeln!(out, "  }}");
         writeln!(out, "}}");
     }
     begin_function_definition(out);
     writeln!(
         out,
-        "void cxxbridge1$lw_shared_ptr${}$clone(::seastar::lw_shared_ptr<{}> const &self, ::seastar::lw_shared_ptr<{}> *ptr) noexcept {{",
-        instance, inner, inner,
-    );
-    writeln!(
-        out,
-        "  ::new (ptr) ::seastar::lw_shared_ptr<{}>(self);",
-        inner
+        "{} const *{}$get(::seastar::lw_shared_ptr<{}> const &self) noexcept {{",
+        inner, instance, inner,
     );
+    writeln!(out, "  return self.get();");
     writeln!(out, "}}");
     begin_function_definition(out);
     writeln!(
         out,
-        "{} const *cxxbridge1$lw_shared_ptr${}$get(::seastar::lw_shared_ptr<{}> const &self) noexcept {{",
-        inner, instance, inner,
+        "::std::size_t {}$use_count(::seastar::lw_shared_ptr<{}> const &self) noexcept {{",
+        instance, inner,
     );
-    writeln!(out, "  return self.get();");
+    writeln!(out, "  return self.use_count();");
     writeln!(out, "}}");
     begin_function_definition(out);
     writeln!(
         out,
-        "void cxxbridge1$lw_shared_ptr${}$drop(::seastar::lw_shared_ptr<{}> *self) noexcept {{",
+        "void {}$drop(::seastar::lw_shared_ptr<{}> *self) noexcept {{",
         instance, inner,
     );
     writeln!(out, "  self->~lw_shared_ptr();");
@@ -1844,10 +2174,11 @@ fn write_seastar_shared_ptr(out: &mut OutFile, key: NamedImplKey) {
     let ident = key.rust;
     let resolve = out.types.resolve(ident);
     let inner = resolve.name.to_fully_qualified();
-    let instance = resolve.name.to_symbol();
+    let instance = mangle::seastar_shared_ptr(resolve.name);
 
     out.include.new = true;
     out.include.utility = true;
+    out.include.cstddef = true;
 
     // Some aliases are to opaque types; some are to trivial types. We can't
     // know at code generation time, so we generate both C++ and Rust side
@@ -1868,7 +2199,7 @@ fn write_seastar_shared_ptr(out: &mut OutFile, key: NamedImplKey) {
     begin_function_definition(out);
     writeln!(
         out,
-        "void cxxbridge1$seastar_shared_ptr${}$null(::seastar::shared_ptr<{}> *ptr) noexcept {{",
+        "void {}$null(::seastar::shared_ptr<{}> *ptr) noexcept {{",
         instance, inner,
     );
     writeln!(out, "  ::new (ptr) ::seastar::shared_ptr<{}>();", inner);
@@ -1878,7 +2209,7 @@ fn write_seastar_shared_ptr(out: &mut OutFile, key: NamedImplKey) {
         begin_function_definition(out);
         writeln!(
             out,
-            "{} *cxxbridge1$seastar_shared_ptr${}$uninit(::seastar::shared_ptr<{}> *ptr) noexcept {{",
+            "{} *{}$uninit(::seastar::shared_ptr<{}> *ptr) noexcept {{",
             inner, instance, inner,
         );
         writeln!(
@@ -1893,11 +2224,27 @@ fn write_seastar_shared_ptr(out: &mut OutFile, key: NamedImplKey) {
         );
         writeln!(out, "  return uninit;");
         writeln!(out, "}}");
+        begin_function_definition(out);
+        writeln!(
+            out,
+            "{} *{}$uninit_with_deleter(void (*call)(void *, {} *), void *deleter, ::seastar::shared_ptr<{}> *ptr) noexcept {{",
+            inner, instance, inner, inner,
+        );
+        write_seastar_shared_ptr_with_deleter_body(out, &inner);
+        writeln!(out, "}}");
+        begin_function_definition(out);
+        writeln!(
+            out,
+            "bool {}$take_unique(::seastar::shared_ptr<{}> *self, {} *value) noexcept {{",
+            instance, inner, inner,
+        );
+        write_seastar_shared_ptr_take_unique_body(out, &inner);
+        writeln!(out, "}}");
     }
     begin_function_definition(out);
     writeln!(
         out,
-        "void cxxbridge1$seastar_shared_ptr${}$clone(::seastar::shared_ptr<{}> const &self, ::seastar::shared_ptr<{}> *ptr) noexcept {{",
+        "void {}$clone(::seastar::shared_ptr<{}> const &self, ::seastar::shared_ptr<{}> *ptr) noexcept {{",
         instance, inner, inner,
     );
     writeln!(out, "  ::new (ptr) ::seastar::shared_ptr<{}>(self);", inner);
@@ -1905,7 +2252,7 @@ fn write_seastar_shared_ptr(out: &mut OutFile, key: NamedImplKey) {
     begin_function_definition(out);
     writeln!(
         out,
-        "{} const *cxxbridge1$seastar_shared_ptr${}$get(::seastar::shared_ptr<{}> const &self) noexcept {{",
+        "{} const *{}$get(::seastar::shared_ptr<{}> const &self) noexcept {{",
         inner, instance, inner,
     );
     writeln!(out, "  return self.get();");
@@ -1913,11 +2260,106 @@ fn write_seastar_shared_ptr(out: &mut OutFile, key: NamedImplKey) {
     begin_function_definition(out);
     writeln!(
         out,
-        "void cxxbridge1$seastar_shared_ptr${}$drop(::seastar::shared_ptr<{}> *self) noexcept {{",
+        "void {}$from_std(::std::shared_ptr<{}> const &src, ::seastar::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner, inner,
+    );
+    write_seastar_shared_ptr_from_std_body(out, &inner);
+    writeln!(out, "}}");
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "::std::size_t {}$use_count(::seastar::shared_ptr<{}> const &self) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  return self.use_count();");
+    writeln!(out, "}}");
+    begin_function_definition(out);
+    writeln!(
+        out,
+        "void {}$drop(::seastar::shared_ptr<{}> *self) noexcept {{",
         instance, inner,
     );
     writeln!(out, "  self->~shared_ptr();");
     writeln!(out, "}}");
+    if out.types.shared_from_this.contains(ident) {
+        begin_function_definition(out);
+        writeln!(
+            out,
+            "void {}$shared_from_this({} const &self, ::seastar::shared_ptr<{}> *ptr) noexcept {{",
+            instance, inner, inner,
+        );
+        writeln!(
+            out,
+            "  ::new (ptr) ::seastar::shared_ptr<{}>(const_cast<{} &>(self).shared_from_this());",
+            inner, inner,
+        );
+        writeln!(out, "}}");
+    }
+    if let Some(bases) = out.types.seastar_upcasts.get(ident) {
+        for base in bases.iter() {
+            let base_resolve = out.types.resolve(base);
+            let base_inner = base_resolve.name.to_fully_qualified();
+            let link_name = mangle::seastar_upcast(resolve.name, base_resolve.name);
+            // The converting constructor adjusts the pointer by the offset of
+            // the base subobject, as static_cast would under multiple
+            // inheritance, and refuses to compile for a class that is not
+            // an unambiguous public base.
+            begin_function_definition(out);
+            writeln!(
+                out,
+                "void {}(::seastar::shared_ptr<{}> *self, ::seastar::shared_ptr<{}> *ptr) noexcept {{",
+                link_name, inner, base_inner,
+            );
+            writeln!(
+                out,
+                "  ::new (ptr) ::seastar::shared_ptr<{}>(::std::move(*self));",
+                base_inner,
+            );
+            writeln!(out, "  self->~shared_ptr();");
+            writeln!(out, "}}");
+        }
+    }
+}
+
+// seastar::shared_ptr has no aliasing constructor, so the std object goes in a
+// holder that the pointer is repointed past; see the helper in cxx.h.
+fn write_seastar_shared_ptr_from_std_body(out: &mut OutFile, inner: &str) {
+    out.include.memory = true;
+    out.builtin.seastar_shared_ptr = true;
+    writeln!(
+        out,
+        "  ::rust::detail::seastar_shared_ptr_from_std(src, ptr, [](::std::shared_ptr<{0}> const &inner) {{ return ::seastar::make_shared<::rust::detail::seastar_shared_ptr_std_holder<::std::shared_ptr<{0}>>>(inner); }});",
+        inner,
+    );
+}
+
+// seastar::shared_ptr takes no deleter, so the object goes in a holder that
+// calls it instead; see the helper in cxx.h.
+fn write_seastar_shared_ptr_with_deleter_body(out: &mut OutFile, inner: &str) {
+    out.builtin.seastar_shared_ptr = true;
+    writeln!(
+        out,
+        "  return ::rust::detail::seastar_shared_ptr_uninit_with_deleter(::seastar::make_shared<::rust::detail::seastar_shared_ptr_deleter<{}>>(), call, deleter, ptr);",
+        inner,
+    );
+}
+
+// Only a pointer made by seastar::make_shared, either from C++ or by the
+// uninit shim, hands its object over; see the helper in cxx.h.
+fn write_seastar_shared_ptr_take_unique_body(out: &mut OutFile, inner: &str) {
+    out.builtin.maybe_uninit = true;
+    out.builtin.seastar_shared_ptr = true;
+    writeln!(out, "  void **words = reinterpret_cast<void **>(self);");
+    writeln!(
+        out,
+        "  return ::rust::detail::seastar_shared_ptr_take_unique<::seastar::shared_ptr_count_base, ::seastar::shared_ptr_count_for<{0}>>(words, value) ||",
+        inner,
+    );
+    writeln!(
+        out,
+        "         ::rust::detail::seastar_shared_ptr_take_unique<::seastar::shared_ptr_count_base, ::seastar::shared_ptr_count_for<::rust::MaybeUninit<{0}>>>(words, value);",
+        inner,
+    );
 }
 
 fn write_shared_ptr(out: &mut OutFile, key: NamedImplKey) {
@@ -20,6 +20,13 @@
 #include <sys/types.h>
 #endif
 
+namespace seastar {
+template <typename T>
+class lw_shared_ptr;
+template <typename T>
+class shared_ptr;
+} // namespace seastar
+
 namespace rust {
 inline namespace cxxbridge1 {
 
@@ -1107,5 +1114,208 @@ struct IsRelocatable
                         std::is_trivially_destructible<T>::value>>::type {};
 #endif // CXXBRIDGE1_RELOCATABLE
 
+#ifndef CXXBRIDGE1_SEASTAR_PTR
+#define CXXBRIDGE1_SEASTAR_PTR
+namespace detail {
+// The bodies of the shims for seastar::lw_shared_ptr and seastar::shared_ptr,
+// shared by the ones src/cxx.cc defines for primitive targets and the ones a
+// bridge defines for its own. Seastar is only forward declared here, so
+// whatever needs one of its factories is handed a `make` callable.
+template <typename Ptr>
+void seastar_ptr_null(Ptr *self) noexcept {
+  ::new (self) Ptr();
+}
+
+template <typename Ptr>
+void seastar_ptr_clone(const Ptr &self, Ptr *ptr) noexcept {
+  ::new (ptr) Ptr(self);
+}
+
+template <typename Ptr>
+auto seastar_ptr_get(const Ptr &self) noexcept -> decltype(self.get()) {
+  return self.get();
+}
+
+template <typename Ptr>
+std::size_t seastar_ptr_use_count(const Ptr &self) noexcept {
+  return self.use_count();
+}
+
+template <typename Ptr>
+void seastar_ptr_drop(Ptr *self) noexcept {
+  self->~Ptr();
+}
+
+// Moves the pointer to uninitialized storage returned by `make` into the one
+// at `self`, and returns that storage for a T to be constructed in. `make`
+// returns a pointer to rust::MaybeUninit<T>, which has the same layout.
+template <typename T, typename Ptr, typename Make>
+T *seastar_ptr_uninit(Ptr *self, Make make) noexcept {
+  auto uninit = make();
+  T *value = reinterpret_cast<T *>(uninit.get());
+  ::new (self) decltype(uninit)(std::move(uninit));
+  return value;
+}
+
+// Backs the from_str shims, `make` being the string factory of the pointer.
+template <typename Ptr, typename Make>
+void seastar_ptr_from_str(Ptr *self, Make make, const std::uint8_t *s,
+                          std::size_t len) noexcept {
+  ::new (self) Ptr(make(reinterpret_cast<const char *>(s), len));
+}
+} // namespace detail
+#endif // CXXBRIDGE1_SEASTAR_PTR
+
+#ifndef CXXBRIDGE1_SEASTAR_LW_SHARED_PTR
+#define CXXBRIDGE1_SEASTAR_LW_SHARED_PTR
+namespace detail {
+// Both pointers keep the count in a block in front of the object, unless T
+// derives from Esft, seastar::enable_lw_shared_from_this<T>, in which case the
+// count lives inside T and no uninitialized storage can stand in for it. Only
+// this construction is refused for those types; pointers to them handed over
+// from C++ work like any other.
+template <typename Esft, template <typename> class LwSharedPtr, typename T,
+          typename Make>
+typename std::enable_if<!std::is_base_of<Esft, T>::value, T *>::type
+seastar_lw_shared_ptr_uninit(LwSharedPtr<T> *self, Make make) noexcept {
+  return seastar_ptr_uninit<T>(self, make);
+}
+
+template <typename Esft, template <typename> class LwSharedPtr, typename T,
+          typename Make>
+typename std::enable_if<std::is_base_of<Esft, T>::value, T *>::type
+seastar_lw_shared_ptr_uninit(LwSharedPtr<T> *, Make) noexcept {
+  panic<std::invalid_argument>(
+      "SeastarLwSharedPtr cannot construct a type deriving from "
+      "seastar::enable_lw_shared_from_this");
+}
+
+// Copies the object into a new seastar::shared_ptr made by `make`, or leaves
+// that null if the lw_shared_ptr is.
+template <typename LwSharedPtr, typename SharedPtr, typename Make>
+void seastar_lw_shared_ptr_to_shared(const LwSharedPtr &self, SharedPtr *ptr,
+                                     Make make) noexcept {
+  if (self) {
+    ::new (ptr) SharedPtr(make(*self));
+  } else {
+    ::new (ptr) SharedPtr();
+  }
+}
+} // namespace detail
+#endif // CXXBRIDGE1_SEASTAR_LW_SHARED_PTR
+
+#ifndef CXXBRIDGE1_SEASTAR_SHARED_PTR
+#define CXXBRIDGE1_SEASTAR_SHARED_PTR
+namespace detail {
+// Moves the object out of a seastar::shared_ptr, given as its two words, if
+// the pointer is its only owner and its count block is a Block whose `_data`
+// member is the object. The block is then freed without running any
+// destructor, the object having been moved out bitwise, and the pointer is
+// left null. Any other block, such as one calling a deleter or holding a
+// std::shared_ptr, owns the object in a way that cannot be handed over.
+//
+// None of this is seastar API. It was written against seastar 22.11, where
+// CountBase is seastar::shared_ptr_count_base, a polymorphic struct with the
+// reference count in a public `count`; Block is seastar::shared_ptr_count_for,
+// which holds the object in a public `_data`; and such blocks are allocated by
+// a plain new expression. The static_asserts and the member names stop the
+// build if the first two change. Nothing checks the third, so the block is
+// only freed after the checks below have matched it exactly.
+template <typename CountBase, typename Block, typename T>
+bool seastar_shared_ptr_take_unique(void **self, T *value) noexcept {
+  static_assert(std::is_polymorphic<CountBase>::value,
+                "seastar::shared_ptr_count_base is no longer polymorphic");
+  static_assert(std::is_base_of<CountBase, Block>::value,
+                "seastar::shared_ptr_count_for no longer derives from "
+                "seastar::shared_ptr_count_base");
+  static_assert(sizeof(Block::_data) == sizeof(T),
+                "seastar::shared_ptr_count_for no longer holds just the object "
+                "in _data");
+  auto *block = dynamic_cast<Block *>(static_cast<CountBase *>(self[0]));
+  if (block == nullptr || block->count != 1 ||
+      static_cast<void *>(&block->_data) != self[1]) {
+    return false;
+  }
+  std::copy_n(static_cast<const unsigned char *>(self[1]), sizeof(T),
+              reinterpret_cast<unsigned char *>(value));
+#if defined(__cpp_aligned_new)
+  if (alignof(Block) > __STDCPP_DEFAULT_NEW_ALIGNMENT__) {
+    ::operator delete(block, std::align_val_t(alignof(Block)));
+  } else
+#endif
+  {
+    ::operator delete(block);
+  }
+  self[0] = nullptr;
+  self[1] = nullptr;
+  return true;
+}
+
+// seastar::shared_ptr takes no deleter, so an object that has one is placed in
+// raw storage inside this holder, whose destructor calls the deleter in place
+// of the object's destructor. The deleter is opaque to C++ and is handed back
+// to `call` along with the object.
+template <typename T>
+struct seastar_shared_ptr_deleter {
+  alignas(T) unsigned char storage[sizeof(T)];
+  void (*call)(void *, T *);
+  void *deleter;
+  ~seastar_shared_ptr_deleter() {
+    call(deleter, reinterpret_cast<T *>(storage));
+  }
+};
+
+// Moves a holder just made by seastar::make_shared into the seastar::shared_ptr
+// at `self`, and returns the storage for the object to be written into. There
+// is no aliasing constructor, so the pointer is repointed at the storage by
+// hand afterwards.
+template <template <typename> class SharedPtr, typename T>
+T *seastar_shared_ptr_uninit_with_deleter(
+    SharedPtr<seastar_shared_ptr_deleter<T>> holder, void (*call)(void *, T *),
+    void *deleter, void *self) noexcept {
+  holder->call = call;
+  holder->deleter = deleter;
+  T *uninit = reinterpret_cast<T *>(holder->storage);
+  ::new (self) SharedPtr<seastar_shared_ptr_deleter<T>>(std::move(holder));
+  static_cast<void **>(self)[1] = uninit;
+  return uninit;
+}
+
+// seastar::shared_ptr has no aliasing constructor, so one sharing a
+// std::shared_ptr is built as a seastar::shared_ptr to this holder, made by
+// `make`, and then repointed at the std object, the second of its two words.
+// Dropping the last seastar reference destroys the holder through the virtual
+// destructor of the count base, releasing the std one.
+template <typename StdSharedPtr>
+struct seastar_shared_ptr_std_holder {
+  explicit seastar_shared_ptr_std_holder(const StdSharedPtr &inner)
+      : inner(inner) {}
+  StdSharedPtr inner;
+};
+
+template <template <typename> class SharedPtr, typename T,
+          typename StdSharedPtr, typename Make>
+void seastar_shared_ptr_from_std(const StdSharedPtr &src, SharedPtr<T> *self,
+                                 Make make) noexcept {
+  if (!src) {
+    ::new (self) SharedPtr<T>();
+    return;
+  }
+  ::new (self) SharedPtr<seastar_shared_ptr_std_holder<StdSharedPtr>>(
+      make(src));
+  static_cast<void **>(static_cast<void *>(self))[1] =
+      const_cast<void *>(static_cast<const void *>(src.get()));
+}
+} // namespace detail
+#endif // CXXBRIDGE1_SEASTAR_SHARED_PTR
+
+// Seastar's smart pointers hold nothing but pointers to their object and its
+// reference count, so they stay valid after Rust moves them. This lets them,
+// and Rust newtypes around them, be passed by value.
+template <typename T>
+struct IsRelocatable<::seastar::lw_shared_ptr<T>> : std::true_type {};
+template <typename T>
+struct IsRelocatable<::seastar::shared_ptr<T>> : std::true_type {};
+
 } // namespace cxxbridge1
 } // namespace rust
@@ -73,6 +73,7 @@ fn expand(ffi: Module, doc: Doc, attrs: OtherAttrs, apis: &[Api], types: &Types)
                 let ident = &ety.name.rust;
                 if !types.structs.contains_key(ident) && !types.enums.contains_key(ident) {
                     expanded.extend(expand_cxx_type(ety));
+                    expanded.extend(expand_cxx_type_seastar_upcasts(ety, types));
                     hidden.extend(expand_cxx_type_assert_pinned(ety, types));
                 }
             }
@@ -99,6 +100,13 @@ fn expand(ffi: Module, doc: Doc, attrs: OtherAttrs, apis: &[Api], types: &Types)
             ImplKey::RustVec(ident) => {
                 hidden.extend(expand_rust_vec(ident, types, explicit_impl));
             }
+            ImplKey::RustVecSeastarLwSharedPtr(ident) => {
+                hidden.extend(expand_rust_vec_seastar_lw_shared_ptr(
+                    ident,
+                    types,
+                    explicit_impl,
+                ));
+            }
             ImplKey::UniquePtr(ident) => {
                 expanded.extend(expand_unique_ptr(ident, types, explicit_impl));
             }
@@ -402,6 +410,11 @@ fn expand_cxx_type(ety: &ExternType) -> TokenStream {
         }
     };
 
+    let shared_from_this = ety
+        .enable_shared_from_this
+        .as_ref()
+        .map(|_| expand_cxx_type_shared_from_this(ety));
+
     quote! {
         #doc
         #attrs
@@ -414,9 +427,79 @@ fn expand_cxx_type(ety: &ExternType) -> TokenStream {
             type Id = #type_id;
             type Kind = ::cxx::kind::Opaque;
         }
+
+        #shared_from_this
     }
 }
 
+fn expand_cxx_type_shared_from_this(ety: &ExternType) -> TokenStream {
+    let ident = &ety.name.rust;
+    let generics = &ety.generics;
+    let visibility = &ety.visibility;
+    let link_name = format!("{}$shared_from_this", mangle::seastar_shared_ptr(&ety.name),);
+
+    quote! {
+        impl #generics #ident #generics {
+            /// Returns a new SeastarSharedPtr sharing ownership of this object
+            /// with the seastar::shared\_ptr that already owns it.
+            ///
+            /// Matches the behavior of
+            /// seastar::enable\_shared\_from\_this::shared\_from\_this.
+            ///
+            /// # Safety
+            ///
+            /// The object must have been created by C++ `seastar::make_shared`,
+            /// which keeps the reference count in the object itself, and must
+            /// still be owned by a seastar::shared\_ptr. A SeastarSharedPtr from
+            /// `SeastarSharedPtr::from_std_shared` or
+            /// `SeastarSharedPtr::new_with_deleter` does not qualify: it counts
+            /// its owners in a separate block, so the object's own count is
+            /// still zero and the pointer returned here would delete the object
+            /// a second time. Calling this on an object owned in any other way,
+            /// for example on the stack or by a UniquePtr, is undefined
+            /// behavior as well.
+            #visibility unsafe fn shared_from_this(&self) -> ::cxx::SeastarSharedPtr<Self> {
+                extern "C" {
+                    #[link_name = #link_name]
+                    fn __shared_from_this(this: *const ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void);
+                }
+                let mut shared_ptr = ::cxx::core::mem::MaybeUninit::<::cxx::SeastarSharedPtr<Self>>::uninit();
+                let this = self as *const Self as *const ::cxx::core::ffi::c_void;
+                unsafe {
+                    __shared_from_this(this, shared_ptr.as_mut_ptr().cast());
+                    shared_ptr.assume_init()
+                }
+            }
+        }
+    }
+}
+
+fn expand_cxx_type_seastar_upcasts(ety: &ExternType, types: &Types) -> TokenStream {
+    let upcast = match &ety.seastar_upcast {
+        Some(upcast) => upcast,
+        None => return TokenStream::new(),
+    };
+    let ident = &ety.name.rust;
+    let generics = &ety.generics;
+
+    let mut expanded = TokenStream::new();
+    for base in &upcast.bases {
+        let link_name = mangle::seastar_upcast(&ety.name, types.resolve(base).name).to_string();
+        expanded.extend(quote_spanned! {base.span()=>
+            unsafe impl #generics ::cxx::private::SeastarUpcast<#base> for #ident #generics {
+                unsafe fn __upcast(this: *mut ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void) {
+                    extern "C" {
+                        #[link_name = #link_name]
+                        fn __upcast(this: *mut ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void);
+                    }
+                    __upcast(this, new);
+                }
+            }
+        });
+    }
+    expanded
+}
+
 fn expand_cxx_type_assert_pinned(ety: &ExternType, types: &Types) -> TokenStream {
     let ident = &ety.name.rust;
     let infer = Token![_](ident.span());
@@ -676,6 +759,9 @@ fn expand_cxx_function_shim(efn: &ExternFn, types: &Types) -> TokenStream {
                         quote_spanned!(span=> ::cxx::UniquePtr::from_raw(#call))
                     }
                 }
+                Type::SeastarFuture(_) => {
+                    quote_spanned!(span=> ::cxx::SeastarFuture::from_raw(#call))
+                }
                 Type::Ref(ty) => match &ty.inner {
                     Type::Ident(ident) if ident.rust == RustString => match ty.mutable {
                         false => quote_spanned!(span=> #call.as_string()),
@@ -1067,6 +1153,7 @@ fn expand_rust_function_shim_impl(
             }
         }
         Type::UniquePtr(_) => Some(quote_spanned!(span=> ::cxx::UniquePtr::into_raw)),
+        Type::SeastarFuture(_) => Some(quote_spanned!(span=> ::cxx::SeastarFuture::into_raw)),
         Type::Ref(ty) => match &ty.inner {
             Type::Ident(ident) if ident.rust == RustString => match ty.mutable {
                 false => Some(quote_spanned!(span=> ::cxx::private::RustString::from_ref)),
@@ -1236,9 +1323,9 @@ fn expand_type_alias_verify(alias: &TypeAlias, types: &Types) -> TokenStream {
     };
 
     if types.required_trivial.contains_key(&alias.name.rust) {
-        let begin = quote_spanned!(begin_span=> ::cxx::private::verify_extern_kind::<);
+        let begin = quote_spanned!(begin_span=> ::cxx::private::verify_extern_by_value::<);
         verify.extend(quote! {
-            const _: fn() = #begin #ident, ::cxx::kind::Trivial #end;
+            const _: fn() = #begin #ident #end;
         });
     }
 
@@ -1306,6 +1393,71 @@ fn expand_rust_vec(key: NamedImplKey, types: &Types, explicit_impl: Option<&Impl
     let elem = key.rust;
     let resolve = types.resolve(elem);
     let link_prefix = format!("cxxbridge1$rust_vec${}$", resolve.name.to_symbol());
+    let local_prefix = format_ident!("{}__vec_", elem);
+    let prevent_unwind_drop_label = format!("::{} as Drop>::drop", elem);
+
+    let (impl_generics, ty_generics) = generics::split_for_impl(key, explicit_impl, resolve);
+
+    let begin_span = explicit_impl.map_or(key.begin_span, |explicit| explicit.impl_token.span);
+    let end_span = explicit_impl.map_or(key.end_span, |explicit| explicit.brace_token.span);
+    let unsafe_token = format_ident!("unsafe", span = begin_span);
+
+    let shims = expand_rust_vec_shims(
+        quote!(#elem #ty_generics),
+        impl_generics.to_token_stream(),
+        &link_prefix,
+        &local_prefix,
+        &prevent_unwind_drop_label,
+        end_span,
+    );
+
+    quote_spanned! {end_span=>
+        #[doc(hidden)]
+        #unsafe_token impl #impl_generics ::cxx::private::ImplVec for #elem #ty_generics {}
+        #shims
+    }
+}
+
+// Vec<SeastarLwSharedPtr<T>> moves and drops its elements with Rust's Vec
+// like any other element type, since a SeastarLwSharedPtr is a single
+// relocatable pointer. Only the shims are needed, as SeastarLwSharedPtr<T>
+// is not local to the bridge and cannot implement ImplVec here.
+fn expand_rust_vec_seastar_lw_shared_ptr(
+    key: NamedImplKey,
+    types: &Types,
+    explicit_impl: Option<&Impl>,
+) -> TokenStream {
+    let ident = key.rust;
+    let resolve = types.resolve(ident);
+    let link_prefix = format!(
+        "cxxbridge1$rust_vec$seastar_lw_shared_ptr${}$",
+        resolve.name.to_symbol(),
+    );
+    let local_prefix = format_ident!("{}__vec_seastar_lw_shared_ptr_", ident);
+    let prevent_unwind_drop_label = format!("::SeastarLwSharedPtr<{}> as Drop>::drop", ident);
+
+    let (impl_generics, ty_generics) = generics::split_for_impl(key, explicit_impl, resolve);
+
+    let end_span = explicit_impl.map_or(key.end_span, |explicit| explicit.brace_token.span);
+
+    expand_rust_vec_shims(
+        quote!(::cxx::SeastarLwSharedPtr<#ident #ty_generics>),
+        impl_generics.to_token_stream(),
+        &link_prefix,
+        &local_prefix,
+        &prevent_unwind_drop_label,
+        end_span,
+    )
+}
+
+fn expand_rust_vec_shims(
+    elem: TokenStream,
+    impl_generics: TokenStream,
+    link_prefix: &str,
+    local_prefix: &Ident,
+    prevent_unwind_drop_label: &str,
+    end_span: Span,
+) -> TokenStream {
     let link_new = format!("{}new", link_prefix);
     let link_drop = format!("{}drop", link_prefix);
     let link_len = format!("{}len", link_prefix);
@@ -1315,7 +1467,6 @@ fn expand_rust_vec(key: NamedImplKey, types: &Types, explicit_impl: Option<&Impl
     let link_set_len = format!("{}set_len", link_prefix);
     let link_truncate = format!("{}truncate", link_prefix);
 
-    let local_prefix = format_ident!("{}__vec_", elem);
     let local_new = format_ident!("{}new", local_prefix);
     let local_drop = format_ident!("{}drop", local_prefix);
     let local_len = format_ident!("{}len", local_prefix);
@@ -1325,61 +1476,52 @@ fn expand_rust_vec(key: NamedImplKey, types: &Types, explicit_impl: Option<&Impl
     let local_set_len = format_ident!("{}set_len", local_prefix);
     let local_truncate = format_ident!("{}truncate", local_prefix);
 
-    let (impl_generics, ty_generics) = generics::split_for_impl(key, explicit_impl, resolve);
-
-    let begin_span = explicit_impl.map_or(key.begin_span, |explicit| explicit.impl_token.span);
-    let end_span = explicit_impl.map_or(key.end_span, |explicit| explicit.brace_token.span);
-    let unsafe_token = format_ident!("unsafe", span = begin_span);
-    let prevent_unwind_drop_label = format!("::{} as Drop>::drop", elem);
-
     quote_spanned! {end_span=>
-        #[doc(hidden)]
-        #unsafe_token impl #impl_generics ::cxx::private::ImplVec for #elem #ty_generics {}
         #[doc(hidden)]
         #[export_name = #link_new]
-        unsafe extern "C" fn #local_new #impl_generics(this: *mut ::cxx::private::RustVec<#elem #ty_generics>) {
+        unsafe extern "C" fn #local_new #impl_generics(this: *mut ::cxx::private::RustVec<#elem>) {
             // No prevent_unwind: cannot panic.
             ::cxx::core::ptr::write(this, ::cxx::private::RustVec::new());
         }
         #[doc(hidden)]
         #[export_name = #link_drop]
-        unsafe extern "C" fn #local_drop #impl_generics(this: *mut ::cxx::private::RustVec<#elem #ty_generics>) {
+        unsafe extern "C" fn #local_drop #impl_generics(this: *mut ::cxx::private::RustVec<#elem>) {
             let __fn = concat!("<", module_path!(), #prevent_unwind_drop_label);
             ::cxx::private::prevent_unwind(__fn, || ::cxx::core::ptr::drop_in_place(this));
         }
         #[doc(hidden)]
         #[export_name = #link_len]
-        unsafe extern "C" fn #local_len #impl_generics(this: *const ::cxx::private::RustVec<#elem #ty_generics>) -> usize {
+        unsafe extern "C" fn #local_len #impl_generics(this: *const ::cxx::private::RustVec<#elem>) -> usize {
             // No prevent_unwind: cannot panic.
             (*this).len()
         }
         #[doc(hidden)]
         #[export_name = #link_capacity]
-        unsafe extern "C" fn #local_capacity #impl_generics(this: *const ::cxx::private::RustVec<#elem #ty_generics>) -> usize {
+        unsafe extern "C" fn #local_capacity #impl_generics(this: *const ::cxx::private::RustVec<#elem>) -> usize {
             // No prevent_unwind: cannot panic.
             (*this).capacity()
         }
         #[doc(hidden)]
         #[export_name = #link_data]
-        unsafe extern "C" fn #local_data #impl_generics(this: *const ::cxx::private::RustVec<#elem #ty_generics>) -> *const #elem #ty_generics {
+        unsafe extern "C" fn #local_data #impl_generics(this: *const ::cxx::private::RustVec<#elem>) -> *const #elem {
             // No prevent_unwind: cannot panic.
             (*this).as_ptr()
         }
         #[doc(hidden)]
         #[export_name = #link_reserve_total]
-        unsafe extern "C" fn #local_reserve_total #impl_generics(this: *mut ::cxx::private::RustVec<#elem #ty_generics>, new_cap: usize) {
+        unsafe extern "C" fn #local_reserve_total #impl_generics(this: *mut ::cxx::private::RustVec<#elem>, new_cap: usize) {
             // No prevent_unwind: the global allocator is not allowed to panic.
             (*this).reserve_total(new_cap);
         }
         #[doc(hidden)]
         #[export_name = #link_set_len]
-        unsafe extern "C" fn #local_set_len #impl_generics(this: *mut ::cxx::private::RustVec<#elem #ty_generics>, len: usize) {
+        unsafe extern "C" fn #local_set_len #impl_generics(this: *mut ::cxx::private::RustVec<#elem>, len: usize) {
             // No prevent_unwind: cannot panic.
             (*this).set_len(len);
         }
         #[doc(hidden)]
         #[export_name = #link_truncate]
-        unsafe extern "C" fn #local_truncate #impl_generics(this: *mut ::cxx::private::RustVec<#elem #ty_generics>, len: usize) {
+        unsafe extern "C" fn #local_truncate #impl_generics(this: *mut ::cxx::private::RustVec<#elem>, len: usize) {
             let __fn = concat!("<", module_path!(), #prevent_unwind_drop_label);
             ::cxx::private::prevent_unwind(__fn, || (*this).truncate(len));
         }
@@ -1556,12 +1698,14 @@ fn expand_seastar_lw_shared_ptr(
     let ident = key.rust;
     let name = ident.to_string();
     let resolve = types.resolve(ident);
-    let prefix = format!("cxxbridge1$lw_shared_ptr${}$", resolve.name.to_symbol());
+    let prefix = format!("{}$", mangle::seastar_lw_shared_ptr(resolve.name));
     let link_null = format!("{}null", prefix);
     let link_uninit = format!("{}uninit", prefix);
     let link_clone = format!("{}clone", prefix);
     let link_get = format!("{}get", prefix);
+    let link_use_count = format!("{}use_count", prefix);
     let link_drop = format!("{}drop", prefix);
+    let link_to_shared = format!("{}to_shared", prefix);
 
     let (impl_generics, ty_generics) = generics::split_for_impl(key, explicit_impl, resolve);
 
@@ -1569,11 +1713,20 @@ fn expand_seastar_lw_shared_ptr(
     let new_method = if can_construct_from_value {
         Some(quote! {
             unsafe fn __new(value: Self, new: *mut ::cxx::core::ffi::c_void) {
+                Self::__uninit(new).cast::<#ident #ty_generics>().write(value);
+            }
+        })
+    } else {
+        None
+    };
+    let to_shared_method = if can_construct_from_value {
+        Some(quote! {
+            unsafe fn __to_shared(this: *const ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void) {
                 extern "C" {
-                    #[link_name = #link_uninit]
-                    fn __uninit(new: *mut ::cxx::core::ffi::c_void) -> *mut ::cxx::core::ffi::c_void;
+                    #[link_name = #link_to_shared]
+                    fn __to_shared(this: *const ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void);
                 }
-                __uninit(new).cast::<#ident #ty_generics>().write(value);
+                __to_shared(this, new);
             }
         })
     } else {
@@ -1596,6 +1749,13 @@ fn expand_seastar_lw_shared_ptr(
                 }
                 __null(new);
             }
+            unsafe fn __uninit(new: *mut ::cxx::core::ffi::c_void) -> *mut ::cxx::core::ffi::c_void {
+                extern "C" {
+                    #[link_name = #link_uninit]
+                    fn __uninit(new: *mut ::cxx::core::ffi::c_void) -> *mut ::cxx::core::ffi::c_void;
+                }
+                __uninit(new)
+            }
             #new_method
             unsafe fn __clone(this: *const ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void) {
                 extern "C" {
@@ -1604,6 +1764,7 @@ fn expand_seastar_lw_shared_ptr(
                 }
                 __clone(this, new);
             }
+            #to_shared_method
             unsafe fn __get(this: *const ::cxx::core::ffi::c_void) -> *const Self {
                 extern "C" {
                     #[link_name = #link_get]
@@ -1611,6 +1772,13 @@ fn expand_seastar_lw_shared_ptr(
                 }
                 __get(this).cast()
             }
+            unsafe fn __use_count(this: *const ::cxx::core::ffi::c_void) -> usize {
+                extern "C" {
+                    #[link_name = #link_use_count]
+                    fn __use_count(this: *const ::cxx::core::ffi::c_void) -> usize;
+                }
+                __use_count(this)
+            }
             unsafe fn __drop(this: *mut ::cxx::core::ffi::c_void) {
                 extern "C" {
                     #[link_name = #link_drop]
@@ -1630,11 +1798,15 @@ fn expand_seastar_shared_ptr(
     let ident = key.rust;
     let name = ident.to_string();
     let resolve = types.resolve(ident);
-    let prefix = format!("cxxbridge1$shared_ptr${}$", resolve.name.to_symbol());
+    let prefix = format!("{}$", mangle::seastar_shared_ptr(resolve.name));
     let link_null = format!("{}null", prefix);
     let link_uninit = format!("{}uninit", prefix);
+    let link_uninit_with_deleter = format!("{}uninit_with_deleter", prefix);
+    let link_take_unique = format!("{}take_unique", prefix);
     let link_clone = format!("{}clone", prefix);
     let link_get = format!("{}get", prefix);
+    let link_from_std = format!("{}from_std", prefix);
+    let link_use_count = format!("{}use_count", prefix);
     let link_drop = format!("{}drop", prefix);
 
     let (impl_generics, ty_generics) = generics::split_for_impl(key, explicit_impl, resolve);
@@ -1649,6 +1821,21 @@ fn expand_seastar_shared_ptr(
                 }
                 __uninit(new).cast::<#ident #ty_generics>().write(value);
             }
+            unsafe fn __new_with_deleter(value: Self, call: unsafe extern "C" fn(*mut ::cxx::core::ffi::c_void, *mut Self), deleter: *mut ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void) {
+                extern "C" {
+                    #[link_name = #link_uninit_with_deleter]
+                    fn __uninit_with_deleter(call: unsafe extern "C" fn(*mut ::cxx::core::ffi::c_void, *mut ::cxx::core::ffi::c_void), deleter: *mut ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void) -> *mut ::cxx::core::ffi::c_void;
+                }
+                let call = ::cxx::core::mem::transmute::<unsafe extern "C" fn(*mut ::cxx::core::ffi::c_void, *mut Self), unsafe extern "C" fn(*mut ::cxx::core::ffi::c_void, *mut ::cxx::core::ffi::c_void)>(call);
+                __uninit_with_deleter(call, deleter, new).cast::<#ident #ty_generics>().write(value);
+            }
+            unsafe fn __take_unique(this: *mut ::cxx::core::ffi::c_void, value: *mut Self) -> bool {
+                extern "C" {
+                    #[link_name = #link_take_unique]
+                    fn __take_unique(this: *mut ::cxx::core::ffi::c_void, value: *mut ::cxx::core::ffi::c_void) -> bool;
+                }
+                __take_unique(this, value.cast())
+            }
         })
     } else {
         None
@@ -1685,6 +1872,20 @@ fn expand_seastar_shared_ptr(
                 }
                 __get(this).cast()
             }
+            unsafe fn __from_std(src: *const ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void) {
+                extern "C" {
+                    #[link_name = #link_from_std]
+                    fn __from_std(src: *const ::cxx::core::ffi::c_void, new: *mut ::cxx::core::ffi::c_void);
+                }
+                __from_std(src, new);
+            }
+            unsafe fn __use_count(this: *const ::cxx::core::ffi::c_void) -> usize {
+                extern "C" {
+                    #[link_name = #link_use_count]
+                    fn __use_count(this: *const ::cxx::core::ffi::c_void) -> usize;
+                }
+                __use_count(this)
+            }
             unsafe fn __drop(this: *mut ::cxx::core::ffi::c_void) {
                 extern "C" {
                     #[link_name = #link_drop]
@@ -1914,6 +2115,10 @@ fn expand_extern_type(ty: &Type, types: &Types, proper: bool) -> TokenStream {
                 quote_spanned!(span=> *mut #inner)
             }
         }
+        Type::SeastarFuture(ty) => {
+            let span = ty.name.span();
+            quote_spanned!(span=> *mut ::cxx::core::ffi::c_void)
+        }
         Type::RustVec(ty) => {
             let span = ty.name.span();
             let langle = ty.langle;
@@ -1,8 +1,27 @@
 #include "../include/cxx.h"
 #include <cstring>
+#include <deque>
 #include <iostream>
 #include <memory>
+#include <seastar/core/abort_source.hh>
+#include <seastar/core/chunked_fifo.hh>
+#include <seastar/core/circular_buffer.hh>
+#include <seastar/core/condition-variable.hh>
+#include <seastar/core/file.hh>
+#include <seastar/core/future.hh>
+#include <seastar/core/gate.hh>
+#include <seastar/core/iostream.hh>
+#include <seastar/core/metrics.hh>
+#include <seastar/core/rwlock.hh>
+#include <seastar/core/seastar.hh>
+#include <seastar/core/semaphore.hh>
 #include <seastar/core/shared_ptr.hh>
+#include <seastar/core/sleep.hh>
+#include <seastar/core/smp.hh>
+#include <seastar/core/temporary_buffer.hh>
+#include <seastar/core/timer.hh>
+#include <seastar/net/inet_address.hh>
+#include <seastar/net/socket_defs.hh>
 
 extern "C" {
 void cxxbridge1$cxx_string$init(std::string *s, const std::uint8_t *ptr,
@@ -85,6 +104,7 @@ void panic [[noreturn]] (const char *msg) {
 }
 
 template void panic<std::out_of_range> [[noreturn]] (const char *msg);
+template void panic<std::invalid_argument> [[noreturn]] (const char *msg);
 
 template <typename T>
 static bool is_aligned(const void *ptr) noexcept {
@@ -559,6 +579,57 @@ template <typename T>
 void destroy(T *ptr) {
   ptr->~T();
 }
+
+rust::repr::PtrLen seastar_exception_what(std::exception_ptr ex) noexcept {
+  rust::repr::PtrLen what;
+  rust::detail::Fail fail(what);
+  try {
+    std::rethrow_exception(std::move(ex));
+  } catch (const std::exception &e) {
+    fail(e.what());
+  } catch (...) {
+    fail("unknown exception");
+  }
+  return what;
+}
+
+[[noreturn]] void seastar_rethrow_panic(rust::repr::PtrLen panic) {
+  // The message is a NUL terminated copy made by cxxbridge1$error.
+  std::runtime_error error(static_cast<const char *>(panic.ptr));
+  delete[] static_cast<char *>(panic.ptr);
+  throw error;
+}
+
+// seastar::smp::submit_to indexes its queues by shard without checking it,
+// so the shims refuse a shard out of range before getting there, and have
+// the trampoline drop the closure unrun by passing it no result slot. The
+// current shard is always accepted, as smp::submit_to runs the closure in
+// place for it without touching the queues.
+template <typename T>
+bool seastar_submit_to_refuse(unsigned shard, void *data,
+                              bool (*call)(void *, T *, rust::repr::PtrLen *),
+                              std::exception_ptr *error) noexcept {
+  if (shard == seastar::this_shard_id() || shard < seastar::smp::count) {
+    return false;
+  }
+  call(data, nullptr, nullptr);
+  *error = std::make_exception_ptr(std::out_of_range(
+      "seastar_submit_to: shard " + std::to_string(shard) +
+      " is out of range for " + std::to_string(seastar::smp::count) +
+      " shards"));
+  return true;
+}
+
+template <typename T>
+T seastar_submit_to_call(void *data,
+                         bool (*call)(void *, T *, rust::repr::PtrLen *)) {
+  T value;
+  rust::repr::PtrLen panic;
+  if (!call(data, &value, &panic)) {
+    seastar_rethrow_panic(panic);
+  }
+  return value;
+}
 } // namespace
 
 extern "C" {
@@ -592,6 +663,126 @@ static_assert(sizeof(std::string) <= kMaxExpectedWordsInString * sizeof(void *),
               "unexpectedly large std::string size");
 } // namespace
 
+namespace {
+// The from_std shims, for primitive and vector targets; see the helper in
+// cxx.h.
+template <typename T>
+void seastar_shared_ptr_from_std(const std::shared_ptr<T> &src,
+                                 seastar::shared_ptr<T> *ptr) noexcept {
+  using holder =
+      rust::detail::seastar_shared_ptr_std_holder<std::shared_ptr<T>>;
+  rust::detail::seastar_shared_ptr_from_std(
+      src, ptr, [](const std::shared_ptr<T> &inner) {
+        return seastar::make_shared<holder>(inner);
+      });
+}
+} // namespace
+
+namespace {
+// The two ends of an in-memory stream made by cxxbridge1$seastar$pipe$new
+// share the buffers written but not yet read, in the order they were put.
+struct seastar_pipe_state {
+  std::deque<seastar::temporary_buffer<char>> buffers;
+  bool closed = false;
+  seastar::condition_variable readable;
+};
+
+class seastar_pipe_sink final : public seastar::data_sink_impl {
+public:
+  explicit seastar_pipe_sink(seastar::lw_shared_ptr<seastar_pipe_state> state)
+      : state(std::move(state)) {}
+
+  using seastar::data_sink_impl::put;
+
+  seastar::future<> put(seastar::net::packet data) override {
+    for (auto &buf : data.release()) {
+      // An empty buffer would read as the end of the stream.
+      if (!buf.empty()) {
+        this->state->buffers.push_back(std::move(buf));
+      }
+    }
+    this->state->readable.broadcast();
+    return seastar::make_ready_future<>();
+  }
+
+  seastar::future<> flush() override { return seastar::make_ready_future<>(); }
+
+  seastar::future<> close() override {
+    this->state->closed = true;
+    this->state->readable.broadcast();
+    return seastar::make_ready_future<>();
+  }
+
+private:
+  seastar::lw_shared_ptr<seastar_pipe_state> state;
+};
+
+class seastar_pipe_source final : public seastar::data_source_impl {
+public:
+  explicit seastar_pipe_source(
+      seastar::lw_shared_ptr<seastar_pipe_state> state)
+      : state(std::move(state)) {}
+
+  seastar::future<seastar::temporary_buffer<char>> get() override {
+    auto state = this->state;
+    return state->readable
+        .wait([state] { return !state->buffers.empty() || state->closed; })
+        .then([state] {
+          // Closed with nothing left to read is the end of the stream.
+          if (state->buffers.empty()) {
+            return seastar::temporary_buffer<char>();
+          }
+          auto buf = std::move(state->buffers.front());
+          state->buffers.pop_front();
+          return buf;
+        });
+  }
+
+private:
+  seastar::lw_shared_ptr<seastar_pipe_state> state;
+};
+
+// seastar::output_stream asserts when it is destroyed with bytes still in its
+// buffer, which only closing it rules out. Rust may drop its handle without
+// closing the stream, or with operations still in flight, so whichever
+// reference goes last while the stream is still open first closes it in the
+// background, keeping it alive until that completes.
+struct seastar_output_stream {
+  seastar::output_stream<char> stream;
+  bool closed = false;
+};
+
+void seastar_output_stream_release(
+    seastar::lw_shared_ptr<seastar_output_stream> stream) noexcept {
+  if (stream.use_count() != 1 || stream->closed) {
+    return;
+  }
+  stream->closed = true;
+  (void)seastar::futurize_invoke([stream] { return stream->stream.close(); })
+      .then_wrapped([stream](seastar::future<> f) { f.ignore_ready_future(); });
+}
+
+// Like seastar::sleep, which arms its timer at now() + duration and so
+// overflows the clock for a long enough duration. The deadline is clamped to
+// the last time point the clock can represent instead.
+seastar::future<> seastar_sleep(std::chrono::nanoseconds duration) {
+  using clock = seastar::steady_clock_type;
+  struct sleeper {
+    seastar::promise<> done;
+    seastar::timer<clock> timer{[this] { done.set_value(); }};
+  };
+  auto now = clock::now();
+  auto deadline = clock::time_point::max();
+  if (duration < deadline - now) {
+    deadline = now + std::chrono::duration_cast<clock::duration>(duration);
+  }
+  auto s = std::make_unique<sleeper>();
+  s->timer.arm(deadline);
+  auto done = s->done.get_future();
+  return done.finally([s = std::move(s)] {});
+}
+} // namespace
+
 #define STD_VECTOR_OPS(RUST_TYPE, CXX_TYPE)                                    \
   std::size_t cxxbridge1$std$vector$##RUST_TYPE##$size(                        \
       const std::vector<CXX_TYPE> &s) noexcept {                               \
@@ -742,62 +933,242 @@ static_assert(sizeof(std::string) <= kMaxExpectedWordsInString * sizeof(void *),
     self->~weak_ptr();                                                         \
   }
 
-#define SEASTAR_LW_SHARED_PTR_OPS(RUST_TYPE, CXX_TYPE)                                \
-  static_assert(sizeof(seastar::lw_shared_ptr<CXX_TYPE>) == sizeof(void *), "");      \
-  static_assert(alignof(seastar::lw_shared_ptr<CXX_TYPE>) == alignof(void *), "");    \
-  void cxxbridge1$seastar$lw_shared_ptr$##RUST_TYPE##$null(                           \
-      seastar::lw_shared_ptr<CXX_TYPE> *ptr) noexcept {                               \
-    new (ptr) seastar::lw_shared_ptr<CXX_TYPE>();                                     \
-  }                                                                                   \
-  CXX_TYPE *cxxbridge1$seastar$lw_shared_ptr$##RUST_TYPE##$uninit(                    \
-    seastar::lw_shared_ptr<CXX_TYPE> *ptr) noexcept {                                 \
-    seastar::lw_shared_ptr<rust::MaybeUninit<CXX_TYPE>>* uninit_ptr =                 \
-      reinterpret_cast<seastar::lw_shared_ptr<rust::MaybeUninit<CXX_TYPE>>*>(ptr);    \
-    new (uninit_ptr) seastar::lw_shared_ptr<rust::MaybeUninit<CXX_TYPE>>(seastar::make_lw_shared<rust::MaybeUninit<CXX_TYPE>>()); \
-    return ptr->get();                                                                \
-  }                                                                                   \
-  void cxxbridge1$seastar$lw_shared_ptr$##RUST_TYPE##$clone(                          \
-      const seastar::lw_shared_ptr<CXX_TYPE> &self,                                   \
-      seastar::lw_shared_ptr<CXX_TYPE> *ptr) noexcept {                               \
-    new (ptr) seastar::lw_shared_ptr<CXX_TYPE>(self);                                 \
-  }                                                                                   \
-  const CXX_TYPE *cxxbridge1$seastar$lw_shared_ptr$##RUST_TYPE##$get(                 \
-      const seastar::lw_shared_ptr<CXX_TYPE> &self) noexcept {                        \
-    return self.get();                                                                \
-  }                                                                                   \
-  void cxxbridge1$seastar$lw_shared_ptr$##RUST_TYPE##$drop(                           \
-      const seastar::lw_shared_ptr<CXX_TYPE> *self) noexcept {                        \
-    self->~lw_shared_ptr();                                                           \
-  }                                                                                   \
-
-#define SEASTAR_SHARED_PTR_OPS(RUST_TYPE, CXX_TYPE)                                \
-  static_assert(sizeof(seastar::shared_ptr<CXX_TYPE>) == 2 * sizeof(void *), "");      \
-  static_assert(alignof(seastar::shared_ptr<CXX_TYPE>) == alignof(void *), "");    \
-  void cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$null(                           \
-      seastar::shared_ptr<CXX_TYPE> *ptr) noexcept {                               \
-    new (ptr) seastar::shared_ptr<CXX_TYPE>();                                     \
-  }                                                                                \
-  CXX_TYPE *cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$uninit(                    \
-    seastar::shared_ptr<CXX_TYPE> *ptr) noexcept {                                 \
-    seastar::shared_ptr<rust::MaybeUninit<CXX_TYPE>>* uninit_ptr =                 \
-      reinterpret_cast<seastar::shared_ptr<rust::MaybeUninit<CXX_TYPE>>*>(ptr);    \
-    new (uninit_ptr) seastar::shared_ptr<rust::MaybeUninit<CXX_TYPE>>(seastar::make_shared<rust::MaybeUninit<CXX_TYPE>>()); \
-    return ptr->get();                                                             \
-  }                                                                                \
-  void cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$clone(                          \
-      const seastar::shared_ptr<CXX_TYPE> &self,                                   \
-      seastar::shared_ptr<CXX_TYPE> *ptr) noexcept {                               \
-    new (ptr) seastar::shared_ptr<CXX_TYPE>(self);                                 \
-  }                                                                                \
-  const CXX_TYPE *cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$get(                 \
-      const seastar::shared_ptr<CXX_TYPE> &self) noexcept {                        \
-    return self.get();                                                             \
-  }                                                                                \
-  void cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$drop(                           \
-      const seastar::shared_ptr<CXX_TYPE> *self) noexcept {                        \
-    self->~shared_ptr();                                                           \
-  }                                                                                \
+// The use_count shims return the seastar reference count as std::size_t.
+// seastar counts in a long, which is never wider than a pointer on the
+// platforms seastar supports, so the conversion cannot truncate.
+static_assert(sizeof(std::declval<const seastar::lw_shared_ptr<int> &>()
+                         .use_count()) <= sizeof(std::size_t),
+              "");
+static_assert(sizeof(std::declval<const seastar::shared_ptr<int> &>()
+                         .use_count()) <= sizeof(std::size_t),
+              "");
+
+#define SEASTAR_LW_SHARED_PTR_OPS(RUST_TYPE, CXX_TYPE)                        \
+  static_assert(sizeof(seastar::lw_shared_ptr<CXX_TYPE>) == sizeof(void *),    \
+                "");                                                           \
+  static_assert(alignof(seastar::lw_shared_ptr<CXX_TYPE>) == alignof(void *),  \
+                "");                                                           \
+  void cxxbridge1$seastar$lw_shared_ptr$##RUST_TYPE##$null(                    \
+      seastar::lw_shared_ptr<CXX_TYPE> *ptr) noexcept {                        \
+    rust::detail::seastar_ptr_null(ptr);                                       \
+  }                                                                            \
+  CXX_TYPE *cxxbridge1$seastar$lw_shared_ptr$##RUST_TYPE##$uninit(             \
+      seastar::lw_shared_ptr<CXX_TYPE> *ptr) noexcept {                        \
+    return rust::detail::seastar_lw_shared_ptr_uninit<                         \
+        seastar::enable_lw_shared_from_this<CXX_TYPE>>(ptr, [] {               \
+      return seastar::make_lw_shared<rust::MaybeUninit<CXX_TYPE>>();           \
+    });                                                                        \
+  }                                                                            \
+  void cxxbridge1$seastar$lw_shared_ptr$##RUST_TYPE##$clone(                   \
+      const seastar::lw_shared_ptr<CXX_TYPE> &self,                            \
+      seastar::lw_shared_ptr<CXX_TYPE> *ptr) noexcept {                        \
+    rust::detail::seastar_ptr_clone(self, ptr);                                \
+  }                                                                            \
+  const CXX_TYPE *cxxbridge1$seastar$lw_shared_ptr$##RUST_TYPE##$get(          \
+      const seastar::lw_shared_ptr<CXX_TYPE> &self) noexcept {                 \
+    return rust::detail::seastar_ptr_get(self);                                \
+  }                                                                            \
+  void cxxbridge1$seastar$lw_to_shared$##RUST_TYPE(                            \
+      const seastar::lw_shared_ptr<CXX_TYPE> &self,                            \
+      seastar::shared_ptr<CXX_TYPE> *ptr) noexcept {                           \
+    rust::detail::seastar_lw_shared_ptr_to_shared(                             \
+        self, ptr, [](const CXX_TYPE &value) {                                 \
+          return seastar::make_shared<CXX_TYPE>(value);                        \
+        });                                                                    \
+  }                                                                            \
+  std::size_t cxxbridge1$seastar$lw_shared_ptr$##RUST_TYPE##$use_count(        \
+      const seastar::lw_shared_ptr<CXX_TYPE> &self) noexcept {                 \
+    return rust::detail::seastar_ptr_use_count(self);                          \
+  }                                                                            \
+  void cxxbridge1$seastar$lw_shared_ptr$##RUST_TYPE##$drop(                    \
+      seastar::lw_shared_ptr<CXX_TYPE> *self) noexcept {                       \
+    rust::detail::seastar_ptr_drop(self);                                      \
+  }
 
+#define SEASTAR_SHARED_PTR_OPS(RUST_TYPE, CXX_TYPE)                            \
+  static_assert(sizeof(seastar::shared_ptr<CXX_TYPE>) == 2 * sizeof(void *),   \
+                "");                                                           \
+  static_assert(alignof(seastar::shared_ptr<CXX_TYPE>) == alignof(void *),     \
+                "");                                                           \
+  void cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$null(                       \
+      seastar::shared_ptr<CXX_TYPE> *ptr) noexcept {                           \
+    rust::detail::seastar_ptr_null(ptr);                                       \
+  }                                                                            \
+  CXX_TYPE *cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$uninit(                \
+      seastar::shared_ptr<CXX_TYPE> *ptr) noexcept {                           \
+    return rust::detail::seastar_ptr_uninit<CXX_TYPE>(ptr, [] {                \
+      return seastar::make_shared<rust::MaybeUninit<CXX_TYPE>>();              \
+    });                                                                        \
+  }                                                                            \
+  CXX_TYPE *cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$uninit_with_deleter(   \
+      void (*call)(void *, CXX_TYPE *), void *deleter,                         \
+      seastar::shared_ptr<CXX_TYPE> *ptr) noexcept {                           \
+    return rust::detail::seastar_shared_ptr_uninit_with_deleter(               \
+        seastar::make_shared<                                                  \
+            rust::detail::seastar_shared_ptr_deleter<CXX_TYPE>>(),             \
+        call, deleter, ptr);                                                   \
+  }                                                                            \
+  bool cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$take_unique(                \
+      seastar::shared_ptr<CXX_TYPE> *self, CXX_TYPE *value) noexcept {         \
+    void **words = reinterpret_cast<void **>(self);                            \
+    return rust::detail::seastar_shared_ptr_take_unique<                       \
+               seastar::shared_ptr_count_base,                                 \
+               seastar::shared_ptr_count_for<CXX_TYPE>>(words, value) ||       \
+           rust::detail::seastar_shared_ptr_take_unique<                       \
+               seastar::shared_ptr_count_base,                                 \
+               seastar::shared_ptr_count_for<rust::MaybeUninit<CXX_TYPE>>>(    \
+               words, value);                                                  \
+  }                                                                            \
+  void cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$clone(                      \
+      const seastar::shared_ptr<CXX_TYPE> &self,                               \
+      seastar::shared_ptr<CXX_TYPE> *ptr) noexcept {                           \
+    rust::detail::seastar_ptr_clone(self, ptr);                                \
+  }                                                                            \
+  const CXX_TYPE *cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$get(             \
+      const seastar::shared_ptr<CXX_TYPE> &self) noexcept {                    \
+    return rust::detail::seastar_ptr_get(self);                                \
+  }                                                                            \
+  void cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$from_std(                   \
+      const std::shared_ptr<CXX_TYPE> &src,                                    \
+      seastar::shared_ptr<CXX_TYPE> *ptr) noexcept {                           \
+    seastar_shared_ptr_from_std(src, ptr);                                     \
+  }                                                                            \
+  std::size_t cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$use_count(           \
+      const seastar::shared_ptr<CXX_TYPE> &self) noexcept {                    \
+    return rust::detail::seastar_ptr_use_count(self);                          \
+  }                                                                            \
+  void cxxbridge1$seastar$shared_ptr$##RUST_TYPE##$drop(                       \
+      seastar::shared_ptr<CXX_TYPE> *self) noexcept {                          \
+    rust::detail::seastar_ptr_drop(self);                                      \
+  }
+
+#define SEASTAR_SHARED_PTR_VECTOR_OPS(RUST_TYPE, CXX_TYPE)                      \
+  static_assert(sizeof(seastar::shared_ptr<std::vector<CXX_TYPE>>) ==           \
+                    2 * sizeof(void *),                                         \
+                "");                                                            \
+  void cxxbridge1$seastar$shared_ptr$std$vector$##RUST_TYPE##$null(             \
+      seastar::shared_ptr<std::vector<CXX_TYPE>> *ptr) noexcept {               \
+    new (ptr) seastar::shared_ptr<std::vector<CXX_TYPE>>();                     \
+  }                                                                             \
+  void cxxbridge1$seastar$shared_ptr$std$vector$##RUST_TYPE##$with_len(         \
+      std::size_t len,                                                          \
+      seastar::shared_ptr<std::vector<CXX_TYPE>> *ptr) noexcept {               \
+    new (ptr) seastar::shared_ptr<std::vector<CXX_TYPE>>(                       \
+        seastar::make_shared<std::vector<CXX_TYPE>>(len));                      \
+  }                                                                             \
+  void cxxbridge1$seastar$shared_ptr$std$vector$##RUST_TYPE##$clone(            \
+      const seastar::shared_ptr<std::vector<CXX_TYPE>> &self,                   \
+      seastar::shared_ptr<std::vector<CXX_TYPE>> *ptr) noexcept {               \
+    new (ptr) seastar::shared_ptr<std::vector<CXX_TYPE>>(self);                 \
+  }                                                                             \
+  const std::vector<CXX_TYPE>                                                   \
+      *cxxbridge1$seastar$shared_ptr$std$vector$##RUST_TYPE##$get(              \
+          const seastar::shared_ptr<std::vector<CXX_TYPE>> &self) noexcept {    \
+    return self.get();                                                          \
+  }                                                                             \
+  void cxxbridge1$seastar$shared_ptr$std$vector$##RUST_TYPE##$from_std(         \
+      const std::shared_ptr<std::vector<CXX_TYPE>> &src,                        \
+      seastar::shared_ptr<std::vector<CXX_TYPE>> *ptr) noexcept {               \
+    seastar_shared_ptr_from_std(src, ptr);                                      \
+  }                                                                             \
+  std::size_t cxxbridge1$seastar$shared_ptr$std$vector$##RUST_TYPE##$use_count( \
+      const seastar::shared_ptr<std::vector<CXX_TYPE>> &self) noexcept {        \
+    return self.use_count();                                                    \
+  }                                                                             \
+  void cxxbridge1$seastar$shared_ptr$std$vector$##RUST_TYPE##$drop(             \
+      const seastar::shared_ptr<std::vector<CXX_TYPE>> *self) noexcept {        \
+    self->~shared_ptr();                                                        \
+  }                                                                             \
+
+
+#define SEASTAR_FUTURE_OPS(RUST_TYPE, CXX_TYPE)                                 \
+  seastar::future<CXX_TYPE> *cxxbridge1$seastar$future$##RUST_TYPE##$ready(     \
+      CXX_TYPE value) noexcept {                                                \
+    return new seastar::future<CXX_TYPE>(                                       \
+        seastar::make_ready_future<CXX_TYPE>(value));                           \
+  }                                                                             \
+  std::uint8_t cxxbridge1$seastar$future$##RUST_TYPE##$poll(                   \
+      seastar::future<CXX_TYPE> *self, void *waker, void (*wake)(void *),       \
+      CXX_TYPE *value, rust::repr::PtrLen *exception) noexcept {                \
+    if (!self->available()) {                                                   \
+      if (waker) {                                                              \
+        *self = std::move(*self).then_wrapped(                                  \
+            [waker, wake](seastar::future<CXX_TYPE> f) noexcept {               \
+              wake(waker);                                                      \
+              return f;                                                         \
+            });                                                                 \
+      }                                                                         \
+      return 0;                                                                 \
+    }                                                                           \
+    if (self->failed()) {                                                       \
+      *exception = seastar_exception_what(self->get_exception());               \
+      return 2;                                                                 \
+    }                                                                           \
+    *value = self->get();                                                       \
+    return 1;                                                                   \
+  }                                                                             \
+  bool cxxbridge1$seastar$future$##RUST_TYPE##$available(                       \
+      const seastar::future<CXX_TYPE> *self) noexcept {                         \
+    return self->available();                                                   \
+  }                                                                             \
+  void cxxbridge1$seastar$future$##RUST_TYPE##$drop(                            \
+      seastar::future<CXX_TYPE> *self) noexcept {                               \
+    delete self;                                                                \
+  }                                                                             \
+  seastar::future<CXX_TYPE> *cxxbridge1$seastar$future$##RUST_TYPE##$submit_to( \
+      unsigned shard, void *data,                                               \
+      bool (*call)(void *, CXX_TYPE *, rust::repr::PtrLen *)) noexcept {        \
+    std::exception_ptr error;                                                   \
+    if (seastar_submit_to_refuse(shard, data, call, &error)) {                  \
+      return new seastar::future<CXX_TYPE>(                                     \
+          seastar::make_exception_future<CXX_TYPE>(std::move(error)));          \
+    }                                                                           \
+    return new seastar::future<CXX_TYPE>(seastar::futurize_invoke([=] {         \
+      return seastar::smp::submit_to(                                           \
+          shard, [=] { return seastar_submit_to_call(data, call); });           \
+    }));                                                                        \
+  }                                                                             \
+
+#define SEASTAR_CIRCULAR_BUFFER_OPS(RUST_TYPE, CXX_TYPE)                        \
+  std::size_t cxxbridge1$seastar$circular_buffer$##RUST_TYPE##$size(            \
+      const seastar::circular_buffer<CXX_TYPE> &s) noexcept {                   \
+    return s.size();                                                            \
+  }                                                                             \
+  const CXX_TYPE *cxxbridge1$seastar$circular_buffer$##RUST_TYPE##$get_unchecked(\
+      const seastar::circular_buffer<CXX_TYPE> &s, std::size_t pos) noexcept {  \
+    return &s[pos];                                                             \
+  }                                                                             \
+
+#define SEASTAR_CHUNKED_FIFO_OPS(RUST_TYPE, CXX_TYPE)                           \
+  seastar::chunked_fifo<CXX_TYPE> *                                             \
+      cxxbridge1$seastar$chunked_fifo$##RUST_TYPE##$new() noexcept {            \
+    return new seastar::chunked_fifo<CXX_TYPE>();                               \
+  }                                                                             \
+  std::size_t cxxbridge1$seastar$chunked_fifo$##RUST_TYPE##$size(               \
+      const seastar::chunked_fifo<CXX_TYPE> *self) noexcept {                   \
+    return self->size();                                                        \
+  }                                                                             \
+  const CXX_TYPE *cxxbridge1$seastar$chunked_fifo$##RUST_TYPE##$front(          \
+      const seastar::chunked_fifo<CXX_TYPE> *self) noexcept {                   \
+    return self->empty() ? nullptr : &self->front();                            \
+  }                                                                             \
+  void cxxbridge1$seastar$chunked_fifo$##RUST_TYPE##$push_back(                 \
+      seastar::chunked_fifo<CXX_TYPE> *self, CXX_TYPE value) noexcept {         \
+    self->push_back(value);                                                     \
+  }                                                                             \
+  bool cxxbridge1$seastar$chunked_fifo$##RUST_TYPE##$pop_front(                 \
+      seastar::chunked_fifo<CXX_TYPE> *self, CXX_TYPE *out) noexcept {          \
+    if (self->empty()) {                                                        \
+      return false;                                                             \
+    }                                                                           \
+    *out = std::move(self->front());                                            \
+    self->pop_front();                                                          \
+    return true;                                                                \
+  }                                                                             \
+  void cxxbridge1$seastar$chunked_fifo$##RUST_TYPE##$drop(                      \
+      seastar::chunked_fifo<CXX_TYPE> *self) noexcept {                         \
+    delete self;                                                                \
+  }                                                                             \
 
 // Usize and isize are the same type as one of the below.
 #define FOR_EACH_NUMERIC(MACRO)                                                \
@@ -851,13 +1222,685 @@ static_assert(sizeof(std::string) <= kMaxExpectedWordsInString * sizeof(void *),
   MACRO(isize, rust::isize)                                                    \
   MACRO(string, std::string)
 
+#define FOR_EACH_SEASTAR_FUTURE(MACRO)                                         \
+  FOR_EACH_NUMERIC(MACRO)                                                      \
+  MACRO(bool, bool)                                                            \
+  MACRO(usize, std::size_t)                                                    \
+  MACRO(isize, rust::isize)
+
+#define FOR_EACH_SEASTAR_CHUNKED_FIFO(MACRO)                                   \
+  FOR_EACH_NUMERIC(MACRO)                                                      \
+  MACRO(bool, bool)                                                            \
+  MACRO(usize, std::size_t)                                                    \
+  MACRO(isize, rust::isize)
+
+#define FOR_EACH_SEASTAR_CIRCULAR_BUFFER(MACRO)                                \
+  FOR_EACH_NUMERIC(MACRO)                                                      \
+  MACRO(usize, std::size_t)                                                    \
+  MACRO(isize, rust::isize)
+
 extern "C" {
 FOR_EACH_STD_VECTOR(STD_VECTOR_OPS)
 FOR_EACH_TRIVIAL_STD_VECTOR(STD_VECTOR_TRIVIAL_OPS)
 FOR_EACH_RUST_VEC(RUST_VEC_EXTERNS)
 FOR_EACH_SHARED_PTR(SHARED_PTR_OPS)
+FOR_EACH_SEASTAR_FUTURE(SEASTAR_FUTURE_OPS)
+FOR_EACH_STD_VECTOR(SEASTAR_SHARED_PTR_VECTOR_OPS)
+FOR_EACH_SEASTAR_CIRCULAR_BUFFER(SEASTAR_CIRCULAR_BUFFER_OPS)
+FOR_EACH_SEASTAR_CHUNKED_FIFO(SEASTAR_CHUNKED_FIFO_OPS)
+
+// With the seastar-shims-on-demand feature, each bridge defines the shims for
+// the primitive seastar pointer targets it refers to.
+#ifndef CXXBRIDGE1_SEASTAR_SHIMS_ON_DEMAND
 FOR_EACH_SEASTAR_LW_SHARED_PTR(SEASTAR_LW_SHARED_PTR_OPS)
 FOR_EACH_SEASTAR_SHARED_PTR(SEASTAR_SHARED_PTR_OPS)
+
+void cxxbridge1$seastar$lw_shared_ptr$string$from_str(
+    seastar::lw_shared_ptr<std::string> *ptr, const std::uint8_t *s,
+    std::size_t len) noexcept {
+  rust::detail::seastar_ptr_from_str(
+      ptr,
+      [](const char *data, std::size_t size) {
+        return seastar::make_lw_shared<std::string>(data, size);
+      },
+      s, len);
+}
+
+void cxxbridge1$seastar$shared_ptr$string$from_str(
+    seastar::shared_ptr<std::string> *ptr, const std::uint8_t *s,
+    std::size_t len) noexcept {
+  rust::detail::seastar_ptr_from_str(
+      ptr,
+      [](const char *data, std::size_t size) {
+        return seastar::make_shared<std::string>(data, size);
+      },
+      s, len);
+}
+#endif // CXXBRIDGE1_SEASTAR_SHIMS_ON_DEMAND
+
+seastar::gate *cxxbridge1$seastar$gate$new() noexcept {
+  return new seastar::gate();
+}
+
+bool cxxbridge1$seastar$gate$try_enter(seastar::gate *self) noexcept {
+  if (self->is_closed()) {
+    return false;
+  }
+  self->enter();
+  return true;
+}
+
+void cxxbridge1$seastar$gate$leave(seastar::gate *self) noexcept {
+  self->leave();
+}
+
+seastar::future<> *cxxbridge1$seastar$gate$close(seastar::gate *self) noexcept {
+  if (self->is_closed()) {
+    // seastar::gate::close asserts that it is only called once.
+    return new seastar::future<>(
+        seastar::make_exception_future<>(seastar::gate_closed_exception()));
+  }
+  return new seastar::future<>(self->close());
+}
+
+bool cxxbridge1$seastar$gate$is_closed(const seastar::gate *self) noexcept {
+  return self->is_closed();
+}
+
+std::size_t cxxbridge1$seastar$gate$count(const seastar::gate *self) noexcept {
+  return self->get_count();
+}
+
+void cxxbridge1$seastar$gate$drop(seastar::gate *self) noexcept {
+  delete self;
+}
+
+seastar::abort_source *cxxbridge1$seastar$abort_source$new() noexcept {
+  return new seastar::abort_source();
+}
+
+void cxxbridge1$seastar$abort_source$request_abort(
+    seastar::abort_source *self) noexcept {
+  if (!self->abort_requested()) {
+    self->request_abort();
+  }
+}
+
+bool cxxbridge1$seastar$abort_source$abort_requested(
+    const seastar::abort_source *self) noexcept {
+  return self->abort_requested();
+}
+
+seastar::abort_source::subscription *
+cxxbridge1$seastar$abort_source$subscribe(seastar::abort_source *self,
+                                          void *data,
+                                          void (*call)(void *)) noexcept {
+  auto subscription = self->subscribe([data, call]() noexcept { call(data); });
+  if (!subscription) {
+    return nullptr;
+  }
+  return new seastar::abort_source::subscription(std::move(*subscription));
+}
+
+void cxxbridge1$seastar$abort_source$unsubscribe(
+    seastar::abort_source::subscription *subscription) noexcept {
+  delete subscription;
+}
+
+void cxxbridge1$seastar$abort_source$drop(seastar::abort_source *self) noexcept {
+  delete self;
+}
+
+seastar::metrics::metric_groups *cxxbridge1$seastar$metrics$add_gauge(
+    const std::uint8_t *group, std::size_t group_len, const std::uint8_t *name,
+    std::size_t name_len, void *data, double (*call)(void *),
+    rust::repr::PtrLen *exception) noexcept {
+  try {
+    auto groups = std::make_unique<seastar::metrics::metric_groups>();
+    groups->add_group(
+        seastar::sstring(reinterpret_cast<const char *>(group), group_len),
+        {seastar::metrics::make_gauge(
+            seastar::sstring(reinterpret_cast<const char *>(name), name_len),
+            [data, call] { return call(data); })});
+    return groups.release();
+  } catch (...) {
+    *exception = seastar_exception_what(std::current_exception());
+    return nullptr;
+  }
+}
+
+seastar::metrics::metric_groups *cxxbridge1$seastar$metrics$add_counter(
+    const std::uint8_t *group, std::size_t group_len, const std::uint8_t *name,
+    std::size_t name_len, void *data, std::uint64_t (*call)(void *),
+    rust::repr::PtrLen *exception) noexcept {
+  try {
+    auto groups = std::make_unique<seastar::metrics::metric_groups>();
+    groups->add_group(
+        seastar::sstring(reinterpret_cast<const char *>(group), group_len),
+        {seastar::metrics::make_counter(
+            seastar::sstring(reinterpret_cast<const char *>(name), name_len),
+            [data, call] { return call(data); })});
+    return groups.release();
+  } catch (...) {
+    *exception = seastar_exception_what(std::current_exception());
+    return nullptr;
+  }
+}
+
+void cxxbridge1$seastar$metrics$unregister(
+    seastar::metrics::metric_groups *self) noexcept {
+  delete self;
+}
+
+seastar::socket_address *
+cxxbridge1$seastar$socket_address$new_v4(const std::uint8_t *octets,
+                                         std::uint16_t port) noexcept {
+  ::in_addr addr;
+  std::memcpy(&addr, octets, sizeof(addr));
+  return new seastar::socket_address(seastar::net::inet_address(addr), port);
+}
+
+seastar::socket_address *
+cxxbridge1$seastar$socket_address$new_v6(const std::uint8_t *octets,
+                                         std::uint16_t port,
+                                         std::uint32_t scope_id) noexcept {
+  ::in6_addr addr;
+  std::memcpy(&addr, octets, sizeof(addr));
+  return new seastar::socket_address(
+      seastar::net::inet_address(addr, scope_id), port);
+}
+
+seastar::socket_address *cxxbridge1$seastar$socket_address$clone(
+    const seastar::socket_address *self) noexcept {
+  return new seastar::socket_address(*self);
+}
+
+std::uint16_t cxxbridge1$seastar$socket_address$port(
+    const seastar::socket_address *self) noexcept {
+  return self->port();
+}
+
+bool cxxbridge1$seastar$socket_address$ip(const seastar::socket_address *self,
+                                          std::uint8_t (*octets)[16],
+                                          std::uint32_t *scope_id) noexcept {
+  auto addr = self->addr();
+  if (addr.is_ipv4()) {
+    ::in_addr ipv4 = addr.as_ipv4_address();
+    std::memcpy(*octets, &ipv4, sizeof(ipv4));
+    return false;
+  }
+  ::in6_addr ipv6 = addr.as_ipv6_address();
+  std::memcpy(*octets, &ipv6, sizeof(ipv6));
+  *scope_id = addr.scope();
+  return true;
+}
+
+void cxxbridge1$seastar$socket_address$drop(
+    seastar::socket_address *self) noexcept {
+  delete self;
+}
+
+seastar::semaphore *cxxbridge1$seastar$semaphore$new(std::size_t count) noexcept {
+  return new seastar::semaphore(count);
+}
+
+bool cxxbridge1$seastar$semaphore$try_wait(seastar::semaphore *self,
+                                           std::size_t units) noexcept {
+  return self->try_wait(units);
+}
+
+void cxxbridge1$seastar$semaphore$signal(seastar::semaphore *self,
+                                         std::size_t units) noexcept {
+  self->signal(units);
+}
+
+std::size_t
+cxxbridge1$seastar$semaphore$current(const seastar::semaphore *self) noexcept {
+  return self->current();
+}
+
+void cxxbridge1$seastar$semaphore$drop(seastar::semaphore *self) noexcept {
+  delete self;
+}
+
+seastar::future<> *cxxbridge1$seastar$future$unit$ready() noexcept {
+  return new seastar::future<>(seastar::make_ready_future<>());
+}
+
+std::uint8_t
+cxxbridge1$seastar$future$unit$poll(seastar::future<> *self, void *waker,
+                                    void (*wake)(void *),
+                                    rust::repr::PtrLen *exception) noexcept {
+  if (!self->available()) {
+    if (waker) {
+      *self = std::move(*self).then_wrapped(
+          [waker, wake](seastar::future<> f) noexcept {
+            wake(waker);
+            return f;
+          });
+    }
+    return 0;
+  }
+  if (self->failed()) {
+    *exception = seastar_exception_what(self->get_exception());
+    return 2;
+  }
+  self->get();
+  return 1;
+}
+
+bool cxxbridge1$seastar$future$unit$available(
+    const seastar::future<> *self) noexcept {
+  return self->available();
+}
+
+void cxxbridge1$seastar$future$unit$drop(seastar::future<> *self) noexcept {
+  delete self;
+}
+
+seastar::future<> *cxxbridge1$seastar$future$unit$submit_to(
+    unsigned shard, void *data,
+    bool (*call)(void *, void *, rust::repr::PtrLen *)) noexcept {
+  std::exception_ptr error;
+  if (seastar_submit_to_refuse(shard, data, call, &error)) {
+    return new seastar::future<>(
+        seastar::make_exception_future<>(std::move(error)));
+  }
+  return new seastar::future<>(seastar::futurize_invoke([=] {
+    return seastar::smp::submit_to(shard, [=] {
+      // Rust writes its zero sized () through this, so it must not be null.
+      char unit;
+      rust::repr::PtrLen panic;
+      if (!call(data, &unit, &panic)) {
+        seastar_rethrow_panic(panic);
+      }
+    });
+  }));
+}
+
+seastar::condition_variable *
+cxxbridge1$seastar$condition_variable$new() noexcept {
+  return new seastar::condition_variable();
+}
+
+seastar::future<> *cxxbridge1$seastar$condition_variable$wait(
+    seastar::condition_variable *self) noexcept {
+  return new seastar::future<>(
+      seastar::futurize_invoke([self] { return self->wait(); }));
+}
+
+void cxxbridge1$seastar$condition_variable$signal(
+    seastar::condition_variable *self) noexcept {
+  self->signal();
+}
+
+void cxxbridge1$seastar$condition_variable$broadcast(
+    seastar::condition_variable *self) noexcept {
+  self->broadcast();
+}
+
+bool cxxbridge1$seastar$condition_variable$has_waiters(
+    const seastar::condition_variable *self) noexcept {
+  return self->has_waiters();
+}
+
+void cxxbridge1$seastar$condition_variable$drop(
+    seastar::condition_variable *self) noexcept {
+  delete self;
+}
+
+// The lock is reference counted so that a waiter given up by Rust while
+// still in line can release it once granted, even if the SeastarRwLock has
+// been dropped in between.
+using rwlock_ptr = seastar::lw_shared_ptr<seastar::rwlock>;
+
+rwlock_ptr *cxxbridge1$seastar$rwlock$new() noexcept {
+  return new rwlock_ptr(seastar::make_lw_shared<seastar::rwlock>());
+}
+
+seastar::future<> *
+cxxbridge1$seastar$rwlock$read_lock(rwlock_ptr *self) noexcept {
+  return new seastar::future<>(
+      seastar::futurize_invoke([self] { return (*self)->read_lock(); }));
+}
+
+seastar::future<> *
+cxxbridge1$seastar$rwlock$write_lock(rwlock_ptr *self) noexcept {
+  return new seastar::future<>(
+      seastar::futurize_invoke([self] { return (*self)->write_lock(); }));
+}
+
+bool cxxbridge1$seastar$rwlock$try_read_lock(rwlock_ptr *self) noexcept {
+  return (*self)->try_read_lock();
+}
+
+bool cxxbridge1$seastar$rwlock$try_write_lock(rwlock_ptr *self) noexcept {
+  return (*self)->try_write_lock();
+}
+
+void cxxbridge1$seastar$rwlock$read_unlock(rwlock_ptr *self) noexcept {
+  (*self)->read_unlock();
+}
+
+void cxxbridge1$seastar$rwlock$write_unlock(rwlock_ptr *self) noexcept {
+  (*self)->write_unlock();
+}
+
+static void rwlock_abandon(rwlock_ptr lock, void (seastar::rwlock::*unlock)(),
+                           seastar::future<> *acquire) noexcept {
+  if (acquire->available()) {
+    if (acquire->failed()) {
+      acquire->ignore_ready_future();
+    } else {
+      ((*lock).*unlock)();
+    }
+  } else {
+    (void)std::move(*acquire)
+        .then([lock, unlock] { ((*lock).*unlock)(); })
+        .handle_exception([](std::exception_ptr) {});
+  }
+  delete acquire;
+}
+
+void cxxbridge1$seastar$rwlock$abandon_read(
+    rwlock_ptr *self, seastar::future<> *acquire) noexcept {
+  rwlock_abandon(*self, &seastar::rwlock::read_unlock, acquire);
+}
+
+void cxxbridge1$seastar$rwlock$abandon_write(
+    rwlock_ptr *self, seastar::future<> *acquire) noexcept {
+  rwlock_abandon(*self, &seastar::rwlock::write_unlock, acquire);
+}
+
+void cxxbridge1$seastar$rwlock$drop(rwlock_ptr *self) noexcept { delete self; }
+
+// The file is reference counted so that operations still in flight when
+// Rust drops its SeastarFile keep it alive until they complete. Reads land in
+// a reference counted buffer for the same reason, which Rust copies out of
+// once the read has completed.
+using file_ptr = seastar::lw_shared_ptr<seastar::file>;
+using read_buffer_ptr = seastar::lw_shared_ptr<seastar::temporary_buffer<char>>;
+
+// Mirrors the bits of OpenFlags in seastar_file.rs.
+static seastar::open_flags seastar_open_flags(std::uint32_t flags) noexcept {
+  auto ret = (flags & 3) == 3   ? seastar::open_flags::rw
+             : (flags & 3) == 2 ? seastar::open_flags::wo
+                                : seastar::open_flags::ro;
+  if (flags & 4) {
+    ret = ret | seastar::open_flags::create;
+  }
+  if (flags & 8) {
+    ret = ret | seastar::open_flags::truncate;
+  }
+  if (flags & 16) {
+    ret = ret | seastar::open_flags::exclusive;
+  }
+  return ret;
+}
+
+file_ptr *cxxbridge1$seastar$file$new() noexcept {
+  return new file_ptr(seastar::make_lw_shared<seastar::file>());
+}
+
+seastar::future<> *cxxbridge1$seastar$file$open(file_ptr *self,
+                                                const std::uint8_t *path,
+                                                std::size_t path_len,
+                                                std::uint32_t flags) noexcept {
+  auto file = *self;
+  return new seastar::future<>(seastar::futurize_invoke([&] {
+    seastar::sstring name(reinterpret_cast<const char *>(path), path_len);
+    return seastar::open_file_dma(name, seastar_open_flags(flags))
+        .then([file](seastar::file opened) { *file = std::move(opened); });
+  }));
+}
+
+seastar::future<std::size_t> *
+cxxbridge1$seastar$file$dma_read(file_ptr *self, std::uint64_t pos,
+                                 std::size_t len,
+                                 read_buffer_ptr **buffer) noexcept {
+  auto file = *self;
+  auto read = seastar::make_lw_shared<seastar::temporary_buffer<char>>();
+  *buffer = new read_buffer_ptr(read);
+  return new seastar::future<std::size_t>(seastar::futurize_invoke([&] {
+    return file->dma_read<char>(pos, len).then(
+        [file, read](seastar::temporary_buffer<char> data) {
+          *read = std::move(data);
+          return read->size();
+        });
+  }));
+}
+
+seastar::future<std::size_t> *
+cxxbridge1$seastar$file$dma_write(file_ptr *self, std::uint64_t pos,
+                                  const std::uint8_t *buf,
+                                  std::size_t len) noexcept {
+  auto file = *self;
+  return new seastar::future<std::size_t>(seastar::futurize_invoke([&] {
+    auto alignment = file->disk_write_dma_alignment();
+    if (pos % alignment != 0 || len % alignment != 0) {
+      throw std::invalid_argument(
+          "dma_write position and length must be multiples of the disk "
+          "write dma alignment");
+    }
+    auto data = seastar::temporary_buffer<char>::aligned(
+        file->memory_dma_alignment(), len);
+    std::memcpy(data.get_write(), buf, len);
+    auto ptr = data.get();
+    return file->dma_write(pos, ptr, len)
+        .finally([file, data = std::move(data)] {});
+  }));
+}
+
+seastar::future<> *cxxbridge1$seastar$file$close(file_ptr *self) noexcept {
+  auto file = *self;
+  return new seastar::future<>(seastar::futurize_invoke(
+      [file] { return file->close().finally([file] {}); }));
+}
+
+std::uint64_t cxxbridge1$seastar$file$disk_read_dma_alignment(
+    const file_ptr *self) noexcept {
+  return (*self)->disk_read_dma_alignment();
+}
+
+std::uint64_t cxxbridge1$seastar$file$disk_write_dma_alignment(
+    const file_ptr *self) noexcept {
+  return (*self)->disk_write_dma_alignment();
+}
+
+void cxxbridge1$seastar$file$drop(file_ptr *self) noexcept { delete self; }
+
+const std::uint8_t *cxxbridge1$seastar$file$read_buffer$data(
+    const read_buffer_ptr *self) noexcept {
+  return reinterpret_cast<const std::uint8_t *>((*self)->get());
+}
+
+void cxxbridge1$seastar$file$read_buffer$drop(read_buffer_ptr *self) noexcept {
+  delete self;
+}
+
+seastar::temporary_buffer<char> *
+cxxbridge1$seastar$temporary_buffer$new(const std::uint8_t *data,
+                                        std::size_t len) noexcept {
+  return new seastar::temporary_buffer<char>(
+      reinterpret_cast<const char *>(data), len);
+}
+
+const std::uint8_t *cxxbridge1$seastar$temporary_buffer$data(
+    const seastar::temporary_buffer<char> *self) noexcept {
+  return reinterpret_cast<const std::uint8_t *>(self->get());
+}
+
+std::size_t cxxbridge1$seastar$temporary_buffer$size(
+    const seastar::temporary_buffer<char> *self) noexcept {
+  return self->size();
+}
+
+void cxxbridge1$seastar$temporary_buffer$drop(
+    seastar::temporary_buffer<char> *self) noexcept {
+  delete self;
+}
+
+seastar::future<seastar::temporary_buffer<char>> *
+cxxbridge1$seastar$future$temporary_buffer$ready(
+    seastar::temporary_buffer<char> *value) noexcept {
+  std::unique_ptr<seastar::temporary_buffer<char>> owned(value);
+  return new seastar::future<seastar::temporary_buffer<char>>(
+      seastar::make_ready_future<seastar::temporary_buffer<char>>(
+          std::move(*owned)));
+}
+
+std::uint8_t cxxbridge1$seastar$future$temporary_buffer$poll(
+    seastar::future<seastar::temporary_buffer<char>> *self, void *waker,
+    void (*wake)(void *), seastar::temporary_buffer<char> **value,
+    rust::repr::PtrLen *exception) noexcept {
+  if (!self->available()) {
+    if (waker) {
+      *self = std::move(*self).then_wrapped(
+          [waker, wake](
+              seastar::future<seastar::temporary_buffer<char>> f) noexcept {
+            wake(waker);
+            return f;
+          });
+    }
+    return 0;
+  }
+  if (self->failed()) {
+    *exception = seastar_exception_what(self->get_exception());
+    return 2;
+  }
+  *value = new seastar::temporary_buffer<char>(self->get());
+  return 1;
+}
+
+bool cxxbridge1$seastar$future$temporary_buffer$available(
+    const seastar::future<seastar::temporary_buffer<char>> *self) noexcept {
+  return self->available();
+}
+
+void cxxbridge1$seastar$future$temporary_buffer$drop(
+    seastar::future<seastar::temporary_buffer<char>> *self) noexcept {
+  delete self;
+}
+
+seastar::future<seastar::temporary_buffer<char>> *
+cxxbridge1$seastar$future$temporary_buffer$submit_to(
+    unsigned shard, void *data,
+    bool (*call)(void *, seastar::temporary_buffer<char> **,
+                 rust::repr::PtrLen *)) noexcept {
+  std::exception_ptr error;
+  if (seastar_submit_to_refuse(shard, data, call, &error)) {
+    return new seastar::future<seastar::temporary_buffer<char>>(
+        seastar::make_exception_future<seastar::temporary_buffer<char>>(
+            std::move(error)));
+  }
+  return new seastar::future<seastar::temporary_buffer<char>>(
+      seastar::futurize_invoke([=] {
+        return seastar::smp::submit_to(shard, [=] {
+          std::unique_ptr<seastar::temporary_buffer<char>> value(
+              seastar_submit_to_call(data, call));
+          return std::move(*value);
+        });
+      }));
+}
+
+// Like the file, the streams are reference counted so that operations still in
+// flight when Rust drops its handle keep the stream alive until they complete.
+using input_stream_ptr = seastar::lw_shared_ptr<seastar::input_stream<char>>;
+using output_stream_ptr = seastar::lw_shared_ptr<seastar_output_stream>;
+
+void cxxbridge1$seastar$pipe$new(std::size_t buffer_size,
+                                 output_stream_ptr **output,
+                                 input_stream_ptr **input) noexcept {
+  auto state = seastar::make_lw_shared<seastar_pipe_state>();
+  *output = new output_stream_ptr(
+      seastar::make_lw_shared<seastar_output_stream>(seastar_output_stream{
+          seastar::output_stream<char>(
+              seastar::data_sink(std::make_unique<seastar_pipe_sink>(state)),
+              buffer_size)}));
+  *input = new input_stream_ptr(
+      seastar::make_lw_shared<seastar::input_stream<char>>(
+          seastar::data_source(std::make_unique<seastar_pipe_source>(state))));
+}
+
+input_stream_ptr *cxxbridge1$seastar$input_stream$from_raw(
+    seastar::input_stream<char> *raw) noexcept {
+  std::unique_ptr<seastar::input_stream<char>> owned(raw);
+  return new input_stream_ptr(
+      seastar::make_lw_shared<seastar::input_stream<char>>(std::move(*owned)));
+}
+
+seastar::future<seastar::temporary_buffer<char>> *
+cxxbridge1$seastar$input_stream$read(input_stream_ptr *self) noexcept {
+  auto stream = *self;
+  return new seastar::future<seastar::temporary_buffer<char>>(
+      seastar::futurize_invoke(
+          [stream] { return stream->read().finally([stream] {}); }));
+}
+
+seastar::future<> *
+cxxbridge1$seastar$input_stream$close(input_stream_ptr *self) noexcept {
+  auto stream = *self;
+  return new seastar::future<>(seastar::futurize_invoke(
+      [stream] { return stream->close().finally([stream] {}); }));
+}
+
+void cxxbridge1$seastar$input_stream$drop(input_stream_ptr *self) noexcept {
+  delete self;
+}
+
+output_stream_ptr *cxxbridge1$seastar$output_stream$from_raw(
+    seastar::output_stream<char> *raw) noexcept {
+  std::unique_ptr<seastar::output_stream<char>> owned(raw);
+  return new output_stream_ptr(seastar::make_lw_shared<seastar_output_stream>(
+      seastar_output_stream{std::move(*owned)}));
+}
+
+// output_stream::write copies the bytes, into its own buffer or into one
+// passed on to the sink, before returning the future.
+seastar::future<> *
+cxxbridge1$seastar$output_stream$write(output_stream_ptr *self,
+                                       const std::uint8_t *buf,
+                                       std::size_t len) noexcept {
+  auto stream = *self;
+  return new seastar::future<>(seastar::futurize_invoke([&] {
+    return stream->stream.write(reinterpret_cast<const char *>(buf), len)
+        .finally([stream]() mutable {
+          seastar_output_stream_release(std::move(stream));
+        });
+  }));
+}
+
+seastar::future<> *
+cxxbridge1$seastar$output_stream$flush(output_stream_ptr *self) noexcept {
+  auto stream = *self;
+  return new seastar::future<>(seastar::futurize_invoke([stream] {
+    return stream->stream.flush().finally([stream]() mutable {
+      seastar_output_stream_release(std::move(stream));
+    });
+  }));
+}
+
+seastar::future<> *
+cxxbridge1$seastar$output_stream$close(output_stream_ptr *self) noexcept {
+  auto stream = *self;
+  stream->closed = true;
+  return new seastar::future<>(seastar::futurize_invoke(
+      [stream] { return stream->stream.close().finally([stream] {}); }));
+}
+
+void cxxbridge1$seastar$output_stream$drop(output_stream_ptr *self) noexcept {
+  seastar_output_stream_release(std::move(*self));
+  delete self;
+}
+
+seastar::future<> *cxxbridge1$seastar$sleep(std::int64_t nanos) noexcept {
+  return new seastar::future<>(seastar::futurize_invoke(
+      [nanos] { return seastar_sleep(std::chrono::nanoseconds(nanos)); }));
+}
+
+unsigned cxxbridge1$seastar$this_shard_id() noexcept {
+  return seastar::this_shard_id();
+}
 } // extern "C"
 
 namespace rust {
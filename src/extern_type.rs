@@ -1,4 +1,4 @@
-use self::kind::{Kind, Opaque, Trivial};
+use self::kind::{Kind, Opaque, Relocatable, Trivial};
 use crate::CxxString;
 #[cfg(feature = "alloc")]
 use alloc::string::String;
@@ -112,10 +112,12 @@ pub unsafe trait ExternType {
     /// ```
     type Id;
 
-    /// Either [`cxx::kind::Opaque`] or [`cxx::kind::Trivial`].
+    /// One of [`cxx::kind::Opaque`], [`cxx::kind::Trivial`] or
+    /// [`cxx::kind::Relocatable`].
     ///
     /// [`cxx::kind::Opaque`]: kind::Opaque
     /// [`cxx::kind::Trivial`]: kind::Trivial
+    /// [`cxx::kind::Relocatable`]: kind::Relocatable
     ///
     /// A C++ type is only okay to hold and pass around by value in Rust if its
     /// [move constructor is trivial] and it has no destructor. In CXX, these
@@ -140,6 +142,13 @@ pub unsafe trait ExternType {
     /// by value, and include it in `struct`s that you have declared to
     /// `cxx::bridge`. Your claim about the triviality of the C++ type will be
     /// checked by a `static_assert` in the generated C++ side of the binding.
+    ///
+    /// A Rust type that wraps a C++ object it destroys in its own `Drop`
+    /// impl, such as a `#[repr(transparent)]` newtype around a
+    /// [`SeastarLwSharedPtr`][crate::SeastarLwSharedPtr], may be passed and
+    /// returned by value in the same way if the C++ type is relocatable,
+    /// while not being trivially destructible. Such a type specifies
+    /// `type Kind = cxx::kind::Relocatable;` instead.
     type Kind: Kind;
 }
 
@@ -168,23 +177,47 @@ pub mod kind {
     /// indirection.
     pub enum Trivial {}
 
+    /// A type which can be moved around in Rust code like a Trivial one, with
+    /// every move a memcpy, but which has a destructor run by its Rust `Drop`
+    /// impl.
+    ///
+    /// This is the kind of a `#[repr(transparent)]` newtype around a
+    /// SeastarSharedPtr or SeastarLwSharedPtr, whose C++ counterpart is the
+    /// seastar pointer itself. It can be passed into and returned from C++
+    /// functions by value, with the C++ side checking that the type is
+    /// relocatable, but it is not accepted where a type without a destructor
+    /// is needed, such as the object of `SeastarLwSharedPtr::new`.
+    pub enum Relocatable {}
+
     #[allow(missing_docs)]
     pub trait Kind: private::Sealed {}
     impl Kind for Opaque {}
     impl Kind for Trivial {}
+    impl Kind for Relocatable {}
 }
 
 mod private {
     pub trait Sealed {}
     impl Sealed for super::Opaque {}
     impl Sealed for super::Trivial {}
+    impl Sealed for super::Relocatable {}
 }
 
+// Kinds of extern types which may exist by value in Rust.
+#[doc(hidden)]
+pub trait ByValue: Kind {}
+impl ByValue for Trivial {}
+impl ByValue for Relocatable {}
+
 #[doc(hidden)]
 pub fn verify_extern_type<T: ExternType<Id = Id>, Id>() {}
 
 #[doc(hidden)]
-pub fn verify_extern_kind<T: ExternType<Kind = Kind>, Kind: self::Kind>() {}
+pub fn verify_extern_by_value<T: ExternType>()
+where
+    T::Kind: ByValue,
+{
+}
 
 macro_rules! impl_extern_type {
     ($([$kind:ident] $($(#[$($attr:tt)*])* $ty:path = $cxxpath:literal)*)*) => {
@@ -1,9 +1,13 @@
-use core::fmt::{self, Display};
+use core::fmt::{self, Debug, Display};
 
 pub(crate) fn display(fmt: impl Fn(&mut fmt::Formatter) -> fmt::Result) -> impl Display {
     DisplayInvoke(fmt)
 }
 
+pub(crate) fn debug(fmt: impl Fn(&mut fmt::Formatter) -> fmt::Result) -> impl Debug {
+    DisplayInvoke(fmt)
+}
+
 struct DisplayInvoke<T>(T);
 
 impl<T> Display for DisplayInvoke<T>
@@ -14,3 +18,12 @@ where
         (self.0)(formatter)
     }
 }
+
+impl<T> Debug for DisplayInvoke<T>
+where
+    T: Fn(&mut fmt::Formatter) -> fmt::Result,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        (self.0)(formatter)
+    }
+}
@@ -451,6 +451,7 @@ mod function;
 mod hash;
 mod lossy;
 pub mod memory;
+mod null_ptr_error;
 mod opaque;
 mod result;
 mod rust_slice;
@@ -458,8 +459,32 @@ mod rust_str;
 mod rust_string;
 mod rust_type;
 mod rust_vec;
+mod seastar_abort_source;
+mod seastar_bool_class;
+mod seastar_chunked_fifo;
+mod seastar_circular_buffer;
+mod seastar_condition_variable;
+mod seastar_exception;
+mod seastar_file;
+mod seastar_future;
+mod seastar_gate;
+mod seastar_graph;
+mod seastar_lru_cache;
 mod seastar_lw_shared_ptr;
+mod seastar_metrics;
+mod seastar_ptr_tracing;
+mod seastar_rwlock;
+mod seastar_semaphore;
+mod seastar_shard_affinity;
 mod seastar_shared_ptr;
+mod seastar_shared_slice;
+mod seastar_smp;
+mod seastar_socket_address;
+mod seastar_stream;
+mod seastar_temporary_buffer;
+mod seastar_timer;
+mod seastar_when_all;
+mod shared_mut_error;
 mod shared_ptr;
 mod sip;
 #[path = "cxx_string.rs"]
@@ -475,8 +500,35 @@ pub use crate::cxx_vector::CxxVector;
 #[cfg(feature = "alloc")]
 pub use crate::exception::Exception;
 pub use crate::extern_type::{kind, ExternType};
+pub use crate::null_ptr_error::NullPtrError;
+pub use crate::seastar_abort_source::SeastarAbortSource;
+pub use crate::seastar_chunked_fifo::SeastarChunkedFifo;
+pub use crate::seastar_circular_buffer::SeastarCircularBuffer;
+pub use crate::seastar_condition_variable::SeastarConditionVariable;
+#[cfg(feature = "alloc")]
+pub use crate::seastar_exception::SeastarException;
+#[cfg(feature = "alloc")]
+pub use crate::seastar_file::SeastarFile;
+pub use crate::seastar_future::SeastarFuture;
+pub use crate::seastar_gate::SeastarGate;
 pub use crate::seastar_lw_shared_ptr::SeastarLwSharedPtr;
+#[cfg(feature = "alloc")]
+pub use crate::seastar_metrics::SeastarMetrics;
+pub use crate::seastar_rwlock::SeastarRwLock;
+pub use crate::seastar_semaphore::SeastarSemaphore;
 pub use crate::seastar_shared_ptr::SeastarSharedPtr;
+pub use crate::seastar_shared_slice::SeastarSharedSlice;
+#[cfg(feature = "std")]
+pub use crate::seastar_smp::seastar_submit_to;
+#[cfg(feature = "std")]
+pub use crate::seastar_socket_address::SeastarSocketAddress;
+#[cfg(feature = "alloc")]
+pub use crate::seastar_stream::{seastar_pipe, SeastarInputStream, SeastarOutputStream};
+pub use crate::seastar_temporary_buffer::SeastarTemporaryBuffer;
+pub use crate::seastar_timer::seastar_sleep;
+#[cfg(feature = "alloc")]
+pub use crate::seastar_when_all::{seastar_when_all2, seastar_when_all3};
+pub use crate::shared_mut_error::SharedMutError;
 pub use crate::shared_ptr::SharedPtr;
 pub use crate::string::CxxString;
 pub use crate::unique_ptr::UniquePtr;
@@ -502,7 +554,7 @@ pub type Vector<T> = CxxVector<T>;
 pub mod private {
     pub use crate::c_char::c_char;
     pub use crate::cxx_vector::VectorElement;
-    pub use crate::extern_type::{verify_extern_kind, verify_extern_type};
+    pub use crate::extern_type::{verify_extern_by_value, verify_extern_type};
     pub use crate::function::FatFunction;
     pub use crate::hash::hash;
     pub use crate::opaque::Opaque;
@@ -516,7 +568,7 @@ pub mod private {
     #[cfg(feature = "alloc")]
     pub use crate::rust_vec::RustVec;
     pub use crate::seastar_lw_shared_ptr::SeastarLwSharedPtrTarget;
-    pub use crate::seastar_shared_ptr::SeastarSharedPtrTarget;
+    pub use crate::seastar_shared_ptr::{SeastarSharedPtrTarget, SeastarUpcast};
     pub use crate::shared_ptr::SharedPtrTarget;
     pub use crate::string::StackString;
     pub use crate::unique_ptr::UniquePtrTarget;
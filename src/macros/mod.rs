@@ -2,3 +2,5 @@
 mod assert;
 #[macro_use]
 mod concat;
+#[macro_use]
+mod trace;
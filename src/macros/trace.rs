@@ -0,0 +1,10 @@
+// Reports a seastar pointer event to the registered tracer. Expands to nothing
+// unless the "ptr-tracing" feature is enabled.
+macro_rules! trace_ptr {
+    ($event:ident($kind:ident)) => {
+        #[cfg(feature = "ptr-tracing")]
+        crate::seastar_ptr_tracing::trace(crate::seastar_ptr_tracing::PtrEvent::$event(
+            crate::seastar_ptr_tracing::PtrKind::$kind,
+        ));
+    };
+}
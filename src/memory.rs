@@ -2,8 +2,29 @@
 //!
 //! The pointer types themselves are exposed at the crate root.
 
+#[cfg(feature = "alloc")]
+pub use crate::seastar_abort_source::AbortSubscription;
+pub use crate::seastar_chunked_fifo::SeastarChunkedFifoElement;
+pub use crate::seastar_circular_buffer::SeastarCircularBufferElement;
+#[cfg(feature = "alloc")]
+pub use crate::seastar_file::{DmaReadFuture, OpenFileFuture, OpenFlags};
+pub use crate::seastar_future::SeastarFutureTarget;
+pub use crate::seastar_gate::GateHolder;
+#[cfg(feature = "serde")]
+pub use crate::seastar_graph::{deserialize_graph, serialize_graph, SeastarGraphNode};
+#[cfg(feature = "std")]
+pub use crate::seastar_lru_cache::{LruCache, LruCacheKeys};
 pub use crate::seastar_lw_shared_ptr::SeastarLwSharedPtrTarget;
-pub use crate::seastar_shared_ptr::SeastarSharedPtrTarget;
+#[cfg(feature = "alloc")]
+pub use crate::seastar_metrics::MetricRegistration;
+#[cfg(feature = "ptr-tracing")]
+pub use crate::seastar_ptr_tracing::{set_ptr_tracer, PtrEvent, PtrKind};
+#[cfg(feature = "alloc")]
+pub use crate::seastar_rwlock::{RwLockReadFuture, RwLockWriteFuture};
+pub use crate::seastar_rwlock::{RwLockReadGuard, RwLockWriteGuard};
+pub use crate::seastar_shared_ptr::{SeastarRef, SeastarSharedPtrTarget, SeastarUpcast};
+#[cfg(feature = "alloc")]
+pub use crate::seastar_when_all::{WhenAll2, WhenAll3};
 pub use crate::shared_ptr::SharedPtrTarget;
 pub use crate::unique_ptr::UniquePtrTarget;
 pub use crate::weak_ptr::WeakPtrTarget;
@@ -0,0 +1,43 @@
+use crate::fmt::display;
+use core::fmt::{self, Debug, Display};
+
+/// Error returned by the fallible accessors of [`SeastarSharedPtr`] and
+/// [`SeastarLwSharedPtr`] when the pointer does not own an object.
+///
+/// [`SeastarSharedPtr`]: crate::SeastarSharedPtr
+/// [`SeastarLwSharedPtr`]: crate::SeastarLwSharedPtr
+#[derive(Copy, Clone)]
+pub struct NullPtrError {
+    ptr: &'static str,
+    typename: fn(&mut fmt::Formatter) -> fmt::Result,
+}
+
+impl NullPtrError {
+    pub(crate) fn new(ptr: &'static str, typename: fn(&mut fmt::Formatter) -> fmt::Result) -> Self {
+        NullPtrError { ptr, typename }
+    }
+}
+
+impl Display for NullPtrError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{}<{}> is null",
+            self.ptr,
+            display(self.typename),
+        )
+    }
+}
+
+impl Debug for NullPtrError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_tuple("NullPtrError")
+            .field(&format_args!("{}<{}>", self.ptr, display(self.typename)))
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for NullPtrError {}
@@ -37,6 +37,13 @@ where
 }
 
 unsafe fn to_c_error(msg: String) -> Result {
+    let err = unsafe { copy_error(msg) };
+    Result { err }
+}
+
+// Copies the message, NUL terminated, into a buffer allocated by C++ with
+// `new[]`, for C++ to take ownership of.
+pub(crate) unsafe fn copy_error(msg: String) -> PtrLen {
     let mut msg = msg;
     unsafe { msg.as_mut_vec() }.push(b'\0');
     let ptr = msg.as_ptr();
@@ -48,8 +55,7 @@ unsafe fn to_c_error(msg: String) -> Result {
     }
 
     let copy = unsafe { error(ptr, len) };
-    let err = PtrLen { ptr: copy, len };
-    Result { err }
+    PtrLen { ptr: copy, len }
 }
 
 impl Result {
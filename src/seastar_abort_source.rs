@@ -0,0 +1,165 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+#[cfg(feature = "alloc")]
+use core::marker::PhantomData;
+
+extern "C" {
+    #[link_name = "cxxbridge1$seastar$abort_source$new"]
+    fn abort_source_new() -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$abort_source$request_abort"]
+    fn abort_source_request_abort(this: *mut c_void);
+    #[link_name = "cxxbridge1$seastar$abort_source$abort_requested"]
+    fn abort_source_abort_requested(this: *const c_void) -> bool;
+    #[cfg(feature = "alloc")]
+    #[link_name = "cxxbridge1$seastar$abort_source$subscribe"]
+    fn abort_source_subscribe(
+        this: *mut c_void,
+        data: *mut c_void,
+        call: unsafe extern "C" fn(*mut c_void),
+    ) -> *mut c_void;
+    #[cfg(feature = "alloc")]
+    #[link_name = "cxxbridge1$seastar$abort_source$unsubscribe"]
+    fn abort_source_unsubscribe(subscription: *mut c_void);
+    #[link_name = "cxxbridge1$seastar$abort_source$drop"]
+    fn abort_source_drop(this: *mut c_void);
+}
+
+/// Binding to C++ `seastar::abort_source`.
+///
+/// An abort source lets one party cancel operations run by others. Each
+/// cancellable operation registers a callback through
+/// [`subscribe`][SeastarAbortSource::subscribe], and all of them run once
+/// [`request_abort`][SeastarAbortSource::request_abort] is called.
+///
+/// The abort source lives in its own C++ heap allocation and
+/// SeastarAbortSource owns a pointer to it. Like seastar::abort\_source itself
+/// it must be used from a single shard.
+pub struct SeastarAbortSource {
+    repr: *mut c_void,
+}
+
+impl SeastarAbortSource {
+    /// Makes a new abort source on which no abort has been requested.
+    pub fn new() -> Self {
+        SeastarAbortSource {
+            repr: unsafe { abort_source_new() },
+        }
+    }
+
+    /// Requests an abort, running the callbacks of all current subscriptions
+    /// before returning.
+    ///
+    /// Requesting an abort a second time does nothing.
+    pub fn request_abort(&self) {
+        unsafe { abort_source_request_abort(self.repr) }
+    }
+
+    /// Checks whether an abort has been requested.
+    ///
+    /// Matches the behavior of seastar::abort\_source::abort\_requested.
+    pub fn abort_requested(&self) -> bool {
+        unsafe { abort_source_abort_requested(self.repr) }
+    }
+
+    /// Registers `callback` to run when an abort is requested, returning a
+    /// guard that unsubscribes it when dropped.
+    ///
+    /// The callback runs at most once, on the shard calling
+    /// [`request_abort`][SeastarAbortSource::request_abort]. Returns None
+    /// without registering anything if an abort has already been requested,
+    /// matching seastar::abort\_source::subscribe.
+    ///
+    /// A panic in the callback cannot unwind through seastar and aborts the
+    /// process instead.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn subscribe<F>(&self, callback: F) -> Option<AbortSubscription<'_>>
+    where
+        F: FnOnce() + 'static,
+    {
+        let data = Box::into_raw(Box::new(Some(callback))).cast::<c_void>();
+        let repr = unsafe { abort_source_subscribe(self.repr, data, call_callback::<F>) };
+        if repr.is_null() {
+            unsafe { drop_callback::<F>(data) }
+            None
+        } else {
+            Some(AbortSubscription {
+                repr,
+                data,
+                drop_callback: drop_callback::<F>,
+                source: PhantomData,
+            })
+        }
+    }
+}
+
+impl Default for SeastarAbortSource {
+    fn default() -> Self {
+        SeastarAbortSource::new()
+    }
+}
+
+impl Drop for SeastarAbortSource {
+    fn drop(&mut self) {
+        unsafe { abort_source_drop(self.repr) }
+    }
+}
+
+impl Debug for SeastarAbortSource {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("SeastarAbortSource")
+            .field("abort_requested", &self.abort_requested())
+            .finish()
+    }
+}
+
+/// Guard keeping a callback registered with a [`SeastarAbortSource`].
+///
+/// Dropping the guard unsubscribes the callback if it has not run yet, and
+/// frees it either way.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct AbortSubscription<'a> {
+    repr: *mut c_void,
+    data: *mut c_void,
+    drop_callback: unsafe fn(*mut c_void),
+    source: PhantomData<&'a SeastarAbortSource>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Drop for AbortSubscription<'a> {
+    fn drop(&mut self) {
+        // Unlink from the abort source before freeing the callback it points
+        // to.
+        unsafe {
+            abort_source_unsubscribe(self.repr);
+            (self.drop_callback)(self.data);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Debug for AbortSubscription<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("AbortSubscription")
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe extern "C" fn call_callback<F>(data: *mut c_void)
+where
+    F: FnOnce(),
+{
+    let callback = unsafe { &mut *data.cast::<Option<F>>() };
+    if let Some(callback) = callback.take() {
+        crate::unwind::prevent_unwind("SeastarAbortSource callback", callback);
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe fn drop_callback<F>(data: *mut c_void) {
+    drop(unsafe { Box::from_raw(data.cast::<Option<F>>()) });
+}
@@ -0,0 +1,79 @@
+/// Declares a Rust binding to one instantiation of C++
+/// `seastar::bool_class<Tag>`.
+///
+/// Seastar uses `bool_class` for strongly typed boolean parameters, such as
+/// `seastar::stop_iteration`. Every tag produces a distinct C++ type, so each
+/// instantiation used across the bridge needs its own Rust type. This macro
+/// declares that type as a `#[repr(transparent)]` wrapper around `bool` with
+/// `YES`/`NO` constants and conversions from and to `bool`, and implements
+/// [`ExternType`] for it as a trivial type named by the given C++ path.
+///
+/// Register one type per tag, then refer to it from the bridge as an extern
+/// type alias. It can be passed and returned by value like any other trivial
+/// extern type.
+///
+/// ```no_run
+/// cxx::seastar_bool_class! {
+///     /// Binding to `seastar::stop_iteration`.
+///     pub struct StopIteration = "seastar::stop_iteration";
+/// }
+///
+/// #[cxx::bridge]
+/// mod ffi {
+///     #[namespace = "seastar"]
+///     extern "C++" {
+///         include!(<seastar/core/future.hh>);
+///
+///         type stop_iteration = crate::StopIteration;
+///     }
+///
+///     unsafe extern "C++" {
+///         fn keep_going(n: u32) -> stop_iteration;
+///     }
+/// }
+///
+/// # fn main() {}
+/// ```
+///
+/// [`ExternType`]: trait.ExternType.html
+#[macro_export]
+macro_rules! seastar_bool_class {
+    ($(#[$attr:meta])* $vis:vis struct $name:ident = $cxx:literal;) => {
+        $(#[$attr])*
+        #[repr(transparent)]
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name(bool);
+
+        impl $name {
+            /// The `true` value of this flag.
+            pub const YES: Self = $name(true);
+            /// The `false` value of this flag.
+            pub const NO: Self = $name(false);
+        }
+
+        impl ::core::convert::From<bool> for $name {
+            fn from(value: bool) -> Self {
+                $name(value)
+            }
+        }
+
+        impl ::core::convert::From<$name> for bool {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl ::core::ops::Not for $name {
+            type Output = Self;
+
+            fn not(self) -> Self {
+                $name(!self.0)
+            }
+        }
+
+        unsafe impl $crate::ExternType for $name {
+            type Id = $crate::type_id!($cxx);
+            type Kind = $crate::kind::Trivial;
+        }
+    };
+}
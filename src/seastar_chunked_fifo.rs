@@ -0,0 +1,203 @@
+use crate::fmt::display;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+
+/// Binding to C++ `seastar::chunked_fifo<T>`.
+///
+/// The queue lives in its own C++ heap allocation and SeastarChunkedFifo owns
+/// a pointer to it. Like seastar::chunked\_fifo itself it must be used from a
+/// single shard.
+pub struct SeastarChunkedFifo<T>
+where
+    T: SeastarChunkedFifoElement,
+{
+    repr: *mut c_void,
+    ty: PhantomData<T>,
+}
+
+impl<T> SeastarChunkedFifo<T>
+where
+    T: SeastarChunkedFifoElement,
+{
+    /// Makes a new empty queue.
+    pub fn new() -> Self {
+        SeastarChunkedFifo {
+            repr: unsafe { T::__new() },
+            ty: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements in the queue.
+    ///
+    /// Matches the behavior of C++ seastar::chunked\_fifo\<T\>::size.
+    pub fn len(&self) -> usize {
+        unsafe { T::__size(self.repr) }
+    }
+
+    /// Returns true if the queue contains no elements.
+    ///
+    /// Matches the behavior of C++ seastar::chunked\_fifo\<T\>::empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the element at the front of the queue, or
+    /// `None` if the queue is empty.
+    pub fn front(&self) -> Option<&T> {
+        unsafe { T::__front(self.repr).as_ref() }
+    }
+
+    /// Appends an element to the back of the queue.
+    ///
+    /// Matches the behavior of C++ seastar::chunked\_fifo\<T\>::push\_back.
+    pub fn push_back(&mut self, value: T) {
+        unsafe { T::__push_back(self.repr, value) }
+    }
+
+    /// Removes the element at the front of the queue and returns it, or
+    /// `None` if the queue is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let mut value = MaybeUninit::uninit();
+        if unsafe { T::__pop_front(self.repr, value.as_mut_ptr()) } {
+            Some(unsafe { value.assume_init() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Default for SeastarChunkedFifo<T>
+where
+    T: SeastarChunkedFifoElement,
+{
+    fn default() -> Self {
+        SeastarChunkedFifo::new()
+    }
+}
+
+impl<T> Drop for SeastarChunkedFifo<T>
+where
+    T: SeastarChunkedFifoElement,
+{
+    fn drop(&mut self) {
+        unsafe { T::__drop(self.repr) }
+    }
+}
+
+impl<T> Debug for SeastarChunkedFifo<T>
+where
+    T: SeastarChunkedFifoElement,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "SeastarChunkedFifo<{}> {{ len: {} }}",
+            display(T::__typename),
+            self.len(),
+        )
+    }
+}
+
+/// Trait bound for types which may be used as the `T` inside of a
+/// `SeastarChunkedFifo<T>` in generic code.
+///
+/// This trait has no publicly callable or implementable methods. Implementing
+/// it outside of the CXX codebase is not supported.
+///
+/// So far it is implemented only for the primitive types.
+pub unsafe trait SeastarChunkedFifoElement: Sized {
+    #[doc(hidden)]
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result;
+    #[doc(hidden)]
+    unsafe fn __new() -> *mut c_void;
+    #[doc(hidden)]
+    unsafe fn __size(this: *const c_void) -> usize;
+    #[doc(hidden)]
+    unsafe fn __front(this: *const c_void) -> *const Self;
+    #[doc(hidden)]
+    unsafe fn __push_back(this: *mut c_void, value: Self);
+    #[doc(hidden)]
+    unsafe fn __pop_front(this: *mut c_void, out: *mut Self) -> bool;
+    #[doc(hidden)]
+    unsafe fn __drop(this: *mut c_void);
+}
+
+macro_rules! impl_chunked_fifo_element_for_primitive {
+    ($ty:ident, $name:expr) => {
+        unsafe impl SeastarChunkedFifoElement for $ty {
+            fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str($name)
+            }
+            unsafe fn __new() -> *mut c_void {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$chunked_fifo$", stringify!($ty), "$new")]
+                        fn __new() -> *mut c_void;
+                    }
+                }
+                unsafe { __new() }
+            }
+            unsafe fn __size(this: *const c_void) -> usize {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$chunked_fifo$", stringify!($ty), "$size")]
+                        fn __size(this: *const c_void) -> usize;
+                    }
+                }
+                unsafe { __size(this) }
+            }
+            unsafe fn __front(this: *const c_void) -> *const Self {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$chunked_fifo$", stringify!($ty), "$front")]
+                        fn __front(this: *const c_void) -> *const $ty;
+                    }
+                }
+                unsafe { __front(this) }
+            }
+            unsafe fn __push_back(this: *mut c_void, value: Self) {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$chunked_fifo$", stringify!($ty), "$push_back")]
+                        fn __push_back(this: *mut c_void, value: $ty);
+                    }
+                }
+                unsafe { __push_back(this, value) }
+            }
+            unsafe fn __pop_front(this: *mut c_void, out: *mut Self) -> bool {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$chunked_fifo$", stringify!($ty), "$pop_front")]
+                        fn __pop_front(this: *mut c_void, out: *mut $ty) -> bool;
+                    }
+                }
+                unsafe { __pop_front(this, out) }
+            }
+            unsafe fn __drop(this: *mut c_void) {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$chunked_fifo$", stringify!($ty), "$drop")]
+                        fn __drop(this: *mut c_void);
+                    }
+                }
+                unsafe { __drop(this) }
+            }
+        }
+    };
+}
+
+impl_chunked_fifo_element_for_primitive!(bool, "bool");
+impl_chunked_fifo_element_for_primitive!(u8, "uint8_t");
+impl_chunked_fifo_element_for_primitive!(u16, "uint16_t");
+impl_chunked_fifo_element_for_primitive!(u32, "uint32_t");
+impl_chunked_fifo_element_for_primitive!(u64, "uint64_t");
+impl_chunked_fifo_element_for_primitive!(usize, "size_t");
+impl_chunked_fifo_element_for_primitive!(i8, "int8_t");
+impl_chunked_fifo_element_for_primitive!(i16, "int16_t");
+impl_chunked_fifo_element_for_primitive!(i32, "int32_t");
+impl_chunked_fifo_element_for_primitive!(i64, "int64_t");
+impl_chunked_fifo_element_for_primitive!(isize, "rust::isize");
+impl_chunked_fifo_element_for_primitive!(f32, "float");
+impl_chunked_fifo_element_for_primitive!(f64, "double");
@@ -0,0 +1,157 @@
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::marker::{PhantomData, PhantomPinned};
+use core::mem;
+
+/// Binding to C++ `seastar::circular_buffer<T>`.
+///
+/// Only read access is supported so far. Unlike `std::vector`, the elements
+/// of a circular\_buffer are not contiguous in memory, so there is no slice
+/// view; elements are reached one at a time by index from the front.
+///
+/// # Invariants
+///
+/// As with [`CxxVector`][crate::CxxVector], in Rust code we can never obtain a
+/// `SeastarCircularBuffer` by value. Rust code only ever looks at a
+/// circular\_buffer behind a reference, as in `&SeastarCircularBuffer<T>`.
+#[repr(C, packed)]
+pub struct SeastarCircularBuffer<T> {
+    // A thing, because repr(C) structs are not allowed to consist exclusively
+    // of PhantomData fields.
+    _void: [c_void; 0],
+    // The conceptual buffer elements to ensure that autotraits are propagated
+    // correctly.
+    _elements: PhantomData<[T]>,
+    // Prevent unpin operation from Pin<&mut SeastarCircularBuffer<T>>.
+    _pinned: PhantomData<PhantomPinned>,
+}
+
+impl<T> SeastarCircularBuffer<T>
+where
+    T: SeastarCircularBufferElement,
+{
+    /// Returns the number of elements in the buffer.
+    ///
+    /// Matches the behavior of C++ seastar::circular\_buffer\<T\>::size.
+    pub fn len(&self) -> usize {
+        T::__size(self)
+    }
+
+    /// Returns true if the buffer contains no elements.
+    ///
+    /// Matches the behavior of C++ seastar::circular\_buffer\<T\>::empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the first element, or `None` if the buffer is
+    /// empty.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a reference to the last element, or `None` if the buffer is
+    /// empty.
+    pub fn back(&self) -> Option<&T> {
+        let len = self.len();
+        if len == 0 {
+            None
+        } else {
+            self.get(len - 1)
+        }
+    }
+
+    /// Returns a reference to the element at the given position counted from
+    /// the front, or `None` if out of bounds.
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        if pos < self.len() {
+            Some(unsafe { self.get_unchecked(pos) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to an element without doing bounds checking.
+    ///
+    /// This is generally not recommended, use with caution! Calling this method
+    /// with an out-of-bounds index is undefined behavior even if the resulting
+    /// reference is not used.
+    ///
+    /// Matches the behavior of C++
+    /// seastar::circular\_buffer\<T\>::operator\[\] const.
+    pub unsafe fn get_unchecked(&self, pos: usize) -> &T {
+        unsafe { &*T::__get_unchecked(self, pos) }
+    }
+}
+
+impl<T> Debug for SeastarCircularBuffer<T>
+where
+    T: SeastarCircularBufferElement + Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_list()
+            .entries((0..self.len()).map(|pos| unsafe { self.get_unchecked(pos) }))
+            .finish()
+    }
+}
+
+/// Trait bound for types which may be used as the `T` inside of a
+/// `SeastarCircularBuffer<T>` in generic code.
+///
+/// This trait has no publicly callable or implementable methods. Implementing
+/// it outside of the CXX codebase is not supported.
+///
+/// So far it is implemented only for the primitive numeric types.
+pub unsafe trait SeastarCircularBufferElement: Sized {
+    #[doc(hidden)]
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result;
+    #[doc(hidden)]
+    fn __size(buf: &SeastarCircularBuffer<Self>) -> usize;
+    #[doc(hidden)]
+    unsafe fn __get_unchecked(buf: &SeastarCircularBuffer<Self>, pos: usize) -> *const Self;
+}
+
+macro_rules! impl_circular_buffer_element_for_primitive {
+    ($ty:ident, $name:expr) => {
+        const_assert_eq!(0, mem::size_of::<SeastarCircularBuffer<$ty>>());
+        const_assert_eq!(1, mem::align_of::<SeastarCircularBuffer<$ty>>());
+
+        unsafe impl SeastarCircularBufferElement for $ty {
+            fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str($name)
+            }
+            fn __size(buf: &SeastarCircularBuffer<$ty>) -> usize {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$circular_buffer$", stringify!($ty), "$size")]
+                        fn __size(_: &SeastarCircularBuffer<$ty>) -> usize;
+                    }
+                }
+                unsafe { __size(buf) }
+            }
+            unsafe fn __get_unchecked(buf: &SeastarCircularBuffer<$ty>, pos: usize) -> *const $ty {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$circular_buffer$", stringify!($ty), "$get_unchecked")]
+                        fn __get_unchecked(_: &SeastarCircularBuffer<$ty>, _: usize) -> *const $ty;
+                    }
+                }
+                unsafe { __get_unchecked(buf, pos) }
+            }
+        }
+    };
+}
+
+impl_circular_buffer_element_for_primitive!(u8, "uint8_t");
+impl_circular_buffer_element_for_primitive!(u16, "uint16_t");
+impl_circular_buffer_element_for_primitive!(u32, "uint32_t");
+impl_circular_buffer_element_for_primitive!(u64, "uint64_t");
+impl_circular_buffer_element_for_primitive!(usize, "size_t");
+impl_circular_buffer_element_for_primitive!(i8, "int8_t");
+impl_circular_buffer_element_for_primitive!(i16, "int16_t");
+impl_circular_buffer_element_for_primitive!(i32, "int32_t");
+impl_circular_buffer_element_for_primitive!(i64, "int64_t");
+impl_circular_buffer_element_for_primitive!(isize, "rust::isize");
+impl_circular_buffer_element_for_primitive!(f32, "float");
+impl_circular_buffer_element_for_primitive!(f64, "double");
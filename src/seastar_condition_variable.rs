@@ -0,0 +1,93 @@
+use crate::seastar_future::SeastarFuture;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+
+extern "C" {
+    #[link_name = "cxxbridge1$seastar$condition_variable$new"]
+    fn condition_variable_new() -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$condition_variable$wait"]
+    fn condition_variable_wait(this: *mut c_void) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$condition_variable$signal"]
+    fn condition_variable_signal(this: *mut c_void);
+    #[link_name = "cxxbridge1$seastar$condition_variable$broadcast"]
+    fn condition_variable_broadcast(this: *mut c_void);
+    #[link_name = "cxxbridge1$seastar$condition_variable$has_waiters"]
+    fn condition_variable_has_waiters(this: *const c_void) -> bool;
+    #[link_name = "cxxbridge1$seastar$condition_variable$drop"]
+    fn condition_variable_drop(this: *mut c_void);
+}
+
+/// Binding to C++ `seastar::condition_variable`.
+///
+/// Tasks wait for a condition by awaiting the future returned by
+/// [`wait`][SeastarConditionVariable::wait], and whoever changes the
+/// condition wakes one of them with [`signal`][SeastarConditionVariable::signal]
+/// or all of them with [`broadcast`][SeastarConditionVariable::broadcast].
+/// Waiters are woken in the order they started waiting, and only by a signal:
+/// seastar has no spurious wakeups. A waiter should still check the condition
+/// again once woken, since another task may have changed it in between.
+///
+/// The condition variable lives in its own C++ heap allocation and
+/// SeastarConditionVariable owns a pointer to it. Like
+/// seastar::condition\_variable itself it must be used from a single shard.
+pub struct SeastarConditionVariable {
+    repr: *mut c_void,
+}
+
+impl SeastarConditionVariable {
+    /// Makes a new condition variable with no waiters.
+    pub fn new() -> Self {
+        SeastarConditionVariable {
+            repr: unsafe { condition_variable_new() },
+        }
+    }
+
+    /// Starts waiting, returning a future that resolves when this waiter is
+    /// signalled.
+    ///
+    /// If the condition variable is dropped first, the future resolves with
+    /// seastar::broken\_condition\_variable as its exception.
+    pub fn wait(&self) -> SeastarFuture<()> {
+        unsafe { SeastarFuture::from_raw(condition_variable_wait(self.repr)) }
+    }
+
+    /// Wakes the longest waiting waiter, if there is one.
+    ///
+    /// Matches the behavior of seastar::condition\_variable::signal.
+    pub fn signal(&self) {
+        unsafe { condition_variable_signal(self.repr) }
+    }
+
+    /// Wakes every waiter that started waiting before this call.
+    ///
+    /// Matches the behavior of seastar::condition\_variable::broadcast.
+    pub fn broadcast(&self) {
+        unsafe { condition_variable_broadcast(self.repr) }
+    }
+
+    /// Checks whether any waiter is still waiting to be signalled, without
+    /// waiting itself.
+    ///
+    /// Matches the behavior of seastar::condition\_variable::has\_waiters.
+    pub fn has_waiters(&self) -> bool {
+        unsafe { condition_variable_has_waiters(self.repr) }
+    }
+}
+
+impl Default for SeastarConditionVariable {
+    fn default() -> Self {
+        SeastarConditionVariable::new()
+    }
+}
+
+impl Drop for SeastarConditionVariable {
+    fn drop(&mut self) {
+        unsafe { condition_variable_drop(self.repr) }
+    }
+}
+
+impl Debug for SeastarConditionVariable {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("SeastarConditionVariable")
+    }
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "alloc")]
+
+use crate::result::PtrLen;
+use alloc::boxed::Box;
+use core::fmt::{self, Display};
+use core::slice;
+use core::str;
+
+/// Exception that a [`SeastarFuture`][crate::SeastarFuture] resolved with.
+///
+/// Carries the `what()` message of the C++ exception, or "unknown exception"
+/// if the exception was not derived from std::exception.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+pub struct SeastarException {
+    pub(crate) what: Box<str>,
+}
+
+impl Display for SeastarException {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.what)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for SeastarException {}
+
+impl SeastarException {
+    // Takes ownership of a message filled in by the seastar_exception_what
+    // helper on the C++ side.
+    pub(crate) unsafe fn from_ptr_len(what: PtrLen) -> Self {
+        let what = unsafe {
+            let slice = slice::from_raw_parts_mut(what.ptr.as_ptr(), what.len);
+            Box::from_raw(str::from_utf8_unchecked_mut(slice))
+        };
+        SeastarException { what }
+    }
+
+    /// Returns the message of the C++ exception.
+    pub fn what(&self) -> &str {
+        &self.what
+    }
+}
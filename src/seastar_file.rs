@@ -0,0 +1,318 @@
+#![cfg(feature = "alloc")]
+
+use crate::seastar_exception::SeastarException;
+use crate::seastar_future::SeastarFuture;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::future::Future;
+use core::ops::{BitOr, BitOrAssign};
+use core::pin::Pin;
+use core::ptr;
+use core::task::{Context, Poll};
+
+extern "C" {
+    #[link_name = "cxxbridge1$seastar$file$new"]
+    fn file_new() -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$file$open"]
+    fn file_open(this: *mut c_void, path: *const u8, path_len: usize, flags: u32) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$file$dma_read"]
+    fn file_dma_read(
+        this: *mut c_void,
+        pos: u64,
+        len: usize,
+        buffer: *mut *mut c_void,
+    ) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$file$dma_write"]
+    fn file_dma_write(this: *mut c_void, pos: u64, buf: *const u8, len: usize) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$file$close"]
+    fn file_close(this: *mut c_void) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$file$disk_read_dma_alignment"]
+    fn file_disk_read_dma_alignment(this: *const c_void) -> u64;
+    #[link_name = "cxxbridge1$seastar$file$disk_write_dma_alignment"]
+    fn file_disk_write_dma_alignment(this: *const c_void) -> u64;
+    #[link_name = "cxxbridge1$seastar$file$drop"]
+    fn file_drop(this: *mut c_void);
+    #[link_name = "cxxbridge1$seastar$file$read_buffer$data"]
+    fn read_buffer_data(this: *const c_void) -> *const u8;
+    #[link_name = "cxxbridge1$seastar$file$read_buffer$drop"]
+    fn read_buffer_drop(this: *mut c_void);
+}
+
+/// Binding to C++ `seastar::file`, opened for direct I/O.
+///
+/// The file lives in its own C++ heap allocation and SeastarFile owns a
+/// reference counted pointer to it, which reads, writes and closes still in
+/// flight hold on to as well. Like seastar::file itself it must be used from
+/// the shard that opened it, and should be [closed][SeastarFile::close]
+/// before it is dropped.
+///
+/// Direct I/O moves data between the disk and memory in blocks, so seastar
+/// requires positions, lengths and buffers to be aligned. SeastarFile stages
+/// data through aligned C++ buffers, which lifts the requirement on the Rust
+/// buffers at the cost of a copy. Reads may start anywhere, while writes must
+/// still start and end on a multiple of
+/// [`disk_write_dma_alignment`][SeastarFile::disk_write_dma_alignment].
+///
+/// ```no_run
+/// # async fn example() -> Result<(), cxx::SeastarException> {
+/// use cxx::memory::OpenFlags;
+/// use cxx::SeastarFile;
+///
+/// let file = SeastarFile::open("data.bin", OpenFlags::READ_ONLY).await?;
+/// let mut buf = [0; 100];
+/// let len = file.dma_read(0, &mut buf).await?;
+/// println!("{:?}", &buf[..len]);
+/// file.close().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct SeastarFile {
+    repr: *mut c_void,
+}
+
+impl SeastarFile {
+    /// Starts opening the file at `path`, returning a future that resolves
+    /// to the open file.
+    ///
+    /// Matches the behavior of seastar::open\_file\_dma. Dropping the future
+    /// before it resolves does not cancel the open; a file opened after that
+    /// is released again without being closed.
+    pub fn open(path: &str, flags: OpenFlags) -> OpenFileFuture {
+        let file = SeastarFile {
+            repr: unsafe { file_new() },
+        };
+        let opened = unsafe { file_open(file.repr, path.as_ptr(), path.len(), flags.0) };
+        OpenFileFuture {
+            file: Some(file),
+            opened: unsafe { SeastarFuture::from_raw(opened) },
+        }
+    }
+
+    /// Starts reading into `buf` from position `pos` of the file, returning
+    /// a future that resolves to the number of bytes read.
+    ///
+    /// Matches the behavior of seastar::file::dma\_read, reading
+    /// `buf.len()` bytes unless the end of the file comes first. Neither
+    /// `pos` nor `buf` need to be aligned: seastar reads the aligned blocks
+    /// covering the range into a buffer of its own, and the bytes are copied
+    /// into `buf` when the future resolves. Dropping the future before then
+    /// leaves `buf` untouched.
+    pub fn dma_read<'a>(&self, pos: u64, buf: &'a mut [u8]) -> DmaReadFuture<'a> {
+        let mut buffer = ptr::null_mut();
+        let read = unsafe { file_dma_read(self.repr, pos, buf.len(), &mut buffer) };
+        DmaReadFuture {
+            buf,
+            buffer,
+            read: unsafe { SeastarFuture::from_raw(read) },
+        }
+    }
+
+    /// Starts writing `buf` at position `pos` of the file, returning a
+    /// future that resolves to the number of bytes written.
+    ///
+    /// Matches the behavior of seastar::file::dma\_write. `buf` is copied
+    /// into an aligned buffer before this returns, so it need not be aligned
+    /// itself, but both `pos` and `buf.len()` must be multiples of
+    /// [`disk_write_dma_alignment`][SeastarFile::disk_write_dma_alignment].
+    /// Otherwise the future resolves to `Err` without anything having been
+    /// written.
+    pub fn dma_write(&self, pos: u64, buf: &[u8]) -> SeastarFuture<usize> {
+        unsafe { SeastarFuture::from_raw(file_dma_write(self.repr, pos, buf.as_ptr(), buf.len())) }
+    }
+
+    /// Starts closing the file, returning a future that resolves once it has
+    /// been closed.
+    ///
+    /// Matches the behavior of seastar::file::close. Reads and writes must
+    /// not be started on the file afterwards.
+    pub fn close(&self) -> SeastarFuture<()> {
+        unsafe { SeastarFuture::from_raw(file_close(self.repr)) }
+    }
+
+    /// Returns the alignment, in bytes, of the blocks seastar reads from the
+    /// disk.
+    ///
+    /// Matches the behavior of seastar::file::disk\_read\_dma\_alignment.
+    pub fn disk_read_dma_alignment(&self) -> u64 {
+        unsafe { file_disk_read_dma_alignment(self.repr) }
+    }
+
+    /// Returns the alignment, in bytes, that positions and lengths passed to
+    /// [`dma_write`][SeastarFile::dma_write] must have.
+    ///
+    /// Matches the behavior of seastar::file::disk\_write\_dma\_alignment.
+    pub fn disk_write_dma_alignment(&self) -> u64 {
+        unsafe { file_disk_write_dma_alignment(self.repr) }
+    }
+}
+
+impl Drop for SeastarFile {
+    fn drop(&mut self) {
+        unsafe { file_drop(self.repr) }
+    }
+}
+
+impl Debug for SeastarFile {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("SeastarFile")
+    }
+}
+
+/// Flags a [`SeastarFile`] is opened with, matching `seastar::open_flags`.
+///
+/// Combine an access mode with any of the other flags using `|`. Without an
+/// access mode the file is opened read only.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct OpenFlags(u32);
+
+impl OpenFlags {
+    /// Opens the file for reading only, like seastar::open\_flags::ro.
+    pub const READ_ONLY: Self = OpenFlags(1);
+    /// Opens the file for writing only, like seastar::open\_flags::wo.
+    pub const WRITE_ONLY: Self = OpenFlags(2);
+    /// Opens the file for reading and writing, like seastar::open\_flags::rw.
+    pub const READ_WRITE: Self = OpenFlags(3);
+    /// Creates the file if it does not exist, like
+    /// seastar::open\_flags::create.
+    pub const CREATE: Self = OpenFlags(4);
+    /// Truncates the file to zero length, like
+    /// seastar::open\_flags::truncate.
+    pub const TRUNCATE: Self = OpenFlags(8);
+    /// Fails if the file already exists when combined with `CREATE`, like
+    /// seastar::open\_flags::exclusive.
+    pub const EXCLUSIVE: Self = OpenFlags(16);
+
+    /// Checks whether all of the flags in `other` are set.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for OpenFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        OpenFlags(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for OpenFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl Debug for OpenFlags {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let access = match self.0 & 3 {
+            2 => "WRITE_ONLY",
+            3 => "READ_WRITE",
+            _ => "READ_ONLY",
+        };
+        formatter.write_str(access)?;
+        for (flag, name) in [
+            (OpenFlags::CREATE, "CREATE"),
+            (OpenFlags::TRUNCATE, "TRUNCATE"),
+            (OpenFlags::EXCLUSIVE, "EXCLUSIVE"),
+        ] {
+            if self.contains(flag) {
+                write!(formatter, " | {}", name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Future returned by [`SeastarFile::open`].
+///
+/// Resolves to `Ok` with the open file, or to `Err` if seastar failed to
+/// open it.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct OpenFileFuture {
+    // None once the file has been handed out.
+    file: Option<SeastarFile>,
+    opened: SeastarFuture<()>,
+}
+
+impl OpenFileFuture {
+    /// Checks whether the file has been opened, or opening it failed, so
+    /// that awaiting would complete without waiting.
+    pub fn is_ready(&self) -> bool {
+        self.opened.is_ready()
+    }
+}
+
+impl Future for OpenFileFuture {
+    type Output = Result<SeastarFile, SeastarException>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let result = match Pin::new(&mut this.opened).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        let file = this.file.take();
+        Poll::Ready(result.map(|()| file.unwrap()))
+    }
+}
+
+impl Debug for OpenFileFuture {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("OpenFileFuture")
+    }
+}
+
+/// Future returned by [`SeastarFile::dma_read`].
+///
+/// Resolves to `Ok` with the number of bytes read into the buffer, or to
+/// `Err` if the read failed.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct DmaReadFuture<'a> {
+    buf: &'a mut [u8],
+    // The aligned C++ buffer seastar reads into, shared with the read.
+    buffer: *mut c_void,
+    read: SeastarFuture<usize>,
+}
+
+impl<'a> DmaReadFuture<'a> {
+    /// Checks whether the read has completed, or failed, so that awaiting
+    /// would complete without waiting.
+    pub fn is_ready(&self) -> bool {
+        self.read.is_ready()
+    }
+}
+
+impl<'a> Future for DmaReadFuture<'a> {
+    type Output = Result<usize, SeastarException>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let result = match Pin::new(&mut this.read).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(result.map(|len| {
+            let len = len.min(this.buf.len());
+            unsafe {
+                let data = read_buffer_data(this.buffer);
+                ptr::copy_nonoverlapping(data, this.buf.as_mut_ptr(), len);
+            }
+            len
+        }))
+    }
+}
+
+impl<'a> Drop for DmaReadFuture<'a> {
+    fn drop(&mut self) {
+        // A read still in flight keeps its own reference to the buffer.
+        unsafe { read_buffer_drop(self.buffer) }
+    }
+}
+
+impl<'a> Debug for DmaReadFuture<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("DmaReadFuture")
+    }
+}
@@ -0,0 +1,388 @@
+use crate::fmt::display;
+#[cfg(feature = "alloc")]
+use crate::result::PtrLen;
+#[cfg(feature = "alloc")]
+use crate::seastar_exception::SeastarException;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+#[cfg(feature = "alloc")]
+use core::future::Future;
+use core::marker::PhantomData;
+use core::mem;
+#[cfg(feature = "alloc")]
+use core::mem::MaybeUninit;
+#[cfg(feature = "alloc")]
+use core::pin::Pin;
+#[cfg(feature = "alloc")]
+use core::ptr;
+#[cfg(feature = "alloc")]
+use core::task::{Context, Poll, Waker};
+
+/// Binding to C++ `seastar::future<T>`.
+///
+/// A seastar::future cannot be relocated with a plain memcpy, so the future
+/// lives in its own C++ heap allocation and SeastarFuture owns a pointer to
+/// it. Rust code can make one already resolved, to return from an `extern
+/// "Rust"` function that a C++ caller may `co_await`, or receive one from an
+/// `extern "C++"` function and `.await` it.
+///
+/// Awaiting yields `Ok` with the value, or `Err` with the message of the
+/// exception the future failed with. A pending future wakes the awaiting task
+/// from a continuation run by the seastar reactor, so it must be awaited on
+/// the shard that created it, under an executor driven by that reactor.
+#[repr(C)]
+pub struct SeastarFuture<T>
+where
+    T: SeastarFutureTarget,
+{
+    repr: *mut c_void,
+    ty: PhantomData<T>,
+    #[cfg(feature = "alloc")]
+    state: PollState,
+}
+
+#[cfg(feature = "alloc")]
+enum PollState {
+    Idle,
+    // A continuation waking this waker has been chained onto the future.
+    Registered(Waker),
+    Done,
+}
+
+impl<T> SeastarFuture<T>
+where
+    T: SeastarFutureTarget,
+{
+    /// Makes a future that is already resolved with `value`.
+    ///
+    /// Matches the behavior of seastar::make\_ready\_future\<T\>.
+    pub fn ready(value: T) -> Self {
+        SeastarFuture {
+            repr: unsafe { T::__ready(value) },
+            ty: PhantomData,
+            #[cfg(feature = "alloc")]
+            state: PollState::Idle,
+        }
+    }
+
+    /// Checks whether the future has resolved, with either a value or an
+    /// exception, so that awaiting it would complete without waiting.
+    ///
+    /// Matches the behavior of seastar::future::available. Once the future
+    /// has been polled while pending, it only becomes ready after the seastar
+    /// reactor has run the continuation that wakes the awaiting task.
+    pub fn is_ready(&self) -> bool {
+        unsafe { T::__available(self.repr) }
+    }
+
+    /// Consumes the SeastarFuture, releasing its ownership of the heap
+    /// allocated seastar::future.
+    ///
+    /// The returned pointer is a `seastar::future<T> *` allocated with C++
+    /// `new`.
+    pub fn into_raw(self) -> *mut c_void {
+        let ptr = self.repr;
+        mem::forget(self);
+        ptr
+    }
+
+    /// Constructs a SeastarFuture retaking ownership of a heap allocated
+    /// seastar::future.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a `seastar::future<T> *` allocated with C++ `new`, such
+    /// as one previously returned by [`SeastarFuture::into_raw`], and must not
+    /// be owned by anything else.
+    pub unsafe fn from_raw(raw: *mut c_void) -> Self {
+        SeastarFuture {
+            repr: raw,
+            ty: PhantomData,
+            #[cfg(feature = "alloc")]
+            state: PollState::Idle,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<T> Future for SeastarFuture<T>
+where
+    T: SeastarFutureTarget,
+{
+    type Output = Result<T, SeastarException>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let waker = match &this.state {
+            PollState::Idle => Some(cx.waker()),
+            PollState::Registered(waker) if waker.will_wake(cx.waker()) => None,
+            PollState::Registered(_) => Some(cx.waker()),
+            PollState::Done => panic!(
+                "SeastarFuture<{}> polled after completion",
+                display(T::__typename),
+            ),
+        };
+        // Ownership of the boxed waker passes to the continuation if one is
+        // chained, which only happens while the future is pending.
+        let boxed = match waker {
+            Some(waker) => Box::into_raw(Box::new(waker.clone())).cast::<c_void>(),
+            None => ptr::null_mut(),
+        };
+        let mut value = MaybeUninit::<T>::uninit();
+        let mut exception = MaybeUninit::<PtrLen>::uninit();
+        let status = unsafe {
+            T::__poll(
+                this.repr,
+                boxed,
+                wake,
+                value.as_mut_ptr(),
+                exception.as_mut_ptr(),
+            )
+        };
+        if status == 0 {
+            if let Some(waker) = waker {
+                this.state = PollState::Registered(waker.clone());
+            }
+            return Poll::Pending;
+        }
+        if !boxed.is_null() {
+            drop(unsafe { Box::from_raw(boxed.cast::<Waker>()) });
+        }
+        this.state = PollState::Done;
+        if status == 1 {
+            Poll::Ready(Ok(unsafe { value.assume_init() }))
+        } else {
+            let exception = unsafe { exception.assume_init() };
+            Poll::Ready(Err(unsafe { SeastarException::from_ptr_len(exception) }))
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe extern "C" fn wake(waker: *mut c_void) {
+    let waker = unsafe { Box::from_raw(waker.cast::<Waker>()) };
+    crate::unwind::prevent_unwind("SeastarFuture waker", move || waker.wake());
+}
+
+// The seastar::future lives behind a pointer, so moving the SeastarFuture
+// does not move it.
+impl<T> Unpin for SeastarFuture<T> where T: SeastarFutureTarget {}
+
+impl<T> Drop for SeastarFuture<T>
+where
+    T: SeastarFutureTarget,
+{
+    fn drop(&mut self) {
+        unsafe { T::__drop(self.repr) }
+    }
+}
+
+impl<T> Debug for SeastarFuture<T>
+where
+    T: SeastarFutureTarget,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "SeastarFuture<{}>", display(T::__typename))
+    }
+}
+
+/// Trait bound for types which may be used as the `T` inside of a
+/// `SeastarFuture<T>` in generic code.
+///
+/// This trait has no publicly callable or implementable methods. Implementing
+/// it outside of the CXX codebase is not supported.
+///
+/// So far it is implemented only for the primitive types, for `()`, which
+/// stands for seastar::future\<\>, and for [`SeastarTemporaryBuffer`].
+///
+/// [`SeastarTemporaryBuffer`]: crate::SeastarTemporaryBuffer
+pub unsafe trait SeastarFutureTarget {
+    #[doc(hidden)]
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result;
+    #[doc(hidden)]
+    unsafe fn __ready(value: Self) -> *mut c_void
+    where
+        Self: Sized;
+    #[doc(hidden)]
+    #[cfg(feature = "alloc")]
+    unsafe fn __poll(
+        this: *mut c_void,
+        waker: *mut c_void,
+        wake: unsafe extern "C" fn(*mut c_void),
+        value: *mut Self,
+        exception: *mut PtrLen,
+    ) -> u8
+    where
+        Self: Sized;
+    #[doc(hidden)]
+    #[cfg(feature = "std")]
+    unsafe fn __submit_to(
+        shard: u32,
+        data: *mut c_void,
+        call: unsafe extern "C" fn(*mut c_void, *mut Self, *mut PtrLen) -> bool,
+    ) -> *mut c_void
+    where
+        Self: Sized;
+    #[doc(hidden)]
+    unsafe fn __available(this: *const c_void) -> bool;
+    #[doc(hidden)]
+    unsafe fn __drop(this: *mut c_void);
+}
+
+macro_rules! impl_future_target_for_primitive {
+    ($ty:ident, $name:expr) => {
+        unsafe impl SeastarFutureTarget for $ty {
+            fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str($name)
+            }
+            unsafe fn __ready(value: Self) -> *mut c_void {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$future$", stringify!($ty), "$ready")]
+                        fn __ready(value: $ty) -> *mut c_void;
+                    }
+                }
+                unsafe { __ready(value) }
+            }
+            #[cfg(feature = "alloc")]
+            unsafe fn __poll(
+                this: *mut c_void,
+                waker: *mut c_void,
+                wake: unsafe extern "C" fn(*mut c_void),
+                value: *mut Self,
+                exception: *mut PtrLen,
+            ) -> u8 {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$future$", stringify!($ty), "$poll")]
+                        fn __poll(
+                            this: *mut c_void,
+                            waker: *mut c_void,
+                            wake: unsafe extern "C" fn(*mut c_void),
+                            value: *mut $ty,
+                            exception: *mut PtrLen,
+                        ) -> u8;
+                    }
+                }
+                unsafe { __poll(this, waker, wake, value, exception) }
+            }
+            #[cfg(feature = "std")]
+            unsafe fn __submit_to(
+                shard: u32,
+                data: *mut c_void,
+                call: unsafe extern "C" fn(*mut c_void, *mut Self, *mut PtrLen) -> bool,
+            ) -> *mut c_void {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$future$", stringify!($ty), "$submit_to")]
+                        fn __submit_to(
+                            shard: u32,
+                            data: *mut c_void,
+                            call: unsafe extern "C" fn(*mut c_void, *mut $ty, *mut PtrLen) -> bool,
+                        ) -> *mut c_void;
+                    }
+                }
+                unsafe { __submit_to(shard, data, call) }
+            }
+            unsafe fn __available(this: *const c_void) -> bool {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$future$", stringify!($ty), "$available")]
+                        fn __available(this: *const c_void) -> bool;
+                    }
+                }
+                unsafe { __available(this) }
+            }
+            unsafe fn __drop(this: *mut c_void) {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$future$", stringify!($ty), "$drop")]
+                        fn __drop(this: *mut c_void);
+                    }
+                }
+                unsafe { __drop(this) }
+            }
+        }
+    };
+}
+
+impl_future_target_for_primitive!(bool, "bool");
+impl_future_target_for_primitive!(u8, "uint8_t");
+impl_future_target_for_primitive!(u16, "uint16_t");
+impl_future_target_for_primitive!(u32, "uint32_t");
+impl_future_target_for_primitive!(u64, "uint64_t");
+impl_future_target_for_primitive!(usize, "size_t");
+impl_future_target_for_primitive!(i8, "int8_t");
+impl_future_target_for_primitive!(i16, "int16_t");
+impl_future_target_for_primitive!(i32, "int32_t");
+impl_future_target_for_primitive!(i64, "int64_t");
+impl_future_target_for_primitive!(isize, "rust::isize");
+impl_future_target_for_primitive!(f32, "float");
+impl_future_target_for_primitive!(f64, "double");
+
+unsafe impl SeastarFutureTarget for () {
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("void")
+    }
+    unsafe fn __ready(_value: Self) -> *mut c_void {
+        extern "C" {
+            #[link_name = "cxxbridge1$seastar$future$unit$ready"]
+            fn __ready() -> *mut c_void;
+        }
+        unsafe { __ready() }
+    }
+    #[cfg(feature = "alloc")]
+    unsafe fn __poll(
+        this: *mut c_void,
+        waker: *mut c_void,
+        wake: unsafe extern "C" fn(*mut c_void),
+        _value: *mut Self,
+        exception: *mut PtrLen,
+    ) -> u8 {
+        // seastar::future<> carries no value, so there is nothing to write
+        // through `value`.
+        extern "C" {
+            #[link_name = "cxxbridge1$seastar$future$unit$poll"]
+            fn __poll(
+                this: *mut c_void,
+                waker: *mut c_void,
+                wake: unsafe extern "C" fn(*mut c_void),
+                exception: *mut PtrLen,
+            ) -> u8;
+        }
+        unsafe { __poll(this, waker, wake, exception) }
+    }
+    #[cfg(feature = "std")]
+    unsafe fn __submit_to(
+        shard: u32,
+        data: *mut c_void,
+        call: unsafe extern "C" fn(*mut c_void, *mut Self, *mut PtrLen) -> bool,
+    ) -> *mut c_void {
+        extern "C" {
+            #[link_name = "cxxbridge1$seastar$future$unit$submit_to"]
+            fn __submit_to(
+                shard: u32,
+                data: *mut c_void,
+                call: unsafe extern "C" fn(*mut c_void, *mut (), *mut PtrLen) -> bool,
+            ) -> *mut c_void;
+        }
+        unsafe { __submit_to(shard, data, call) }
+    }
+    unsafe fn __available(this: *const c_void) -> bool {
+        extern "C" {
+            #[link_name = "cxxbridge1$seastar$future$unit$available"]
+            fn __available(this: *const c_void) -> bool;
+        }
+        unsafe { __available(this) }
+    }
+    unsafe fn __drop(this: *mut c_void) {
+        extern "C" {
+            #[link_name = "cxxbridge1$seastar$future$unit$drop"]
+            fn __drop(this: *mut c_void);
+        }
+        unsafe { __drop(this) }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::seastar_future::SeastarFuture;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+
+extern "C" {
+    #[link_name = "cxxbridge1$seastar$gate$new"]
+    fn gate_new() -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$gate$try_enter"]
+    fn gate_try_enter(this: *mut c_void) -> bool;
+    #[link_name = "cxxbridge1$seastar$gate$leave"]
+    fn gate_leave(this: *mut c_void);
+    #[link_name = "cxxbridge1$seastar$gate$close"]
+    fn gate_close(this: *mut c_void) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$gate$is_closed"]
+    fn gate_is_closed(this: *const c_void) -> bool;
+    #[link_name = "cxxbridge1$seastar$gate$count"]
+    fn gate_count(this: *const c_void) -> usize;
+    #[link_name = "cxxbridge1$seastar$gate$drop"]
+    fn gate_drop(this: *mut c_void);
+}
+
+/// Binding to C++ `seastar::gate`.
+///
+/// A gate tracks in-flight operations so that a service can stop accepting
+/// new work and wait for the outstanding work to drain before shutting down.
+/// Each operation holds a [`GateHolder`] for as long as it runs.
+///
+/// The gate lives in its own C++ heap allocation and SeastarGate owns a
+/// pointer to it. Like seastar::gate itself it must be used from a single
+/// shard.
+pub struct SeastarGate {
+    repr: *mut c_void,
+}
+
+impl SeastarGate {
+    /// Makes a new open gate with no operations in flight.
+    pub fn new() -> Self {
+        SeastarGate {
+            repr: unsafe { gate_new() },
+        }
+    }
+
+    /// Registers an operation with the gate, returning a guard that leaves
+    /// the gate when dropped.
+    ///
+    /// Returns None if the gate has been closed, where seastar::gate::enter
+    /// would throw seastar::gate\_closed\_exception.
+    pub fn enter(&self) -> Option<GateHolder<'_>> {
+        if unsafe { gate_try_enter(self.repr) } {
+            Some(GateHolder { gate: self })
+        } else {
+            None
+        }
+    }
+
+    /// Closes the gate, so that any further [`enter`][SeastarGate::enter]
+    /// fails, returning a future that resolves once every operation in flight
+    /// has left the gate.
+    ///
+    /// Matches the behavior of seastar::gate::close. Operations already in
+    /// flight are unaffected, and the future is ready right away if there are
+    /// none. The gate is closed whether or not the future is awaited. Closing
+    /// an already closed gate is an error in seastar, so the future resolves
+    /// to `Err` with a seastar::gate\_closed\_exception instead.
+    pub fn close(&self) -> SeastarFuture<()> {
+        unsafe { SeastarFuture::from_raw(gate_close(self.repr)) }
+    }
+
+    /// Checks whether the gate has been closed.
+    ///
+    /// Matches the behavior of seastar::gate::is\_closed.
+    pub fn is_closed(&self) -> bool {
+        unsafe { gate_is_closed(self.repr) }
+    }
+
+    /// Returns the number of operations currently in flight.
+    ///
+    /// Matches the behavior of seastar::gate::get\_count.
+    pub fn count(&self) -> usize {
+        unsafe { gate_count(self.repr) }
+    }
+}
+
+impl Default for SeastarGate {
+    fn default() -> Self {
+        SeastarGate::new()
+    }
+}
+
+impl Drop for SeastarGate {
+    fn drop(&mut self) {
+        unsafe { gate_drop(self.repr) }
+    }
+}
+
+impl Debug for SeastarGate {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("SeastarGate")
+            .field("count", &self.count())
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+/// Guard representing one operation in flight through a [`SeastarGate`].
+///
+/// Dropping the guard leaves the gate, matching seastar::gate::leave.
+pub struct GateHolder<'a> {
+    gate: &'a SeastarGate,
+}
+
+impl<'a> Drop for GateHolder<'a> {
+    fn drop(&mut self) {
+        unsafe { gate_leave(self.gate.repr) }
+    }
+}
+
+impl<'a> Debug for GateHolder<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("GateHolder")
+    }
+}
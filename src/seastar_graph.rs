@@ -0,0 +1,142 @@
+#![cfg(feature = "serde")]
+
+use crate::seastar_shared_ptr::{SeastarSharedPtr, SeastarSharedPtrTarget};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A node of an object graph linked through [`SeastarSharedPtr`], as
+/// traversed by [`serialize_graph`] and rebuilt by [`deserialize_graph`].
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub trait SeastarGraphNode: SeastarSharedPtrTarget + Sized {
+    /// The node's own data, excluding its links to other nodes.
+    type Value;
+
+    /// Returns a copy of the node's own data.
+    fn value(&self) -> Self::Value;
+
+    /// Returns the node's outgoing links, in order. Null links are preserved.
+    fn children(&self) -> Vec<SeastarSharedPtr<Self>>;
+
+    /// Allocates a new node holding `value` and no links.
+    fn new_node(value: Self::Value) -> SeastarSharedPtr<Self>;
+
+    /// Appends `child` to the outgoing links of `node`.
+    fn push_child(node: &SeastarSharedPtr<Self>, child: SeastarSharedPtr<Self>);
+}
+
+#[derive(Serialize, Deserialize)]
+struct Graph<V> {
+    root: Option<usize>,
+    nodes: Vec<Node<V>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Node<V> {
+    value: V,
+    children: Vec<Option<usize>>,
+}
+
+/// Serializes the object graph reachable from `root`.
+///
+/// Every distinct object is written once and assigned an id; links to it,
+/// including links that close a cycle, are written as that id. Deserializing
+/// with [`deserialize_graph`] therefore restores the same sharing structure.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub fn serialize_graph<T, S>(root: &SeastarSharedPtr<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: SeastarGraphNode,
+    T::Value: Serialize,
+    S: Serializer,
+{
+    let mut ids = BTreeMap::new();
+    let mut order = Vec::new();
+    let root = intern(root, &mut ids, &mut order);
+
+    let mut nodes = Vec::new();
+    while nodes.len() < order.len() {
+        let node = order[nodes.len()].as_ref().unwrap();
+        let value = node.value();
+        let children = node
+            .children()
+            .iter()
+            .map(|child| intern(child, &mut ids, &mut order))
+            .collect();
+        nodes.push(Node { value, children });
+    }
+
+    Graph { root, nodes }.serialize(serializer)
+}
+
+/// Rebuilds an object graph written by [`serialize_graph`], returning its
+/// root.
+///
+/// All nodes are allocated before any links are made, so shared nodes and
+/// cycles are reconstructed as in the original graph. Note that objects on a
+/// cycle keep each other alive until the cycle is broken by hand.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub fn deserialize_graph<'de, T, D>(deserializer: D) -> Result<SeastarSharedPtr<T>, D::Error>
+where
+    T: SeastarGraphNode,
+    T::Value: DeserializeOwned,
+    D: Deserializer<'de>,
+{
+    let graph = Graph::<T::Value>::deserialize(deserializer)?;
+
+    let len = graph.nodes.len();
+    let links = graph.root.iter().chain(
+        graph
+            .nodes
+            .iter()
+            .flat_map(|node| node.children.iter().flatten()),
+    );
+    for &id in links {
+        if id >= len {
+            return Err(D::Error::custom(format_args!(
+                "graph node id {} out of range for {} nodes",
+                id, len,
+            )));
+        }
+    }
+
+    let mut links = Vec::with_capacity(len);
+    let mut ptrs = Vec::with_capacity(len);
+    for node in graph.nodes {
+        links.push(node.children);
+        ptrs.push(T::new_node(node.value));
+    }
+    for (ptr, children) in ptrs.iter().zip(links) {
+        for child in children {
+            T::push_child(ptr, resolve(&ptrs, child));
+        }
+    }
+
+    Ok(resolve(&ptrs, graph.root))
+}
+
+fn intern<T>(
+    ptr: &SeastarSharedPtr<T>,
+    ids: &mut BTreeMap<*const T, usize>,
+    order: &mut Vec<SeastarSharedPtr<T>>,
+) -> Option<usize>
+where
+    T: SeastarSharedPtrTarget,
+{
+    let address = ptr.as_ref()? as *const T;
+    let id = *ids.entry(address).or_insert_with(|| {
+        order.push(ptr.clone());
+        order.len() - 1
+    });
+    Some(id)
+}
+
+fn resolve<T>(ptrs: &[SeastarSharedPtr<T>], id: Option<usize>) -> SeastarSharedPtr<T>
+where
+    T: SeastarSharedPtrTarget,
+{
+    match id {
+        Some(id) => ptrs[id].clone(),
+        None => SeastarSharedPtr::null(),
+    }
+}
@@ -0,0 +1,229 @@
+#![cfg(feature = "std")]
+
+use crate::seastar_lw_shared_ptr::{SeastarLwSharedPtr, SeastarLwSharedPtrTarget};
+use alloc::vec::Vec;
+use core::hash::Hash;
+use core::mem;
+use std::collections::HashMap;
+
+const NIL: usize = usize::MAX;
+
+/// Least-recently-used cache holding its values as [`SeastarLwSharedPtr`].
+///
+/// Entries are linked into an intrusive doubly linked list threaded through
+/// the cache's own slot storage, ordered from most to least recently used.
+/// Inserting a new key into a full cache evicts the least recently used entry,
+/// dropping the cache's `SeastarLwSharedPtr` and thereby releasing its
+/// reference to the object. Other owners of the same object are unaffected.
+///
+/// Like `seastar::lw_shared_ptr` itself, the cache is meant to be used from a
+/// single shard.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub struct LruCache<K, V>
+where
+    V: SeastarLwSharedPtrTarget,
+{
+    map: HashMap<K, usize>,
+    slots: Vec<Option<Entry<K, V>>>,
+    free: Vec<usize>,
+    head: usize,
+    tail: usize,
+    capacity: usize,
+}
+
+struct Entry<K, V>
+where
+    V: SeastarLwSharedPtrTarget,
+{
+    key: K,
+    value: SeastarLwSharedPtr<V>,
+    prev: usize,
+    next: usize,
+}
+
+impl<K, V> LruCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: SeastarLwSharedPtrTarget,
+{
+    /// Makes a new empty cache holding at most `capacity` entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "LruCache capacity must be nonzero");
+        LruCache {
+            map: HashMap::with_capacity(capacity),
+            slots: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            head: NIL,
+            tail: NIL,
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of entries held by the cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of entries currently held by the cache.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the cache holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Inserts `value` under `key`, making it the most recently used entry.
+    ///
+    /// If the key was already present, its previous value is replaced and
+    /// returned. Otherwise, if the cache was full, the least recently used
+    /// entry is evicted and dropped.
+    pub fn insert(
+        &mut self,
+        key: K,
+        value: SeastarLwSharedPtr<V>,
+    ) -> Option<SeastarLwSharedPtr<V>> {
+        if let Some(&index) = self.map.get(&key) {
+            self.touch(index);
+            let entry = self.entry_mut(index);
+            return Some(mem::replace(&mut entry.value, value));
+        }
+
+        if self.map.len() == self.capacity {
+            let lru = self.tail;
+            let evicted = self.remove_slot(lru);
+            self.map.remove(&evicted.key);
+        }
+
+        let entry = Entry {
+            key: key.clone(),
+            value,
+            prev: NIL,
+            next: NIL,
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = Some(entry);
+                index
+            }
+            None => {
+                self.slots.push(Some(entry));
+                self.slots.len() - 1
+            }
+        };
+        self.push_front(index);
+        self.map.insert(key, index);
+        None
+    }
+
+    /// Returns the value stored under `key`, if any, and marks the entry as
+    /// the most recently used.
+    pub fn get(&mut self, key: &K) -> Option<&SeastarLwSharedPtr<V>> {
+        let index = *self.map.get(key)?;
+        self.touch(index);
+        Some(&self.entry(index).value)
+    }
+
+    /// Returns the value stored under `key`, if any, without affecting the
+    /// recency order.
+    pub fn peek(&self, key: &K) -> Option<&SeastarLwSharedPtr<V>> {
+        let index = *self.map.get(key)?;
+        Some(&self.entry(index).value)
+    }
+
+    /// Removes the entry stored under `key` and returns its value, if any.
+    pub fn remove(&mut self, key: &K) -> Option<SeastarLwSharedPtr<V>> {
+        let index = self.map.remove(key)?;
+        Some(self.remove_slot(index).value)
+    }
+
+    /// Returns the keys held by the cache, from most to least recently used.
+    pub fn keys(&self) -> LruCacheKeys<'_, K, V> {
+        LruCacheKeys {
+            cache: self,
+            next: self.head,
+        }
+    }
+
+    fn entry(&self, index: usize) -> &Entry<K, V> {
+        self.slots[index].as_ref().unwrap()
+    }
+
+    fn entry_mut(&mut self, index: usize) -> &mut Entry<K, V> {
+        self.slots[index].as_mut().unwrap()
+    }
+
+    fn touch(&mut self, index: usize) {
+        if self.head != index {
+            self.unlink(index);
+            self.push_front(index);
+        }
+    }
+
+    fn push_front(&mut self, index: usize) {
+        let head = self.head;
+        let entry = self.entry_mut(index);
+        entry.prev = NIL;
+        entry.next = head;
+        if head == NIL {
+            self.tail = index;
+        } else {
+            self.entry_mut(head).prev = index;
+        }
+        self.head = index;
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let entry = self.entry(index);
+        let (prev, next) = (entry.prev, entry.next);
+        if prev == NIL {
+            self.head = next;
+        } else {
+            self.entry_mut(prev).next = next;
+        }
+        if next == NIL {
+            self.tail = prev;
+        } else {
+            self.entry_mut(next).prev = prev;
+        }
+    }
+
+    fn remove_slot(&mut self, index: usize) -> Entry<K, V> {
+        self.unlink(index);
+        self.free.push(index);
+        self.slots[index].take().unwrap()
+    }
+}
+
+/// Iterator over the keys of an [`LruCache`], from most to least recently
+/// used.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub struct LruCacheKeys<'a, K, V>
+where
+    V: SeastarLwSharedPtrTarget,
+{
+    cache: &'a LruCache<K, V>,
+    next: usize,
+}
+
+impl<'a, K, V> Iterator for LruCacheKeys<'a, K, V>
+where
+    K: Hash + Eq + Clone,
+    V: SeastarLwSharedPtrTarget,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == NIL {
+            return None;
+        }
+        let entry = self.cache.entry(self.next);
+        self.next = entry.next;
+        Some(&entry.key)
+    }
+}
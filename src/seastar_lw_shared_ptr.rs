@@ -1,14 +1,52 @@
-use crate::fmt::display;
+use crate::fmt::{debug, display};
 use crate::kind::Trivial;
+use crate::null_ptr_error::NullPtrError;
+#[cfg(all(debug_assertions, feature = "seastar-shard-checks"))]
+use crate::seastar_shard_affinity;
+#[cfg(debug_assertions)]
+use crate::seastar_shared_ptr::POISON;
+use crate::seastar_shared_ptr::{SeastarSharedPtr, SeastarSharedPtrTarget};
+use crate::shared_mut_error::SharedMutError;
 use crate::string::CxxString;
 use crate::ExternType;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
 use core::ffi::c_void;
 use core::fmt::{self, Debug, Display};
 use core::marker::PhantomData;
-use core::mem::MaybeUninit;
+use core::mem::{self, ManuallyDrop, MaybeUninit};
 use core::ops::Deref;
+use core::option;
+use core::pin::Pin;
+use core::ptr;
+use core::str::Utf8Error;
 
 /// Binding to C++ `seastar::lw_shared_ptr<T>`.
+///
+/// # Newtypes
+///
+/// A `#[repr(transparent)]` newtype around a SeastarLwSharedPtr can be passed
+/// by value through a bridge in place of the pointer. Give it a C++ name that
+/// aliases the pointer type, for example `using Conn =
+/// seastar::lw_shared_ptr<Connection>;` in a header, then declare it as a
+/// [`Relocatable`][crate::kind::Relocatable] extern type, since dropping it
+/// releases a reference:
+///
+/// ```ignore
+/// #[repr(transparent)]
+/// pub struct Conn(pub SeastarLwSharedPtr<ffi::Connection>);
+///
+/// unsafe impl ExternType for Conn {
+///     type Id = type_id!("Conn");
+///     type Kind = cxx::kind::Relocatable;
+/// }
+/// ```
+///
+/// and refer to it as `type Conn = crate::Conn;` inside the bridge. The same
+/// works for newtypes around [`SeastarSharedPtr`].
 #[repr(C)]
 pub struct SeastarLwSharedPtr<T>
 where
@@ -22,10 +60,31 @@ impl<T> SeastarLwSharedPtr<T>
 where
     T: SeastarLwSharedPtrTarget,
 {
+    /// A null SeastarLwSharedPtr, usable in `const` items.
+    ///
+    /// A default-constructed seastar::lw\_shared\_ptr is all zero bits, so
+    /// unlike [`null`][SeastarLwSharedPtr::null] this needs no call into C++.
+    /// The handle is not `Sync`, so it cannot be put in a `static`; use a
+    /// `const` item instead, which makes a fresh null handle wherever it is
+    /// used.
+    ///
+    /// ```
+    /// use cxx::{CxxString, SeastarLwSharedPtr};
+    ///
+    /// const EMPTY: SeastarLwSharedPtr<CxxString> = SeastarLwSharedPtr::NULL;
+    ///
+    /// assert!(EMPTY.is_null());
+    /// ```
+    pub const NULL: Self = SeastarLwSharedPtr {
+        repr: MaybeUninit::new(ptr::null_mut()),
+        ty: PhantomData,
+    };
+
     /// Makes a new SeastarLwSharedPtr wrapping a null pointer.
     ///
     /// Matches the behavior of default-constructing a seastar::lw_shared\_ptr.
     pub fn null() -> Self {
+        trace_ptr!(Null(SeastarLwSharedPtr));
         let mut lw_shared_ptr = MaybeUninit::<SeastarLwSharedPtr<T>>::uninit();
         let new = lw_shared_ptr.as_mut_ptr().cast();
         unsafe {
@@ -35,18 +94,127 @@ where
     }
 
     /// Allocates memory on the heap and makes a SeastarLwSharedPtr owner for it.
+    ///
+    /// The object is destroyed by seastar::lw\_shared\_ptr running its C++ destructor, and
+    /// Rust drop glue never runs for it. `new` therefore accepts only targets
+    /// that have no Rust `Drop` to lose:
+    ///
+    /// - the primitive types;
+    /// - shared structs, which the bridge forbids from implementing `Drop`;
+    /// - C++ types whose [`ExternType`] impl declares them [`Trivial`], whose
+    ///   Rust definition mirrors the C++ type and its destructor.
+    ///
+    /// Opaque C++ types are rejected by the `Trivial` bound. Rust types
+    /// cannot be targets at all, so a Rust value with a `Drop` impl cannot be
+    /// moved into the pointer and leaked.
     pub fn new(value: T) -> Self
     where
         T: ExternType<Kind = Trivial>,
     {
+        trace_ptr!(New(SeastarLwSharedPtr));
         let mut lw_shared_ptr = MaybeUninit::<SeastarLwSharedPtr<T>>::uninit();
         let new = lw_shared_ptr.as_mut_ptr().cast();
-        unsafe {
+        let lw_shared_ptr = unsafe {
             T::__new(value, new);
             lw_shared_ptr.assume_init()
+        };
+        lw_shared_ptr.record_owner_shard();
+        lw_shared_ptr
+    }
+
+    /// Allocates uninitialized memory on the heap, makes a SeastarLwSharedPtr
+    /// owner for it, and has `construct` build the object in place.
+    ///
+    /// This is the way to put an opaque C++ type, which can never exist by
+    /// value in Rust, behind a SeastarLwSharedPtr: `construct` typically
+    /// passes the storage to a bridged C++ factory doing placement new, the
+    /// same as seastar::make\_lw\_shared.
+    ///
+    /// Types deriving from seastar::enable\_lw\_shared\_from\_this keep
+    /// their reference count inside the object, where the storage handed to
+    /// `construct` has none, so for them this aborts the process instead.
+    /// Pointers to such types made in C++ can still be passed, cloned and
+    /// dropped.
+    ///
+    /// # Safety
+    ///
+    /// `construct` must construct a valid `T` at the address it is given
+    /// before returning. If it panics, the allocation is leaked: the
+    /// half-built SeastarLwSharedPtr is never dropped, so no C++ destructor
+    /// runs on the uninitialized object.
+    pub unsafe fn emplace_with<F>(construct: F) -> Self
+    where
+        F: FnOnce(*mut c_void),
+    {
+        trace_ptr!(New(SeastarLwSharedPtr));
+        let mut lw_shared_ptr = MaybeUninit::<SeastarLwSharedPtr<T>>::uninit();
+        let new = lw_shared_ptr.as_mut_ptr().cast();
+        // Until assume_init, a panic in construct unwinds past storage that
+        // has no drop glue.
+        let lw_shared_ptr = unsafe {
+            construct(T::__uninit(new));
+            lw_shared_ptr.assume_init()
+        };
+        lw_shared_ptr.record_owner_shard();
+        lw_shared_ptr
+    }
+
+    /// Moves a boxed object into a newly allocated SeastarLwSharedPtr owner,
+    /// copying it straight from the Rust heap into the C++ allocation.
+    ///
+    /// Passing a large `T` to [`new`][SeastarLwSharedPtr::new] by value can
+    /// cost copies through the stack on the way in. Here the object is copied
+    /// exactly once, with a memcpy from the box, after which the box's memory
+    /// is freed without dropping the object. The trade-off is the extra
+    /// allocation for the box, which only pays off for large objects, or ones
+    /// that are already boxed.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn new_boxed(value: Box<T>) -> Self
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        let value = Box::into_raw(value);
+        unsafe {
+            let lw_shared_ptr =
+                Self::emplace_with(|new| ptr::copy_nonoverlapping(value, new.cast::<T>(), 1));
+            // The object now belongs to the SeastarLwSharedPtr.
+            drop(Box::from_raw(value.cast::<MaybeUninit<T>>()));
+            lw_shared_ptr
         }
     }
 
+    /// Releases the owned object, if any, leaving this SeastarLwSharedPtr null.
+    ///
+    /// Matches the behavior of assigning `nullptr` to a seastar::lw_shared\_ptr.
+    pub fn reset(&mut self) {
+        trace_ptr!(Drop(SeastarLwSharedPtr));
+        trace_ptr!(Null(SeastarLwSharedPtr));
+        self.release_owner_shard();
+        let this = self as *mut Self as *mut c_void;
+        unsafe {
+            T::__drop(this);
+            T::__null(this);
+        }
+    }
+
+    /// Releases the owned object, if any, and makes this SeastarLwSharedPtr the owner
+    /// of a newly allocated `value` instead.
+    pub fn reset_to(&mut self, value: T)
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        trace_ptr!(Drop(SeastarLwSharedPtr));
+        trace_ptr!(New(SeastarLwSharedPtr));
+        self.release_owner_shard();
+        let this = self as *mut Self as *mut c_void;
+        unsafe {
+            T::__drop(this);
+            T::__new(value, this);
+        }
+        self.record_owner_shard();
+    }
+
     /// Checks whether the SeastarLwSharedPtr does not own an object.
     ///
     /// This is the opposite of [seastar::lw_shared_ptr\<T\>::operator bool].
@@ -56,12 +224,487 @@ where
         ptr.is_null()
     }
 
+    /// Converts this SeastarLwSharedPtr into an `Option`, mapping a null pointer to
+    /// `None`.
+    pub fn into_option(self) -> Option<Self> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+
+    /// Collapses an `Option` back into a SeastarLwSharedPtr, mapping `None` to a
+    /// null pointer.
+    ///
+    /// This is the inverse of [`SeastarLwSharedPtr::into_option`].
+    pub fn from_option(option: Option<Self>) -> Self {
+        option.unwrap_or_else(Self::null)
+    }
+
     /// Returns a reference to the object owned by this SeastarLwSharedPtr if any,
     /// otherwise None.
     pub fn as_ref(&self) -> Option<&T> {
+        self.debug_assert_owner_shard();
         let this = self as *const Self as *const c_void;
         unsafe { T::__get(this).as_ref() }
     }
+
+    /// Returns a pinned reference to the object owned by this SeastarLwSharedPtr if
+    /// any, otherwise None.
+    ///
+    /// This is how to call methods taking `self: Pin<&Self>`. The object lives
+    /// in its own C++ allocation and is never moved by the SeastarLwSharedPtr, so
+    /// pinning it is sound.
+    pub fn as_pin_ref(&self) -> Option<Pin<&T>> {
+        self.as_ref()
+            .map(|object| unsafe { Pin::new_unchecked(object) })
+    }
+
+    /// Returns a mutable pinned reference to the object owned by this
+    /// SeastarLwSharedPtr if it is the object's only owner, otherwise None.
+    ///
+    /// Matches the behavior of `Rc::get_mut`: a null pointer, or an object
+    /// shared with other owners that could observe the mutation, gives None.
+    pub fn get_mut(&mut self) -> Option<Pin<&mut T>> {
+        self.try_pin_mut().ok()
+    }
+
+    /// Returns a mutable pinned reference to the object owned by this
+    /// SeastarLwSharedPtr, which must be its only owner.
+    ///
+    /// This is the counterpart of [`UniquePtr::pin_mut`], for calling methods
+    /// taking `self: Pin<&mut Self>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the SeastarLwSharedPtr is null, like dereferencing it does,
+    /// or if the object is shared with other owners, which could otherwise
+    /// observe the mutation. Use [`get_mut`][SeastarLwSharedPtr::get_mut] or
+    /// [`get_or_insert_default`][SeastarLwSharedPtr::get_or_insert_default]
+    /// to handle those cases without panicking.
+    ///
+    /// [`UniquePtr::pin_mut`]: crate::UniquePtr::pin_mut
+    pub fn pin_mut(&mut self) -> Pin<&mut T> {
+        let typename = display(T::__typename);
+        match self.try_pin_mut() {
+            Ok(target) => target,
+            Err(SharedMutError::Null) => {
+                panic!("called pin_mut on a null SeastarLwSharedPtr<{}>", typename)
+            }
+            Err(SharedMutError::Shared) => {
+                panic!(
+                    "called pin_mut on a shared SeastarLwSharedPtr<{}>",
+                    typename
+                )
+            }
+        }
+    }
+
+    fn try_pin_mut(&mut self) -> Result<Pin<&mut T>, SharedMutError> {
+        if self.is_null() {
+            return Err(SharedMutError::Null);
+        }
+        if !self.is_unique() {
+            return Err(SharedMutError::Shared);
+        }
+        self.debug_assert_owner_shard();
+        Ok(unsafe { Pin::new_unchecked(&mut *self.as_mut_ptr()) })
+    }
+
+    /// Applies `f` to the object owned by this SeastarLwSharedPtr, or returns None if it
+    /// is null.
+    ///
+    /// Shorthand for `ptr.as_ref().map(f)`.
+    ///
+    /// ```
+    /// use cxx::SeastarLwSharedPtr;
+    ///
+    /// let ptr = SeastarLwSharedPtr::from_str("seastar");
+    /// assert_eq!(ptr.map(|s| s.len()), Some(7));
+    ///
+    /// let null = SeastarLwSharedPtr::<cxx::CxxString>::null();
+    /// assert_eq!(null.map(|s| s.len()), None);
+    /// ```
+    pub fn map<'a, U, F>(&'a self, f: F) -> Option<U>
+    where
+        F: FnOnce(&'a T) -> U,
+    {
+        self.as_ref().map(f)
+    }
+
+    /// Applies `f` to the object owned by this SeastarLwSharedPtr, or returns None if it
+    /// is null.
+    ///
+    /// Shorthand for `ptr.as_ref().and_then(f)`, for when `f` may itself fail.
+    ///
+    /// ```
+    /// use cxx::SeastarLwSharedPtr;
+    ///
+    /// let ptr = SeastarLwSharedPtr::from_str("2020");
+    /// let year = ptr.and_then(|s| s.to_str().ok()?.parse::<u32>().ok());
+    /// assert_eq!(year, Some(2020));
+    ///
+    /// let null = SeastarLwSharedPtr::<cxx::CxxString>::null();
+    /// assert_eq!(null.and_then(|s| s.to_str().ok()), None);
+    /// ```
+    pub fn and_then<'a, U, F>(&'a self, f: F) -> Option<U>
+    where
+        F: FnOnce(&'a T) -> Option<U>,
+    {
+        self.as_ref().and_then(f)
+    }
+
+    /// Checks whether this SeastarLwSharedPtr owns an object equal to `value`, so false
+    /// for a null pointer.
+    ///
+    /// Shorthand for `ptr.as_ref() == Some(value)`.
+    pub fn points_to(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.as_ref() == Some(value)
+    }
+
+    /// Returns a raw pointer to the object owned by this SeastarLwSharedPtr, or null if
+    /// it does not own an object.
+    ///
+    /// Matches the behavior of seastar::lw_shared\_ptr\<T\>::get. The pointer is valid for as
+    /// long as some owner keeps the object alive.
+    pub fn as_ptr(&self) -> *const T {
+        let this = self as *const Self as *const c_void;
+        unsafe { T::__get(this) }
+    }
+
+    /// Returns a mutable raw pointer to the object owned by this SeastarLwSharedPtr, or
+    /// null if it does not own an object, for handing to C APIs that modify
+    /// the object in place.
+    ///
+    /// Every owner shares the object, so the caller must make sure this
+    /// SeastarLwSharedPtr is its only owner, as checked by
+    /// [`is_unique`][SeastarLwSharedPtr::is_unique], for as long as it writes through
+    /// the pointer. Writing while other owners can observe the object is
+    /// undefined behavior.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.as_ptr() as *mut T
+    }
+
+    /// Returns a reference to the object owned by this SeastarLwSharedPtr, or a
+    /// [`NullPtrError`] if the SeastarLwSharedPtr is null.
+    ///
+    /// This is the non-panicking counterpart of dereferencing the SeastarLwSharedPtr.
+    pub fn try_deref(&self) -> Result<&T, NullPtrError> {
+        self.as_ref()
+            .ok_or_else(|| NullPtrError::new("SeastarLwSharedPtr", T::__typename))
+    }
+
+    /// Returns an object that displays the owned object, or `placeholder` if
+    /// this SeastarLwSharedPtr is null.
+    ///
+    /// The [`Display`] impl of SeastarLwSharedPtr itself always prints `nullptr` for a
+    /// null pointer.
+    pub fn display_or<'a>(&'a self, placeholder: &'a str) -> impl Display + 'a
+    where
+        T: Display,
+    {
+        display(move |formatter| match self.as_ref() {
+            None => formatter.write_str(placeholder),
+            Some(value) => Display::fmt(value, formatter),
+        })
+    }
+
+    /// Returns an object that debug formats the owned object, or a
+    /// conspicuous marker naming the pointer type if this SeastarLwSharedPtr is null.
+    ///
+    /// The [`Debug`] impl of SeastarLwSharedPtr itself prints a quiet `nullptr` for a
+    /// null pointer, which is easy to overlook in a wall of output. This is
+    /// meant for assertion messages and debug logging where an unexpected
+    /// null should stand out.
+    pub fn debug_strict(&self) -> impl Debug + '_
+    where
+        T: Debug,
+    {
+        debug(move |formatter| match self.as_ref() {
+            None => write!(
+                formatter,
+                "<NULL SeastarLwSharedPtr<{}>>",
+                display(T::__typename),
+            ),
+            Some(value) => Debug::fmt(value, formatter),
+        })
+    }
+
+    /// Overwrites the object owned by this SeastarLwSharedPtr with `value` in place,
+    /// without allocating, provided this SeastarLwSharedPtr is its only owner.
+    ///
+    /// Fails without modifying anything if the SeastarLwSharedPtr is null or if the
+    /// object is shared with other owners.
+    pub fn set(&mut self, value: T) -> Result<(), SharedMutError>
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        let this = self as *const Self as *const c_void;
+        let ptr = unsafe { T::__get(this) } as *mut T;
+        if ptr.is_null() {
+            return Err(SharedMutError::Null);
+        }
+        if self.use_count() != 1 {
+            return Err(SharedMutError::Shared);
+        }
+        unsafe { *ptr = value }
+        Ok(())
+    }
+
+    /// Returns a mutable reference to the owned object, first making sure
+    /// that this SeastarLwSharedPtr is its unique owner.
+    ///
+    /// A null SeastarLwSharedPtr is given a newly allocated `T::default()`. If the object
+    /// is shared with other owners, it is cloned into a new allocation owned
+    /// only by this SeastarLwSharedPtr, leaving the other owners untouched.
+    pub fn get_or_insert_default(&mut self) -> Pin<&mut T>
+    where
+        T: ExternType<Kind = Trivial> + Default + Clone,
+    {
+        match Self::as_ref(self) {
+            None => self.reset_to(T::default()),
+            Some(value) if self.use_count() != 1 => {
+                let value = value.clone();
+                self.reset_to(value);
+            }
+            Some(_) => {}
+        }
+        let this = self as *const Self as *const c_void;
+        let ptr = unsafe { T::__get(this) } as *mut T;
+        unsafe { Pin::new_unchecked(&mut *ptr) }
+    }
+
+    /// Returns the number of SeastarLwSharedPtr instances referring to the same object,
+    /// including this one, or 0 if this SeastarLwSharedPtr is null.
+    ///
+    /// Matches the behavior of seastar::lw_shared_ptr\<T\>::use\_count. seastar keeps
+    /// the count in a `long`, which is never wider than `usize` on the
+    /// platforms seastar supports, so the full count is returned; the C++
+    /// shims check this at compile time.
+    pub fn use_count(&self) -> usize {
+        let this = self as *const Self as *const c_void;
+        unsafe { T::__use_count(this) }
+    }
+
+    /// Checks whether this SeastarLwSharedPtr is the only owner of its object, so
+    /// that nobody else can observe changes made through it.
+    ///
+    /// Shorthand for `ptr.use_count() == 1`, so false for a null pointer.
+    pub fn is_unique(&self) -> bool {
+        self.use_count() == 1
+    }
+
+    /// Makes another SeastarLwSharedPtr owning the same object, without copying the object.
+    ///
+    /// This is the same as [`clone`][Clone::clone], under a name that does not
+    /// suggest a deep copy: only the reference count goes up.
+    ///
+    /// ```
+    /// use cxx::SeastarLwSharedPtr;
+    ///
+    /// let a = SeastarLwSharedPtr::new(2020i32);
+    /// let b = a.share();
+    /// assert!(a.ptr_eq(&b));
+    /// assert_eq!(a.use_count(), 2);
+    /// ```
+    pub fn share(&self) -> Self {
+        self.clone()
+    }
+
+    /// Makes another SeastarLwSharedPtr owning the same object, or returns None if this
+    /// SeastarLwSharedPtr is null.
+    ///
+    /// Unlike [`clone`][Clone::clone], which turns a null pointer into another
+    /// null pointer, this makes a null pointer visible at the point it would
+    /// have been copied.
+    pub fn clone_non_null(&self) -> Option<Self> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self.clone())
+        }
+    }
+
+    /// Returns true if the two SeastarLwSharedPtrs point to the same object, or if both
+    /// are null.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        let this = self as *const Self as *const c_void;
+        let other = other as *const Self as *const c_void;
+        unsafe { T::__get(this) == T::__get(other) }
+    }
+
+    /// Exchanges the objects owned by this SeastarLwSharedPtr and `other`.
+    ///
+    /// Neither reference count is touched. The handle is trivially
+    /// relocatable, as relied on by passing it by value across the bridge,
+    /// so swapping its words in place is all a C++ swap would do.
+    pub fn swap(&mut self, other: &mut Self) {
+        mem::swap(self, other);
+    }
+
+    /// Makes a new SeastarSharedPtr owning a copy of the object owned by this
+    /// SeastarLwSharedPtr, or a null SeastarSharedPtr if this one is null.
+    ///
+    /// seastar::lw\_shared\_ptr and seastar::shared\_ptr use incompatible
+    /// control blocks, and seastar::shared\_ptr has no aliasing constructor,
+    /// so the two handles cannot share one object. Instead the object is
+    /// copy-constructed into a fresh seastar::shared\_ptr allocation. Both
+    /// handles observe the same value afterwards, but later changes through
+    /// one are not visible through the other.
+    pub fn to_shared(&self) -> SeastarSharedPtr<T>
+    where
+        T: SeastarSharedPtrTarget + ExternType<Kind = Trivial>,
+    {
+        trace_ptr!(New(SeastarSharedPtr));
+        let mut shared_ptr = MaybeUninit::<SeastarSharedPtr<T>>::uninit();
+        let new = shared_ptr.as_mut_ptr().cast();
+        let this = self as *const Self as *const c_void;
+        unsafe {
+            T::__to_shared(this, new);
+            shared_ptr.assume_init()
+        }
+    }
+
+    /// Panics if the object owned by this SeastarLwSharedPtr was allocated
+    /// from Rust on a different shard than the one currently running.
+    ///
+    /// A seastar::lw\_shared\_ptr keeps a non-atomic reference count, so
+    /// touching one from a foreign shard is undefined behavior. In builds with
+    /// `debug_assertions` and the opt-in "seastar-shard-checks" feature, the
+    /// shard is recorded when Rust allocates the object through
+    /// [`new`][SeastarLwSharedPtr::new],
+    /// [`emplace_with`][SeastarLwSharedPtr::emplace_with] and friends, and
+    /// this check runs on every clone, drop and dereference. Objects allocated
+    /// by C++ are not checked. The shards are kept in a table keyed by object
+    /// address behind a process-wide lock, and an address C++ frees and
+    /// reuses on another shard may be reported falsely. Otherwise this does
+    /// nothing.
+    pub fn debug_assert_owner_shard(&self) {
+        #[cfg(all(debug_assertions, feature = "seastar-shard-checks"))]
+        seastar_shard_affinity::check(self.as_ptr().cast(), &display(T::__typename));
+    }
+
+    /// Returns the shard on which Rust allocated the object owned by this
+    /// SeastarLwSharedPtr.
+    ///
+    /// This reads the record kept for
+    /// [`debug_assert_owner_shard`][SeastarLwSharedPtr::debug_assert_owner_shard],
+    /// so it is only available in builds with `debug_assertions` and the
+    /// "seastar-shard-checks" feature. It returns None without them, for a
+    /// null pointer, and for objects allocated by C++, whose shard is never
+    /// recorded.
+    pub fn owning_shard(&self) -> Option<u32> {
+        #[cfg(all(debug_assertions, feature = "seastar-shard-checks"))]
+        return seastar_shard_affinity::owner(self.as_ptr().cast());
+        #[cfg(not(all(debug_assertions, feature = "seastar-shard-checks")))]
+        return None;
+    }
+
+    fn record_owner_shard(&self) {
+        #[cfg(all(debug_assertions, feature = "seastar-shard-checks"))]
+        seastar_shard_affinity::record(self.as_ptr().cast());
+    }
+
+    fn release_owner_shard(&self) {
+        self.debug_assert_owner_shard();
+        #[cfg(all(debug_assertions, feature = "seastar-shard-checks"))]
+        if self.use_count() == 1 {
+            seastar_shard_affinity::release(self.as_ptr().cast());
+        }
+    }
+
+    /// Consumes the SeastarLwSharedPtr, returning its raw representation
+    /// without touching the reference count.
+    ///
+    /// The returned pointer is the single pointer word that makes up a
+    /// seastar::lw\_shared\_ptr; it is null for a null SeastarLwSharedPtr.
+    /// The reference it carries is leaked unless it is passed exactly once to
+    /// [`SeastarLwSharedPtr::from_raw`].
+    pub fn into_raw(self) -> *mut c_void {
+        let this = ManuallyDrop::new(self);
+        unsafe { this.repr.assume_init() }
+    }
+
+    /// Reconstructs a SeastarLwSharedPtr from the raw representation returned
+    /// by [`SeastarLwSharedPtr::into_raw`], taking over its reference.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by `into_raw` on a SeastarLwSharedPtr of
+    /// the same `T`, and must not be passed to `from_raw` more than once.
+    /// Reclaiming the same pointer twice results in a double free.
+    pub unsafe fn from_raw(ptr: *mut c_void) -> Self {
+        SeastarLwSharedPtr {
+            repr: MaybeUninit::new(ptr),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl SeastarLwSharedPtr<CxxString> {
+    /// Makes a new SeastarLwSharedPtr owning a `std::string` with the contents of `s`.
+    ///
+    /// The string is constructed directly inside the seastar::lw_shared\_ptr allocation,
+    /// without first building a CxxString on the Rust side.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        Self::from_bytes(s.as_bytes())
+    }
+
+    /// Makes a new SeastarLwSharedPtr owning a `std::string` holding a copy of `bytes`.
+    ///
+    /// The string is built from a pointer and a length, so embedded NUL bytes
+    /// are kept rather than ending it, and the bytes need not be UTF-8.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        trace_ptr!(New(SeastarLwSharedPtr));
+        extern "C" {
+            #[link_name = "cxxbridge1$seastar$lw_shared_ptr$string$from_str"]
+            fn from_str(new: *mut c_void, ptr: *const u8, len: usize);
+        }
+        let mut lw_shared_ptr = MaybeUninit::<SeastarLwSharedPtr<CxxString>>::uninit();
+        let new = lw_shared_ptr.as_mut_ptr().cast();
+        let lw_shared_ptr = unsafe {
+            from_str(new, bytes.as_ptr(), bytes.len());
+            lw_shared_ptr.assume_init()
+        };
+        lw_shared_ptr.record_owner_shard();
+        lw_shared_ptr
+    }
+
+    /// Returns the pointed-to string as a `&str`, or `None` if the pointer is
+    /// null.
+    ///
+    /// Fails if the contents of the string are not valid UTF-8.
+    pub fn to_str(&self) -> Result<Option<&str>, Utf8Error> {
+        match self.as_ref() {
+            None => Ok(None),
+            Some(string) => string.to_str().map(Some),
+        }
+    }
+
+    /// Appends `s` to the pointed-to string in place, provided this
+    /// SeastarLwSharedPtr is its only owner.
+    ///
+    /// Fails without modifying anything if the SeastarLwSharedPtr is null or
+    /// if the string is shared with other owners.
+    pub fn push_str(&mut self, s: &str) -> Result<(), SharedMutError> {
+        self.try_pin_mut()?.push_str(s);
+        Ok(())
+    }
+
+    /// Removes the contents of the pointed-to string in place, provided this
+    /// SeastarLwSharedPtr is its only owner.
+    ///
+    /// Fails without modifying anything if the SeastarLwSharedPtr is null or
+    /// if the string is shared with other owners.
+    pub fn clear(&mut self) -> Result<(), SharedMutError> {
+        self.try_pin_mut()?.clear();
+        Ok(())
+    }
 }
 
 impl<T> Clone for SeastarLwSharedPtr<T>
@@ -69,6 +712,8 @@ where
     T: SeastarLwSharedPtrTarget,
 {
     fn clone(&self) -> Self {
+        trace_ptr!(Clone(SeastarLwSharedPtr));
+        self.debug_assert_owner_shard();
         let mut lw_shared_ptr = MaybeUninit::<SeastarLwSharedPtr<T>>::uninit();
         let new = lw_shared_ptr.as_mut_ptr().cast();
         let this = self as *const Self as *mut c_void;
@@ -77,6 +722,12 @@ where
             lw_shared_ptr.assume_init()
         }
     }
+
+    fn clone_from(&mut self, source: &Self) {
+        if !self.ptr_eq(source) {
+            *self = source.clone();
+        }
+    }
 }
 
 // SeastarLwSharedPtr is not a self-referential type and is safe to move out of a Pin,
@@ -88,8 +739,23 @@ where
     T: SeastarLwSharedPtrTarget,
 {
     fn drop(&mut self) {
+        trace_ptr!(Drop(SeastarLwSharedPtr));
+        // Catches the same storage being dropped a second time, as by
+        // drop_in_place on a handle already dropped, before the poisoned
+        // words reach C++. A bitwise copy made with ptr::read has storage of
+        // its own that was never poisoned, so dropping both goes unnoticed.
+        #[cfg(debug_assertions)]
+        assert!(
+            unsafe { self.repr.assume_init() } != POISON,
+            "SeastarLwSharedPtr dropped twice",
+        );
+        self.release_owner_shard();
         let this = self as *mut Self as *mut c_void;
         unsafe { T::__drop(this) }
+        #[cfg(debug_assertions)]
+        unsafe {
+            ptr::write_volatile(&mut self.repr, MaybeUninit::new(POISON));
+        }
     }
 }
 
@@ -110,11 +776,97 @@ where
     }
 }
 
+/// Panics if the SeastarLwSharedPtr is null, like dereferencing it does. Use the inherent
+/// [`SeastarLwSharedPtr::as_ref`] to check for null instead.
+impl<T> AsRef<T> for SeastarLwSharedPtr<T>
+where
+    T: SeastarLwSharedPtrTarget,
+{
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+/// Panics if the SeastarLwSharedPtr is null, like dereferencing it does.
+///
+/// For non-null pointers this agrees with the by-value comparisons, so a map
+/// keyed by SeastarLwSharedPtrs can be looked up by an owned object, as in
+/// `map.get(&value)`. Such a lookup borrows the keys it compares against, and
+/// panics on reaching a null key, so a map looked up by value must not have
+/// null keys. Lookups by another SeastarLwSharedPtr are unaffected.
+impl<T> Borrow<T> for SeastarLwSharedPtr<T>
+where
+    T: SeastarLwSharedPtrTarget,
+{
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+/// Yields the owned object if there is one, or nothing if the SeastarLwSharedPtr is
+/// null.
+impl<'a, T> IntoIterator for &'a SeastarLwSharedPtr<T>
+where
+    T: SeastarLwSharedPtrTarget,
+{
+    type Item = &'a T;
+    type IntoIter = option::IntoIter<&'a T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_ref().into_iter()
+    }
+}
+
+/// Compares the owned objects by value, not by address; use
+/// [`ptr_eq`][SeastarLwSharedPtr::ptr_eq] to compare identity. A null SeastarLwSharedPtr is equal
+/// only to another null one.
+impl<T> PartialEq for SeastarLwSharedPtr<T>
+where
+    T: PartialEq + SeastarLwSharedPtrTarget,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl<T> Eq for SeastarLwSharedPtr<T> where T: Eq + SeastarLwSharedPtrTarget {}
+
+/// Orders by the owned objects, with null sorting before every non-null
+/// SeastarLwSharedPtr, the same as comparing [`as_ref`][SeastarLwSharedPtr::as_ref] results.
+/// See the [`Borrow`] impl about null keys in ordered maps.
+impl<T> PartialOrd for SeastarLwSharedPtr<T>
+where
+    T: PartialOrd + SeastarLwSharedPtrTarget,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_ref().partial_cmp(&other.as_ref())
+    }
+}
+
+impl<T> Ord for SeastarLwSharedPtr<T>
+where
+    T: Ord + SeastarLwSharedPtrTarget,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ref().cmp(&other.as_ref())
+    }
+}
+
 impl<T> Debug for SeastarLwSharedPtr<T>
 where
     T: Debug + SeastarLwSharedPtrTarget,
 {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if formatter.alternate() {
+            // {:#?} additionally shows the ownership state, which is what
+            // matters when chasing reference counting bugs.
+            return formatter
+                .debug_struct("SeastarLwSharedPtr")
+                .field("ptr", &self.as_ptr())
+                .field("use_count", &self.use_count())
+                .field("value", &self.as_ref())
+                .finish();
+        }
         match self.as_ref() {
             None => formatter.write_str("nullptr"),
             Some(value) => Debug::fmt(value, formatter),
@@ -134,6 +886,19 @@ where
     }
 }
 
+/// Fails, giving the pointer back, unless it is the only owner of its object.
+/// See [`SeastarSharedPtr::try_into_lw`].
+impl<T> TryFrom<SeastarSharedPtr<T>> for SeastarLwSharedPtr<T>
+where
+    T: SeastarSharedPtrTarget + SeastarLwSharedPtrTarget + ExternType<Kind = Trivial>,
+{
+    type Error = SeastarSharedPtr<T>;
+
+    fn try_from(ptr: SeastarSharedPtr<T>) -> Result<Self, Self::Error> {
+        ptr.try_into_lw()
+    }
+}
+
 /// Trait bound for types which may be used as the `T` inside of a
 /// `SeastarLwSharedPtr<T>` in generic code.
 ///
@@ -176,15 +941,45 @@ pub unsafe trait SeastarLwSharedPtrTarget {
         unreachable!()
     }
     #[doc(hidden)]
+    unsafe fn __uninit(new: *mut c_void) -> *mut c_void;
+    #[doc(hidden)]
     unsafe fn __clone(this: *const c_void, new: *mut c_void);
     #[doc(hidden)]
+    unsafe fn __to_shared(this: *const c_void, new: *mut c_void) {
+        // Like `__new`, only provided for types that may be trivial, as the
+        // shim relies on the C++ copy constructor.
+        let _ = this;
+        let _ = new;
+        unreachable!()
+    }
+    #[doc(hidden)]
     unsafe fn __get(this: *const c_void) -> *const Self;
     #[doc(hidden)]
+    unsafe fn __use_count(this: *const c_void) -> usize;
+    #[doc(hidden)]
     unsafe fn __drop(this: *mut c_void);
 }
 
+macro_rules! lw_shared_ptr_target_by_value_methods {
+    (opaque, $segment:expr, $ty:ty) => {};
+    (trivial, $segment:expr, $ty:ty) => {
+        unsafe fn __new(value: Self, new: *mut c_void) {
+            unsafe { Self::__uninit(new).cast::<$ty>().write(value) }
+        }
+        unsafe fn __to_shared(this: *const c_void, new: *mut c_void) {
+            extern "C" {
+                attr! {
+                    #[link_name = concat!("cxxbridge1$seastar$lw_to_shared$", $segment)]
+                    fn __to_shared(this: *const c_void, new: *mut c_void);
+                }
+            }
+            unsafe { __to_shared(this, new) }
+        }
+    };
+}
+
 macro_rules! impl_lw_shared_ptr_target {
-    ($segment:expr, $name:expr, $ty:ty) => {
+    ($kind:ident, $segment:expr, $name:expr, $ty:ty) => {
         unsafe impl SeastarLwSharedPtrTarget for $ty {
             fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
                 f.write_str($name)
@@ -198,15 +993,16 @@ macro_rules! impl_lw_shared_ptr_target {
                 }
                 unsafe { __null(new) }
             }
-            unsafe fn __new(value: Self, new: *mut c_void) {
+            unsafe fn __uninit(new: *mut c_void) -> *mut c_void {
                 extern "C" {
                     attr! {
                         #[link_name = concat!("cxxbridge1$seastar$lw_shared_ptr$", $segment, "$uninit")]
                         fn __uninit(new: *mut c_void) -> *mut c_void;
                     }
                 }
-                unsafe { __uninit(new).cast::<$ty>().write(value) }
+                unsafe { __uninit(new) }
             }
+            lw_shared_ptr_target_by_value_methods!($kind, $segment, $ty);
             unsafe fn __clone(this: *const c_void, new: *mut c_void) {
                 extern "C" {
                     attr! {
@@ -225,6 +1021,15 @@ macro_rules! impl_lw_shared_ptr_target {
                 }
                 unsafe { __get(this) }.cast()
             }
+            unsafe fn __use_count(this: *const c_void) -> usize {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$lw_shared_ptr$", $segment, "$use_count")]
+                        fn __use_count(this: *const c_void) -> usize;
+                    }
+                }
+                unsafe { __use_count(this) }
+            }
             unsafe fn __drop(this: *mut c_void) {
                 extern "C" {
                     attr! {
@@ -238,24 +1043,30 @@ macro_rules! impl_lw_shared_ptr_target {
     };
 }
 
+// Primitive targets are named by their C++ spelling in panic messages and
+// Debug output, so that they read the same as the C++ side's diagnostics.
 macro_rules! impl_lw_shared_ptr_target_for_primitive {
-    ($ty:ident) => {
-        impl_lw_shared_ptr_target!(stringify!($ty), stringify!($ty), $ty);
+    ($ty:ident, $name:expr) => {
+        impl_lw_shared_ptr_target!(trivial, stringify!($ty), $name, $ty);
     };
 }
 
-impl_lw_shared_ptr_target_for_primitive!(bool);
-impl_lw_shared_ptr_target_for_primitive!(u8);
-impl_lw_shared_ptr_target_for_primitive!(u16);
-impl_lw_shared_ptr_target_for_primitive!(u32);
-impl_lw_shared_ptr_target_for_primitive!(u64);
-impl_lw_shared_ptr_target_for_primitive!(usize);
-impl_lw_shared_ptr_target_for_primitive!(i8);
-impl_lw_shared_ptr_target_for_primitive!(i16);
-impl_lw_shared_ptr_target_for_primitive!(i32);
-impl_lw_shared_ptr_target_for_primitive!(i64);
-impl_lw_shared_ptr_target_for_primitive!(isize);
-impl_lw_shared_ptr_target_for_primitive!(f32);
-impl_lw_shared_ptr_target_for_primitive!(f64);
-
-impl_lw_shared_ptr_target!("string", "CxxString", CxxString);
+// There are deliberately no separate impls for C++ character types: `c_char`
+// is a type alias of `i8` or `u8`, and cxx has no distinct Rust type for
+// `char16_t`/`char32_t`, which it passes as `u16`/`u32`. Pointers to those
+// types are handled through the same-sized integer impls below.
+impl_lw_shared_ptr_target_for_primitive!(bool, "bool");
+impl_lw_shared_ptr_target_for_primitive!(u8, "uint8_t");
+impl_lw_shared_ptr_target_for_primitive!(u16, "uint16_t");
+impl_lw_shared_ptr_target_for_primitive!(u32, "uint32_t");
+impl_lw_shared_ptr_target_for_primitive!(u64, "uint64_t");
+impl_lw_shared_ptr_target_for_primitive!(usize, "size_t");
+impl_lw_shared_ptr_target_for_primitive!(i8, "int8_t");
+impl_lw_shared_ptr_target_for_primitive!(i16, "int16_t");
+impl_lw_shared_ptr_target_for_primitive!(i32, "int32_t");
+impl_lw_shared_ptr_target_for_primitive!(i64, "int64_t");
+impl_lw_shared_ptr_target_for_primitive!(isize, "rust::isize");
+impl_lw_shared_ptr_target_for_primitive!(f32, "float");
+impl_lw_shared_ptr_target_for_primitive!(f64, "double");
+
+impl_lw_shared_ptr_target!(opaque, "string", "std::string", CxxString);
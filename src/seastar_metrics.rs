@@ -0,0 +1,194 @@
+#![cfg(feature = "alloc")]
+
+use crate::result::PtrLen;
+use crate::seastar_exception::SeastarException;
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::mem::MaybeUninit;
+
+type AddMetric<R> = unsafe extern "C" fn(
+    group: *const u8,
+    group_len: usize,
+    name: *const u8,
+    name_len: usize,
+    data: *mut c_void,
+    call: unsafe extern "C" fn(*mut c_void) -> R,
+    exception: *mut PtrLen,
+) -> *mut c_void;
+
+extern "C" {
+    #[link_name = "cxxbridge1$seastar$metrics$add_gauge"]
+    fn metrics_add_gauge(
+        group: *const u8,
+        group_len: usize,
+        name: *const u8,
+        name_len: usize,
+        data: *mut c_void,
+        call: unsafe extern "C" fn(*mut c_void) -> f64,
+        exception: *mut PtrLen,
+    ) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$metrics$add_counter"]
+    fn metrics_add_counter(
+        group: *const u8,
+        group_len: usize,
+        name: *const u8,
+        name_len: usize,
+        data: *mut c_void,
+        call: unsafe extern "C" fn(*mut c_void) -> u64,
+        exception: *mut PtrLen,
+    ) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$metrics$unregister"]
+    fn metrics_unregister(this: *mut c_void);
+}
+
+/// Registers metrics computed by Rust closures with seastar's metrics layer,
+/// by way of C++ `seastar::metrics::metric_groups`.
+///
+/// All metrics registered through one SeastarMetrics share its group name,
+/// which seastar uses as the prefix of the exported metric names. Each
+/// registration returns a [`MetricRegistration`] that keeps the metric
+/// exported, and the closure behind it alive, until it is dropped.
+///
+/// Like seastar's metrics themselves, registrations belong to the shard
+/// that made them, and their closures are called on that shard whenever
+/// the metrics are collected.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct SeastarMetrics {
+    group: String,
+}
+
+impl SeastarMetrics {
+    /// Makes a new SeastarMetrics registering metrics under `group`.
+    pub fn new(group: &str) -> Self {
+        SeastarMetrics {
+            group: String::from(group),
+        }
+    }
+
+    /// Returns the group name the metrics are registered under.
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
+    /// Registers a gauge named `name`, whose value is whatever `f` returns
+    /// when the metrics are collected.
+    ///
+    /// Matches the behavior of seastar::metrics::make\_gauge. Fails if seastar
+    /// refuses the registration, for example because a metric of the same
+    /// name is already registered in the group.
+    ///
+    /// A panic in `f` cannot unwind through seastar and aborts the process
+    /// instead.
+    pub fn register_gauge<F>(
+        &self,
+        name: &str,
+        f: F,
+    ) -> Result<MetricRegistration, SeastarException>
+    where
+        F: Fn() -> f64 + 'static,
+    {
+        self.register(name, f, metrics_add_gauge)
+    }
+
+    /// Registers a counter named `name`, whose value is whatever `f` returns
+    /// when the metrics are collected.
+    ///
+    /// Matches the behavior of seastar::metrics::make\_counter. The value is
+    /// expected never to go down. Fails under the same conditions as
+    /// [`register_gauge`][SeastarMetrics::register_gauge], and a panic in `f`
+    /// likewise aborts the process.
+    pub fn register_counter<F>(
+        &self,
+        name: &str,
+        f: F,
+    ) -> Result<MetricRegistration, SeastarException>
+    where
+        F: Fn() -> u64 + 'static,
+    {
+        self.register(name, f, metrics_add_counter)
+    }
+
+    fn register<F, R>(
+        &self,
+        name: &str,
+        f: F,
+        add: AddMetric<R>,
+    ) -> Result<MetricRegistration, SeastarException>
+    where
+        F: Fn() -> R + 'static,
+    {
+        let data = Box::into_raw(Box::new(f)).cast::<c_void>();
+        let mut exception = MaybeUninit::<PtrLen>::uninit();
+        let repr = unsafe {
+            add(
+                self.group.as_ptr(),
+                self.group.len(),
+                name.as_ptr(),
+                name.len(),
+                data,
+                call_metric::<F, R>,
+                exception.as_mut_ptr(),
+            )
+        };
+        if repr.is_null() {
+            unsafe { drop_metric::<F>(data) }
+            let exception = unsafe { exception.assume_init() };
+            Err(unsafe { SeastarException::from_ptr_len(exception) })
+        } else {
+            Ok(MetricRegistration {
+                repr,
+                data,
+                drop_metric: drop_metric::<F>,
+            })
+        }
+    }
+}
+
+impl Debug for SeastarMetrics {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("SeastarMetrics")
+            .field("group", &self.group)
+            .finish()
+    }
+}
+
+/// Guard keeping a metric registered through [`SeastarMetrics`] exported.
+///
+/// Dropping the guard unregisters the metric and then frees its closure.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct MetricRegistration {
+    repr: *mut c_void,
+    data: *mut c_void,
+    drop_metric: unsafe fn(*mut c_void),
+}
+
+impl Drop for MetricRegistration {
+    fn drop(&mut self) {
+        // Unregister before freeing the closure the metric calls.
+        unsafe {
+            metrics_unregister(self.repr);
+            (self.drop_metric)(self.data);
+        }
+    }
+}
+
+impl Debug for MetricRegistration {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("MetricRegistration")
+    }
+}
+
+unsafe extern "C" fn call_metric<F, R>(data: *mut c_void) -> R
+where
+    F: Fn() -> R,
+{
+    let f = unsafe { &*data.cast::<F>() };
+    crate::unwind::prevent_unwind("SeastarMetrics closure", f)
+}
+
+unsafe fn drop_metric<F>(data: *mut c_void) {
+    drop(unsafe { Box::from_raw(data.cast::<F>()) });
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "ptr-tracing")]
+
+use core::mem;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// The kind of seastar pointer a [`PtrEvent`] concerns.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "ptr-tracing")))]
+pub enum PtrKind {
+    /// [`SeastarSharedPtr`](crate::SeastarSharedPtr)
+    SeastarSharedPtr,
+    /// [`SeastarLwSharedPtr`](crate::SeastarLwSharedPtr)
+    SeastarLwSharedPtr,
+}
+
+/// A seastar pointer handle being created or destroyed on the Rust side.
+///
+/// Every handle Rust creates is reported once as `Null`, `New` or `Clone` and
+/// once as `Drop`, so the counts balance for a program that does not leak.
+/// Handles received from C++ are only reported when dropped, and handles
+/// passed to C++ are never reported as dropped. Round trips through
+/// `into_raw` and `from_raw` are not reported.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "ptr-tracing")))]
+pub enum PtrEvent {
+    /// A null handle was made.
+    Null(PtrKind),
+    /// A handle owning a newly constructed object was made.
+    New(PtrKind),
+    /// A handle was cloned, sharing ownership of an existing object.
+    Clone(PtrKind),
+    /// A handle was dropped or reset.
+    Drop(PtrKind),
+}
+
+static TRACER: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Registers a callback to receive every [`PtrEvent`], replacing any previous
+/// one, or unregisters it when passed `None`.
+///
+/// The callback runs on whichever thread the event happens on, in the middle
+/// of the operation being reported, and must not panic.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "ptr-tracing")))]
+pub fn set_ptr_tracer(tracer: Option<fn(PtrEvent)>) {
+    let tracer = match tracer {
+        Some(tracer) => tracer as *mut (),
+        None => ptr::null_mut(),
+    };
+    TRACER.store(tracer, Ordering::Release);
+}
+
+pub(crate) fn trace(event: PtrEvent) {
+    let tracer = TRACER.load(Ordering::Acquire);
+    if !tracer.is_null() {
+        let tracer = unsafe { mem::transmute::<*mut (), fn(PtrEvent)>(tracer) };
+        tracer(event);
+    }
+}
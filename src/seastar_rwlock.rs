@@ -0,0 +1,280 @@
+#[cfg(feature = "alloc")]
+use crate::seastar_exception::SeastarException;
+#[cfg(feature = "alloc")]
+use crate::seastar_future::SeastarFuture;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+#[cfg(feature = "alloc")]
+use core::future::Future;
+#[cfg(feature = "alloc")]
+use core::pin::Pin;
+#[cfg(feature = "alloc")]
+use core::task::{Context, Poll};
+
+extern "C" {
+    #[link_name = "cxxbridge1$seastar$rwlock$new"]
+    fn rwlock_new() -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$rwlock$read_lock"]
+    fn rwlock_read_lock(this: *mut c_void) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$rwlock$write_lock"]
+    fn rwlock_write_lock(this: *mut c_void) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$rwlock$try_read_lock"]
+    fn rwlock_try_read_lock(this: *mut c_void) -> bool;
+    #[link_name = "cxxbridge1$seastar$rwlock$try_write_lock"]
+    fn rwlock_try_write_lock(this: *mut c_void) -> bool;
+    #[link_name = "cxxbridge1$seastar$rwlock$read_unlock"]
+    fn rwlock_read_unlock(this: *mut c_void);
+    #[link_name = "cxxbridge1$seastar$rwlock$write_unlock"]
+    fn rwlock_write_unlock(this: *mut c_void);
+    #[link_name = "cxxbridge1$seastar$rwlock$abandon_read"]
+    fn rwlock_abandon_read(this: *mut c_void, acquire: *mut c_void);
+    #[link_name = "cxxbridge1$seastar$rwlock$abandon_write"]
+    fn rwlock_abandon_write(this: *mut c_void, acquire: *mut c_void);
+    #[link_name = "cxxbridge1$seastar$rwlock$drop"]
+    fn rwlock_drop(this: *mut c_void);
+}
+
+/// Binding to C++ `seastar::rwlock`.
+///
+/// Any number of readers, or a single writer, may hold the lock at once.
+/// Acquiring returns a guard that releases the lock when dropped. Waiters are
+/// granted the lock in the order they asked for it, so a waiting writer holds
+/// back readers that arrive after it.
+///
+/// The lock lives in its own C++ heap allocation and SeastarRwLock owns a
+/// pointer to it. Like seastar::rwlock itself it must be used from a single
+/// shard.
+pub struct SeastarRwLock {
+    repr: *mut c_void,
+}
+
+impl SeastarRwLock {
+    /// Makes a new unlocked lock.
+    pub fn new() -> Self {
+        SeastarRwLock {
+            repr: unsafe { rwlock_new() },
+        }
+    }
+
+    /// Starts acquiring the lock for reading, returning a future that
+    /// resolves to a read guard once no writer holds or is ahead in line for
+    /// the lock.
+    ///
+    /// Dropping the future before it resolves gives up the place in line: if
+    /// seastar grants it the lock later, the lock is released right away.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn read(&self) -> RwLockReadFuture<'_> {
+        RwLockReadFuture {
+            lock: self,
+            acquire: Some(unsafe { SeastarFuture::from_raw(rwlock_read_lock(self.repr)) }),
+        }
+    }
+
+    /// Starts acquiring the lock for writing, returning a future that
+    /// resolves to a write guard once nobody else holds the lock.
+    ///
+    /// Dropping the future before it resolves gives up the place in line: if
+    /// seastar grants it the lock later, the lock is released right away.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn write(&self) -> RwLockWriteFuture<'_> {
+        RwLockWriteFuture {
+            lock: self,
+            acquire: Some(unsafe { SeastarFuture::from_raw(rwlock_write_lock(self.repr)) }),
+        }
+    }
+
+    /// Acquires the lock for reading if that is possible without waiting.
+    ///
+    /// Matches the behavior of seastar::rwlock::try\_read\_lock.
+    pub fn try_read(&self) -> Option<RwLockReadGuard<'_>> {
+        if unsafe { rwlock_try_read_lock(self.repr) } {
+            Some(RwLockReadGuard { lock: self })
+        } else {
+            None
+        }
+    }
+
+    /// Acquires the lock for writing if that is possible without waiting.
+    ///
+    /// Matches the behavior of seastar::rwlock::try\_write\_lock.
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_>> {
+        if unsafe { rwlock_try_write_lock(self.repr) } {
+            Some(RwLockWriteGuard { lock: self })
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for SeastarRwLock {
+    fn default() -> Self {
+        SeastarRwLock::new()
+    }
+}
+
+impl Drop for SeastarRwLock {
+    fn drop(&mut self) {
+        // Waiters given up while still in line keep the C++ lock alive until
+        // they have been granted it and have released it again.
+        unsafe { rwlock_drop(self.repr) }
+    }
+}
+
+impl Debug for SeastarRwLock {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("SeastarRwLock")
+    }
+}
+
+/// Future returned by [`SeastarRwLock::read`].
+///
+/// Resolves to `Ok` with the guard, or to `Err` if seastar failed to queue
+/// the waiter.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct RwLockReadFuture<'a> {
+    lock: &'a SeastarRwLock,
+    // None once the guard has been handed out.
+    acquire: Option<SeastarFuture<()>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> RwLockReadFuture<'a> {
+    /// Checks whether the lock has been granted, or acquiring it failed, so
+    /// that awaiting would complete without waiting.
+    pub fn is_ready(&self) -> bool {
+        self.acquire.as_ref().map_or(true, SeastarFuture::is_ready)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Future for RwLockReadFuture<'a> {
+    type Output = Result<RwLockReadGuard<'a>, SeastarException>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let acquire = this
+            .acquire
+            .as_mut()
+            .expect("RwLockReadFuture polled after completion");
+        let result = match Pin::new(acquire).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        this.acquire = None;
+        Poll::Ready(result.map(|()| RwLockReadGuard { lock: this.lock }))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Drop for RwLockReadFuture<'a> {
+    fn drop(&mut self) {
+        if let Some(acquire) = self.acquire.take() {
+            unsafe { rwlock_abandon_read(self.lock.repr, acquire.into_raw()) }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Debug for RwLockReadFuture<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("RwLockReadFuture")
+    }
+}
+
+/// Future returned by [`SeastarRwLock::write`].
+///
+/// Resolves to `Ok` with the guard, or to `Err` if seastar failed to queue
+/// the waiter.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct RwLockWriteFuture<'a> {
+    lock: &'a SeastarRwLock,
+    // None once the guard has been handed out.
+    acquire: Option<SeastarFuture<()>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> RwLockWriteFuture<'a> {
+    /// Checks whether the lock has been granted, or acquiring it failed, so
+    /// that awaiting would complete without waiting.
+    pub fn is_ready(&self) -> bool {
+        self.acquire.as_ref().map_or(true, SeastarFuture::is_ready)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Future for RwLockWriteFuture<'a> {
+    type Output = Result<RwLockWriteGuard<'a>, SeastarException>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let acquire = this
+            .acquire
+            .as_mut()
+            .expect("RwLockWriteFuture polled after completion");
+        let result = match Pin::new(acquire).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        this.acquire = None;
+        Poll::Ready(result.map(|()| RwLockWriteGuard { lock: this.lock }))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Drop for RwLockWriteFuture<'a> {
+    fn drop(&mut self) {
+        if let Some(acquire) = self.acquire.take() {
+            unsafe { rwlock_abandon_write(self.lock.repr, acquire.into_raw()) }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Debug for RwLockWriteFuture<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("RwLockWriteFuture")
+    }
+}
+
+/// Guard holding a [`SeastarRwLock`] for reading.
+///
+/// Dropping the guard releases the lock, matching seastar::rwlock::read\_unlock.
+pub struct RwLockReadGuard<'a> {
+    lock: &'a SeastarRwLock,
+}
+
+impl<'a> Drop for RwLockReadGuard<'a> {
+    fn drop(&mut self) {
+        unsafe { rwlock_read_unlock(self.lock.repr) }
+    }
+}
+
+impl<'a> Debug for RwLockReadGuard<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("RwLockReadGuard")
+    }
+}
+
+/// Guard holding a [`SeastarRwLock`] for writing.
+///
+/// Dropping the guard releases the lock, matching
+/// seastar::rwlock::write\_unlock.
+pub struct RwLockWriteGuard<'a> {
+    lock: &'a SeastarRwLock,
+}
+
+impl<'a> Drop for RwLockWriteGuard<'a> {
+    fn drop(&mut self) {
+        unsafe { rwlock_write_unlock(self.lock.repr) }
+    }
+}
+
+impl<'a> Debug for RwLockWriteGuard<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("RwLockWriteGuard")
+    }
+}
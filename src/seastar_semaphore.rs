@@ -0,0 +1,76 @@
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+
+extern "C" {
+    #[link_name = "cxxbridge1$seastar$semaphore$new"]
+    fn semaphore_new(count: usize) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$semaphore$try_wait"]
+    fn semaphore_try_wait(this: *mut c_void, units: usize) -> bool;
+    #[link_name = "cxxbridge1$seastar$semaphore$signal"]
+    fn semaphore_signal(this: *mut c_void, units: usize);
+    #[link_name = "cxxbridge1$seastar$semaphore$current"]
+    fn semaphore_current(this: *const c_void) -> usize;
+    #[link_name = "cxxbridge1$seastar$semaphore$drop"]
+    fn semaphore_drop(this: *mut c_void);
+}
+
+/// Binding to C++ `seastar::semaphore`.
+///
+/// A semaphore holds a number of units that operations take before running
+/// and give back when done, limiting how many run at once. So far only the
+/// non-blocking [`try_wait`][SeastarSemaphore::try_wait] and
+/// [`signal`][SeastarSemaphore::signal] are bound.
+///
+/// The semaphore lives in its own C++ heap allocation and SeastarSemaphore
+/// owns a pointer to it. Like seastar::semaphore itself it must be used from a
+/// single shard.
+pub struct SeastarSemaphore {
+    repr: *mut c_void,
+}
+
+impl SeastarSemaphore {
+    /// Makes a new semaphore holding `count` units.
+    pub fn new(count: usize) -> Self {
+        SeastarSemaphore {
+            repr: unsafe { semaphore_new(count) },
+        }
+    }
+
+    /// Takes `units` units if that many are available, returning whether it
+    /// did.
+    ///
+    /// Matches the behavior of seastar::semaphore::try\_wait, which also fails
+    /// while other operations are queued waiting for units.
+    pub fn try_wait(&self, units: usize) -> bool {
+        unsafe { semaphore_try_wait(self.repr, units) }
+    }
+
+    /// Gives back `units` units, waking any waiters they satisfy.
+    ///
+    /// Matches the behavior of seastar::semaphore::signal.
+    pub fn signal(&self, units: usize) {
+        unsafe { semaphore_signal(self.repr, units) }
+    }
+
+    /// Returns the number of units available right now.
+    ///
+    /// Matches the behavior of seastar::semaphore::current.
+    pub fn current(&self) -> usize {
+        unsafe { semaphore_current(self.repr) }
+    }
+}
+
+impl Drop for SeastarSemaphore {
+    fn drop(&mut self) {
+        unsafe { semaphore_drop(self.repr) }
+    }
+}
+
+impl Debug for SeastarSemaphore {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("SeastarSemaphore")
+            .field("current", &self.current())
+            .finish()
+    }
+}
@@ -0,0 +1,97 @@
+#![cfg(all(debug_assertions, feature = "seastar-shard-checks"))]
+
+// Debug-only bookkeeping of which shard owns each object that Rust has put
+// behind a SeastarLwSharedPtr.
+//
+// The handle is a single pointer word shared with C++, and the count next to
+// the object belongs to seastar, so there is no room to remember a shard id.
+// Instead the owning shard is looked up by the address of the object, in a
+// table behind a process-wide lock, which is why this is opt-in. Objects
+// allocated by C++ are never recorded and are not checked. An entry outlives
+// its object when the last owner is dropped on the C++ side, so an object
+// later allocated at the same address and put behind a SeastarLwSharedPtr by
+// C++ on another shard can be reported falsely.
+
+use core::ffi::c_void;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use std::boxed::Box;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+extern "C" {
+    #[link_name = "cxxbridge1$seastar$this_shard_id"]
+    fn this_shard_id() -> u32;
+}
+
+type Owners = Mutex<HashMap<usize, u32>>;
+
+static OWNERS: AtomicPtr<Owners> = AtomicPtr::new(ptr::null_mut());
+
+fn owners() -> &'static Owners {
+    let mut owners = OWNERS.load(Ordering::Acquire);
+    if owners.is_null() {
+        let new = Box::into_raw(Box::new(Mutex::new(HashMap::new())));
+        owners = match OWNERS.compare_exchange(
+            ptr::null_mut(),
+            new,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => new,
+            Err(existing) => {
+                drop(unsafe { Box::from_raw(new) });
+                existing
+            }
+        };
+    }
+    unsafe { &*owners }
+}
+
+fn lock() -> std::sync::MutexGuard<'static, HashMap<usize, u32>> {
+    owners()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Records the current shard as the owner of a freshly allocated object.
+pub(crate) fn record(object: *const c_void) {
+    if !object.is_null() {
+        let shard = unsafe { this_shard_id() };
+        lock().insert(object as usize, shard);
+    }
+}
+
+// The shard recorded as the owner of the object, if any.
+pub(crate) fn owner(object: *const c_void) -> Option<u32> {
+    if object.is_null() {
+        return None;
+    }
+    lock().get(&(object as usize)).copied()
+}
+
+// Panics if the object was recorded as owned by a shard other than the
+// current one.
+pub(crate) fn check(object: *const c_void, typename: &dyn core::fmt::Display) {
+    if object.is_null() {
+        return;
+    }
+    let owner = lock().get(&(object as usize)).copied();
+    if let Some(owner) = owner {
+        let shard = unsafe { this_shard_id() };
+        assert!(
+            owner == shard,
+            "SeastarLwSharedPtr<{}> owned by shard {} used on shard {}",
+            typename,
+            owner,
+            shard,
+        );
+    }
+}
+
+// Forgets the owner of an object whose last owning handle is going away.
+pub(crate) fn release(object: *const c_void) {
+    if !object.is_null() {
+        lock().remove(&(object as usize));
+    }
+}
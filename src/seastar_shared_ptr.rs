@@ -1,14 +1,43 @@
-use crate::fmt::display;
+use crate::cxx_vector::CxxVector;
+use crate::fmt::{debug, display};
 use crate::kind::Trivial;
+use crate::null_ptr_error::NullPtrError;
+use crate::seastar_lw_shared_ptr::{SeastarLwSharedPtr, SeastarLwSharedPtrTarget};
+use crate::shared_mut_error::SharedMutError;
+use crate::shared_ptr::{SharedPtr, SharedPtrTarget};
 use crate::string::CxxString;
 use crate::ExternType;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
 use core::ffi::c_void;
 use core::fmt::{self, Debug, Display};
 use core::marker::PhantomData;
-use core::mem::MaybeUninit;
+use core::mem::{self, ManuallyDrop, MaybeUninit};
 use core::ops::Deref;
+use core::option;
+use core::pin::Pin;
+use core::ptr;
+use core::str::Utf8Error;
+
+/// Bit pattern that debug builds leave behind in the storage of a dropped
+/// seastar pointer, so that reading through a dangling handle finds an address
+/// that faults on common platforms rather than a freed control block.
+#[cfg(debug_assertions)]
+pub(crate) const POISON: *mut c_void = (usize::MAX / 0xff * 0xa5) as *mut c_void;
 
 /// Binding to C++ `seastar::shared_ptr<T>`.
+///
+/// # Thread safety
+///
+/// SeastarSharedPtr is neither `Send` nor `Sync`, whatever `T` is. Unlike
+/// std::shared\_ptr, seastar::shared\_ptr keeps a plain non-atomic reference
+/// count, since under seastar's shard-per-core model an object belongs to the
+/// shard that allocated it. Cloning or dropping a handle on another thread
+/// would race on that count. To hand an object to another shard, go through
+/// seastar's cross-shard facilities such as seastar::foreign\_ptr on the C++
+/// side.
 #[repr(C)]
 pub struct SeastarSharedPtr<T>
 where
@@ -22,10 +51,30 @@ impl<T> SeastarSharedPtr<T>
 where
     T: SeastarSharedPtrTarget,
 {
+    /// A null SeastarSharedPtr, usable in `const` items.
+    ///
+    /// A default-constructed seastar::shared\_ptr is all zero bits, so unlike
+    /// [`null`][SeastarSharedPtr::null] this needs no call into C++. The handle is not
+    /// `Sync`, so it cannot be put in a `static`; use a `const` item instead,
+    /// which makes a fresh null handle wherever it is used.
+    ///
+    /// ```
+    /// use cxx::{CxxString, SeastarSharedPtr};
+    ///
+    /// const EMPTY: SeastarSharedPtr<CxxString> = SeastarSharedPtr::NULL;
+    ///
+    /// assert!(EMPTY.is_null());
+    /// ```
+    pub const NULL: Self = SeastarSharedPtr {
+        repr: [MaybeUninit::new(ptr::null_mut()); 2],
+        ty: PhantomData,
+    };
+
     /// Makes a new SeastarSharedPtr wrapping a null pointer.
     ///
     /// Matches the behavior of default-constructing a seastar::shared\_ptr.
     pub fn null() -> Self {
+        trace_ptr!(Null(SeastarSharedPtr));
         let mut shared_ptr = MaybeUninit::<SeastarSharedPtr<T>>::uninit();
         let new = shared_ptr.as_mut_ptr().cast();
         unsafe {
@@ -35,10 +84,24 @@ where
     }
 
     /// Allocates memory on the heap and makes a SeastarSharedPtr owner for it.
+    ///
+    /// The object is destroyed by seastar::shared\_ptr running its C++ destructor, and
+    /// Rust drop glue never runs for it. `new` therefore accepts only targets
+    /// that have no Rust `Drop` to lose:
+    ///
+    /// - the primitive types;
+    /// - shared structs, which the bridge forbids from implementing `Drop`;
+    /// - C++ types whose [`ExternType`] impl declares them [`Trivial`], whose
+    ///   Rust definition mirrors the C++ type and its destructor.
+    ///
+    /// Opaque C++ types are rejected by the `Trivial` bound. Rust types
+    /// cannot be targets at all, so a Rust value with a `Drop` impl cannot be
+    /// moved into the pointer and leaked.
     pub fn new(value: T) -> Self
     where
         T: ExternType<Kind = Trivial>,
     {
+        trace_ptr!(New(SeastarSharedPtr));
         let mut shared_ptr = MaybeUninit::<SeastarSharedPtr<T>>::uninit();
         let new = shared_ptr.as_mut_ptr().cast();
         unsafe {
@@ -47,6 +110,116 @@ where
         }
     }
 
+    /// Allocates memory on the heap and makes a SeastarSharedPtr owner for it
+    /// that disposes of the object by calling `deleter`.
+    ///
+    /// When the last owner is dropped, `deleter` is called exactly once with a
+    /// pointer to the object, in place of the C++ destructor that
+    /// [`new`][SeastarSharedPtr::new] would run. The deleter is responsible
+    /// for any cleanup the object needs. It must not free the pointer it is
+    /// given, since the memory still belongs to the pointer's allocation and
+    /// is released after the deleter returns.
+    ///
+    /// seastar::shared\_ptr takes no deleter of its own, so this stores the
+    /// object and `deleter` together in one holder, whose destructor calls
+    /// the deleter.
+    ///
+    /// The last owner may well be dropped by C++ code, which a panic must not
+    /// unwind into, so a panic in `deleter` is caught once the panic hook has
+    /// reported it, and the memory is released all the same. Without the
+    /// `std` feature there is no catching it, and the process aborts instead.
+    pub fn new_with_deleter(value: T, deleter: fn(*mut T)) -> Self
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        trace_ptr!(New(SeastarSharedPtr));
+        let mut shared_ptr = MaybeUninit::<SeastarSharedPtr<T>>::uninit();
+        let new = shared_ptr.as_mut_ptr().cast();
+        unsafe {
+            T::__new_with_deleter(value, call_deleter::<T>, deleter as *mut c_void, new);
+            shared_ptr.assume_init()
+        }
+    }
+
+    /// Makes a SeastarSharedPtr sharing ownership of the object owned by a
+    /// std::shared\_ptr.
+    ///
+    /// The two pointer kinds keep separate reference counts, so the returned
+    /// SeastarSharedPtr holds a copy of `src` that keeps the object alive
+    /// until the last seastar::shared\_ptr to it is dropped. The object is
+    /// destroyed once both the std and the seastar owners are gone, in
+    /// whichever order that happens. A null `src` gives a null result.
+    ///
+    /// # Safety
+    ///
+    /// The std owners keep handing out shared references to the object, but
+    /// [`use_count`][SeastarSharedPtr::use_count] only counts the seastar
+    /// owners, so the result can report itself the unique owner while `src`
+    /// still co-owns the object. The object must not be mutated through the
+    /// result, or any pointer derived from it, for as long as a std owner
+    /// exists: that rules out [`set`][SeastarSharedPtr::set],
+    /// [`get_or_insert_default`][SeastarSharedPtr::get_or_insert_default],
+    /// [`try_into_lw`][SeastarSharedPtr::try_into_lw] and writes through
+    /// [`as_mut_ptr`][SeastarSharedPtr::as_mut_ptr]. Use
+    /// [`from_std_shared_copy`][SeastarSharedPtr::from_std_shared_copy] for a
+    /// pointer that can be mutated freely.
+    pub unsafe fn from_std_shared(src: &SharedPtr<T>) -> Self
+    where
+        T: SharedPtrTarget,
+    {
+        trace_ptr!(New(SeastarSharedPtr));
+        let mut shared_ptr = MaybeUninit::<SeastarSharedPtr<T>>::uninit();
+        let new = shared_ptr.as_mut_ptr().cast();
+        let src = src as *const SharedPtr<T> as *const c_void;
+        unsafe {
+            T::__from_std(src, new);
+            shared_ptr.assume_init()
+        }
+    }
+
+    /// Makes a SeastarSharedPtr owning a newly allocated copy of the object
+    /// owned by a std::shared\_ptr.
+    ///
+    /// Unlike [`SeastarSharedPtr::from_std_shared`], the result is independent
+    /// of `src` afterwards. A null `src` gives a null result.
+    pub fn from_std_shared_copy(src: &SharedPtr<T>) -> Self
+    where
+        T: SharedPtrTarget + ExternType<Kind = Trivial> + Clone,
+    {
+        match src.as_ref() {
+            Some(value) => Self::new(value.clone()),
+            None => Self::null(),
+        }
+    }
+
+    /// Releases the owned object, if any, leaving this SeastarSharedPtr null.
+    ///
+    /// Matches the behavior of assigning `nullptr` to a seastar::shared\_ptr.
+    pub fn reset(&mut self) {
+        trace_ptr!(Drop(SeastarSharedPtr));
+        trace_ptr!(Null(SeastarSharedPtr));
+        let this = self as *mut Self as *mut c_void;
+        unsafe {
+            T::__drop(this);
+            T::__null(this);
+        }
+    }
+
+    /// Releases the owned object, if any, and makes this SeastarSharedPtr the owner
+    /// of a newly allocated `value` instead.
+    pub fn reset_to(&mut self, value: T)
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        trace_ptr!(Drop(SeastarSharedPtr));
+        trace_ptr!(New(SeastarSharedPtr));
+        let this = self as *mut Self as *mut c_void;
+        unsafe {
+            T::__drop(this);
+            T::__new(value, this);
+        }
+    }
+
     /// Checks whether the SeastarSharedPtr does not own an object.
     ///
     /// This is the opposite of [seastar::shared_ptr\<T\>::operator bool].
@@ -56,22 +229,456 @@ where
         ptr.is_null()
     }
 
+    /// Converts this SeastarSharedPtr into an `Option`, mapping a null pointer to
+    /// `None`.
+    pub fn into_option(self) -> Option<Self> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+
+    /// Collapses an `Option` back into a SeastarSharedPtr, mapping `None` to a
+    /// null pointer.
+    ///
+    /// This is the inverse of [`SeastarSharedPtr::into_option`].
+    pub fn from_option(option: Option<Self>) -> Self {
+        option.unwrap_or_else(Self::null)
+    }
+
     /// Returns a reference to the object owned by this SeastarSharedPtr if any,
     /// otherwise None.
     pub fn as_ref(&self) -> Option<&T> {
         let this = self as *const Self as *const c_void;
         unsafe { T::__get(this).as_ref() }
     }
+
+    /// Returns a pinned reference to the object owned by this SeastarSharedPtr if
+    /// any, otherwise None.
+    ///
+    /// This is how to call methods taking `self: Pin<&Self>`. The object lives
+    /// in its own C++ allocation and is never moved by the SeastarSharedPtr, so
+    /// pinning it is sound.
+    pub fn as_pin_ref(&self) -> Option<Pin<&T>> {
+        self.as_ref()
+            .map(|object| unsafe { Pin::new_unchecked(object) })
+    }
+
+    /// Applies `f` to the object owned by this SeastarSharedPtr, or returns None if it
+    /// is null.
+    ///
+    /// Shorthand for `ptr.as_ref().map(f)`.
+    ///
+    /// ```
+    /// use cxx::SeastarSharedPtr;
+    ///
+    /// let ptr = SeastarSharedPtr::from_str("seastar");
+    /// assert_eq!(ptr.map(|s| s.len()), Some(7));
+    ///
+    /// let null = SeastarSharedPtr::<cxx::CxxString>::null();
+    /// assert_eq!(null.map(|s| s.len()), None);
+    /// ```
+    pub fn map<'a, U, F>(&'a self, f: F) -> Option<U>
+    where
+        F: FnOnce(&'a T) -> U,
+    {
+        self.as_ref().map(f)
+    }
+
+    /// Applies `f` to the object owned by this SeastarSharedPtr, or returns None if it
+    /// is null.
+    ///
+    /// Shorthand for `ptr.as_ref().and_then(f)`, for when `f` may itself fail.
+    ///
+    /// ```
+    /// use cxx::SeastarSharedPtr;
+    ///
+    /// let ptr = SeastarSharedPtr::from_str("2020");
+    /// let year = ptr.and_then(|s| s.to_str().ok()?.parse::<u32>().ok());
+    /// assert_eq!(year, Some(2020));
+    ///
+    /// let null = SeastarSharedPtr::<cxx::CxxString>::null();
+    /// assert_eq!(null.and_then(|s| s.to_str().ok()), None);
+    /// ```
+    pub fn and_then<'a, U, F>(&'a self, f: F) -> Option<U>
+    where
+        F: FnOnce(&'a T) -> Option<U>,
+    {
+        self.as_ref().and_then(f)
+    }
+
+    /// Checks whether this SeastarSharedPtr owns an object equal to `value`, so false
+    /// for a null pointer.
+    ///
+    /// Shorthand for `ptr.as_ref() == Some(value)`.
+    pub fn points_to(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.as_ref() == Some(value)
+    }
+
+    /// Returns a raw pointer to the object owned by this SeastarSharedPtr, or null if
+    /// it does not own an object.
+    ///
+    /// Matches the behavior of seastar::shared\_ptr\<T\>::get. The pointer is valid for as
+    /// long as some owner keeps the object alive.
+    pub fn as_ptr(&self) -> *const T {
+        let this = self as *const Self as *const c_void;
+        unsafe { T::__get(this) }
+    }
+
+    /// Returns a mutable raw pointer to the object owned by this SeastarSharedPtr, or
+    /// null if it does not own an object, for handing to C APIs that modify
+    /// the object in place.
+    ///
+    /// Every owner shares the object, so the caller must make sure this
+    /// SeastarSharedPtr is its only owner, as checked by
+    /// [`is_unique`][SeastarSharedPtr::is_unique], for as long as it writes through
+    /// the pointer. Writing while other owners can observe the object is
+    /// undefined behavior.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.as_ptr() as *mut T
+    }
+
+    /// Returns a reference to the object owned by this SeastarSharedPtr, or a
+    /// [`NullPtrError`] if the SeastarSharedPtr is null.
+    ///
+    /// This is the non-panicking counterpart of dereferencing the SeastarSharedPtr.
+    pub fn try_deref(&self) -> Result<&T, NullPtrError> {
+        self.as_ref()
+            .ok_or_else(|| NullPtrError::new("SeastarSharedPtr", T::__typename))
+    }
+
+    /// Returns a guard that owns a new reference to the object owned by this
+    /// SeastarSharedPtr, or None if it is null.
+    ///
+    /// Unlike [`as_ref`][SeastarSharedPtr::as_ref], the reference obtained
+    /// through the guard is not tied to the borrow of `self`: it stays valid
+    /// for as long as the guard lives, even after this SeastarSharedPtr is
+    /// dropped, which makes it suitable for holding across an await point.
+    pub fn deref_owned(&self) -> Option<SeastarRef<T>> {
+        if self.is_null() {
+            None
+        } else {
+            Some(SeastarRef { ptr: self.clone() })
+        }
+    }
+
+    /// Returns an object that displays the owned object, or `placeholder` if
+    /// this SeastarSharedPtr is null.
+    ///
+    /// The [`Display`] impl of SeastarSharedPtr itself always prints `nullptr` for a
+    /// null pointer.
+    pub fn display_or<'a>(&'a self, placeholder: &'a str) -> impl Display + 'a
+    where
+        T: Display,
+    {
+        display(move |formatter| match self.as_ref() {
+            None => formatter.write_str(placeholder),
+            Some(value) => Display::fmt(value, formatter),
+        })
+    }
+
+    /// Returns an object that debug formats the owned object, or a
+    /// conspicuous marker naming the pointer type if this SeastarSharedPtr is null.
+    ///
+    /// The [`Debug`] impl of SeastarSharedPtr itself prints a quiet `nullptr` for a
+    /// null pointer, which is easy to overlook in a wall of output. This is
+    /// meant for assertion messages and debug logging where an unexpected
+    /// null should stand out.
+    pub fn debug_strict(&self) -> impl Debug + '_
+    where
+        T: Debug,
+    {
+        debug(move |formatter| match self.as_ref() {
+            None => write!(
+                formatter,
+                "<NULL SeastarSharedPtr<{}>>",
+                display(T::__typename),
+            ),
+            Some(value) => Debug::fmt(value, formatter),
+        })
+    }
+
+    /// Overwrites the object owned by this SeastarSharedPtr with `value` in place,
+    /// without allocating, provided this SeastarSharedPtr is its only owner.
+    ///
+    /// Fails without modifying anything if the SeastarSharedPtr is null or if the
+    /// object is shared with other owners.
+    pub fn set(&mut self, value: T) -> Result<(), SharedMutError>
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        let this = self as *const Self as *const c_void;
+        let ptr = unsafe { T::__get(this) } as *mut T;
+        if ptr.is_null() {
+            return Err(SharedMutError::Null);
+        }
+        if self.use_count() != 1 {
+            return Err(SharedMutError::Shared);
+        }
+        unsafe { *ptr = value }
+        Ok(())
+    }
+
+    /// Returns a mutable reference to the owned object, first making sure
+    /// that this SeastarSharedPtr is its unique owner.
+    ///
+    /// A null SeastarSharedPtr is given a newly allocated `T::default()`. If the object
+    /// is shared with other owners, it is cloned into a new allocation owned
+    /// only by this SeastarSharedPtr, leaving the other owners untouched.
+    pub fn get_or_insert_default(&mut self) -> Pin<&mut T>
+    where
+        T: ExternType<Kind = Trivial> + Default + Clone,
+    {
+        match Self::as_ref(self) {
+            None => self.reset_to(T::default()),
+            Some(value) if self.use_count() != 1 => {
+                let value = value.clone();
+                self.reset_to(value);
+            }
+            Some(_) => {}
+        }
+        let this = self as *const Self as *const c_void;
+        let ptr = unsafe { T::__get(this) } as *mut T;
+        unsafe { Pin::new_unchecked(&mut *ptr) }
+    }
+
+    /// Returns the number of SeastarSharedPtr instances referring to the same object,
+    /// including this one, or 0 if this SeastarSharedPtr is null.
+    ///
+    /// Matches the behavior of seastar::shared_ptr\<T\>::use\_count. seastar keeps
+    /// the count in a `long`, which is never wider than `usize` on the
+    /// platforms seastar supports, so the full count is returned; the C++
+    /// shims check this at compile time.
+    pub fn use_count(&self) -> usize {
+        let this = self as *const Self as *const c_void;
+        unsafe { T::__use_count(this) }
+    }
+
+    /// Checks whether this SeastarSharedPtr is the only owner of its object, so
+    /// that nobody else can observe changes made through it.
+    ///
+    /// Shorthand for `ptr.use_count() == 1`, so false for a null pointer.
+    pub fn is_unique(&self) -> bool {
+        self.use_count() == 1
+    }
+
+    /// Makes another SeastarSharedPtr owning the same object, without copying the object.
+    ///
+    /// This is the same as [`clone`][Clone::clone], under a name that does not
+    /// suggest a deep copy: only the reference count goes up.
+    ///
+    /// ```
+    /// use cxx::SeastarSharedPtr;
+    ///
+    /// let a = SeastarSharedPtr::new(2020i32);
+    /// let b = a.share();
+    /// assert!(a.ptr_eq(&b));
+    /// assert_eq!(a.use_count(), 2);
+    /// ```
+    pub fn share(&self) -> Self {
+        self.clone()
+    }
+
+    /// Makes another SeastarSharedPtr owning the same object, or returns None if this
+    /// SeastarSharedPtr is null.
+    ///
+    /// Unlike [`clone`][Clone::clone], which turns a null pointer into another
+    /// null pointer, this makes a null pointer visible at the point it would
+    /// have been copied.
+    pub fn clone_non_null(&self) -> Option<Self> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self.clone())
+        }
+    }
+
+    /// Returns true if the two SeastarSharedPtrs point to the same object, or if both
+    /// are null.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        let this = self as *const Self as *const c_void;
+        let other = other as *const Self as *const c_void;
+        unsafe { T::__get(this) == T::__get(other) }
+    }
+
+    /// Exchanges the objects owned by this SeastarSharedPtr and `other`.
+    ///
+    /// Neither reference count is touched. The handle is trivially
+    /// relocatable, as relied on by passing it by value across the bridge,
+    /// so swapping its words in place is all a C++ swap would do.
+    pub fn swap(&mut self, other: &mut Self) {
+        mem::swap(self, other);
+    }
+
+    /// Moves the owned object into a new SeastarLwSharedPtr, provided this
+    /// SeastarSharedPtr is its only owner, or gives back `self` unchanged.
+    ///
+    /// This is the counterpart of [`SeastarLwSharedPtr::to_shared`]. A
+    /// seastar::lw\_shared\_ptr keeps its count next to the object, so it
+    /// cannot take over the allocation made for a seastar::shared\_ptr.
+    /// Instead the object is moved into a fresh seastar::lw\_shared\_ptr
+    /// allocation and the old one is freed without running any destructor.
+    /// That is only possible with no other owners left to observe the move,
+    /// so a null or shared pointer is returned as the `Err`.
+    ///
+    /// Only an object allocated together with its count, by
+    /// [`new`][SeastarSharedPtr::new] or by C++ `seastar::make_shared`, can
+    /// be handed over this way. A pointer from
+    /// [`new_with_deleter`][SeastarSharedPtr::new_with_deleter] or
+    /// [`from_std_shared`][SeastarSharedPtr::from_std_shared] leaves freeing
+    /// the object to its deleter or to the std::shared\_ptr, and is returned
+    /// as the `Err` as well.
+    ///
+    /// The use count is not atomic and only counts owners on one shard, so
+    /// like any other use of the pointer this is only sound on the shard that
+    /// owns the object.
+    pub fn try_into_lw(self) -> Result<SeastarLwSharedPtr<T>, Self>
+    where
+        T: SeastarLwSharedPtrTarget + ExternType<Kind = Trivial>,
+    {
+        let mut this = ManuallyDrop::new(self);
+        let mut value = MaybeUninit::<T>::uninit();
+        let taken = unsafe {
+            let this = &mut *this as *mut Self as *mut c_void;
+            T::__take_unique(this, value.as_mut_ptr())
+        };
+        if !taken {
+            return Err(ManuallyDrop::into_inner(this));
+        }
+        // The pointer was left null, which needs no drop.
+        Ok(SeastarLwSharedPtr::new(unsafe { value.assume_init() }))
+    }
+
+    /// Converts this SeastarSharedPtr into a pointer to the base class `U`,
+    /// sharing ownership of the same object without touching the reference
+    /// count.
+    ///
+    /// Matches the behavior of converting a seastar::shared\_ptr to a derived
+    /// class into one to its base class. The pointer is adjusted to the base
+    /// subobject, which need not be at the same address as the object under
+    /// multiple inheritance. Only bases declared with `#[seastar_upcast(...)]`
+    /// in the bridge are accepted, see [`SeastarUpcast`].
+    pub fn upcast<U>(self) -> SeastarSharedPtr<U>
+    where
+        T: SeastarUpcast<U>,
+        U: SeastarSharedPtrTarget,
+    {
+        let mut this = ManuallyDrop::new(self);
+        let mut shared_ptr = MaybeUninit::<SeastarSharedPtr<U>>::uninit();
+        unsafe {
+            let this = &mut *this as *mut Self as *mut c_void;
+            T::__upcast(this, shared_ptr.as_mut_ptr().cast());
+            shared_ptr.assume_init()
+        }
+    }
+
+    /// Reinterprets this SeastarSharedPtr as a pointer to `U`, sharing
+    /// ownership of the same object without touching the reference count.
+    ///
+    /// This is the unchecked counterpart of seastar::static\_pointer\_cast for
+    /// casting from a base class to a derived class. No `dynamic_cast` is
+    /// performed, so the caller is responsible for knowing the dynamic type.
+    ///
+    /// # Safety
+    ///
+    /// If this SeastarSharedPtr is non-null, the object it owns must be a `U`,
+    /// and the `T` subobject must be located at the same address as the `U`
+    /// object, as is the case for the first base class under single
+    /// inheritance.
+    pub unsafe fn downcast_unchecked<U>(self) -> SeastarSharedPtr<U>
+    where
+        U: SeastarSharedPtrTarget,
+    {
+        let this = ManuallyDrop::new(self);
+        SeastarSharedPtr {
+            repr: this.repr,
+            ty: PhantomData,
+        }
+    }
+
+    /// Consumes the SeastarSharedPtr, returning a raw pointer to it without
+    /// touching the reference count.
+    ///
+    /// A seastar::shared\_ptr is two pointers wide, so unlike
+    /// [`SeastarLwSharedPtr::into_raw`] this moves the handle into a heap
+    /// allocation and returns a pointer to that, usable from C++ as a
+    /// `seastar::shared_ptr<T> *`. The allocation and the reference it
+    /// carries are leaked unless the pointer is passed exactly once to
+    /// [`SeastarSharedPtr::from_raw`].
+    ///
+    /// [`SeastarLwSharedPtr::into_raw`]: crate::SeastarLwSharedPtr::into_raw
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn into_raw(self) -> *mut c_void {
+        Box::into_raw(Box::new(self)).cast()
+    }
+
+    /// Reconstructs a SeastarSharedPtr from the raw pointer returned by
+    /// [`SeastarSharedPtr::into_raw`], taking over its reference and freeing
+    /// the intermediate allocation.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by `into_raw` on a SeastarSharedPtr of
+    /// the same `T`, and must not be passed to `from_raw` more than once.
+    /// Reclaiming the same pointer twice results in a double free.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub unsafe fn from_raw(ptr: *mut c_void) -> Self {
+        *unsafe { Box::from_raw(ptr.cast::<Self>()) }
+    }
 }
 
-unsafe impl<T> Send for SeastarSharedPtr<T> where T: Send + Sync + SeastarSharedPtrTarget {}
-unsafe impl<T> Sync for SeastarSharedPtr<T> where T: Send + Sync + SeastarSharedPtrTarget {}
+impl SeastarSharedPtr<CxxString> {
+    /// Makes a new SeastarSharedPtr owning a `std::string` with the contents of `s`.
+    ///
+    /// The string is constructed directly inside the seastar::shared\_ptr allocation,
+    /// without first building a CxxString on the Rust side.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        Self::from_bytes(s.as_bytes())
+    }
+
+    /// Makes a new SeastarSharedPtr owning a `std::string` holding a copy of `bytes`.
+    ///
+    /// The string is built from a pointer and a length, so embedded NUL bytes
+    /// are kept rather than ending it, and the bytes need not be UTF-8.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        trace_ptr!(New(SeastarSharedPtr));
+        extern "C" {
+            #[link_name = "cxxbridge1$seastar$shared_ptr$string$from_str"]
+            fn from_str(new: *mut c_void, ptr: *const u8, len: usize);
+        }
+        let mut shared_ptr = MaybeUninit::<SeastarSharedPtr<CxxString>>::uninit();
+        let new = shared_ptr.as_mut_ptr().cast();
+        unsafe {
+            from_str(new, bytes.as_ptr(), bytes.len());
+            shared_ptr.assume_init()
+        }
+    }
+
+    /// Returns the pointed-to string as a `&str`, or `None` if the pointer is
+    /// null.
+    ///
+    /// Fails if the contents of the string are not valid UTF-8.
+    pub fn to_str(&self) -> Result<Option<&str>, Utf8Error> {
+        match self.as_ref() {
+            None => Ok(None),
+            Some(string) => string.to_str().map(Some),
+        }
+    }
+}
 
 impl<T> Clone for SeastarSharedPtr<T>
 where
     T: SeastarSharedPtrTarget,
 {
     fn clone(&self) -> Self {
+        trace_ptr!(Clone(SeastarSharedPtr));
         let mut shared_ptr = MaybeUninit::<SeastarSharedPtr<T>>::uninit();
         let new = shared_ptr.as_mut_ptr().cast();
         let this = self as *const Self as *mut c_void;
@@ -80,6 +687,12 @@ where
             shared_ptr.assume_init()
         }
     }
+
+    fn clone_from(&mut self, source: &Self) {
+        if !self.ptr_eq(source) {
+            *self = source.clone();
+        }
+    }
 }
 
 // SeastarSharedPtr is not a self-referential type and is safe to move out of a Pin,
@@ -91,8 +704,22 @@ where
     T: SeastarSharedPtrTarget,
 {
     fn drop(&mut self) {
+        trace_ptr!(Drop(SeastarSharedPtr));
+        // Catches the same storage being dropped a second time, as by
+        // drop_in_place on a handle already dropped, before the poisoned
+        // words reach C++. A bitwise copy made with ptr::read has storage of
+        // its own that was never poisoned, so dropping both goes unnoticed.
+        #[cfg(debug_assertions)]
+        assert!(
+            unsafe { self.repr[0].assume_init() } != POISON,
+            "SeastarSharedPtr dropped twice",
+        );
         let this = self as *mut Self as *mut c_void;
         unsafe { T::__drop(this) }
+        #[cfg(debug_assertions)]
+        unsafe {
+            ptr::write_volatile(&mut self.repr, [MaybeUninit::new(POISON); 2]);
+        }
     }
 }
 
@@ -113,11 +740,151 @@ where
     }
 }
 
+/// Non-null reference to an object owned by a [`SeastarSharedPtr`], keeping the
+/// object alive for as long as the guard lives.
+///
+/// Returned by [`SeastarSharedPtr::deref_owned`].
+pub struct SeastarRef<T>
+where
+    T: SeastarSharedPtrTarget,
+{
+    // Never null.
+    ptr: SeastarSharedPtr<T>,
+}
+
+impl<T> SeastarRef<T>
+where
+    T: SeastarSharedPtrTarget,
+{
+    /// Converts the guard back into the SeastarSharedPtr holding its
+    /// reference.
+    pub fn into_inner(self) -> SeastarSharedPtr<T> {
+        self.ptr
+    }
+}
+
+impl<T> Clone for SeastarRef<T>
+where
+    T: SeastarSharedPtrTarget,
+{
+    fn clone(&self) -> Self {
+        SeastarRef {
+            ptr: self.ptr.clone(),
+        }
+    }
+}
+
+impl<T> Deref for SeastarRef<T>
+where
+    T: SeastarSharedPtrTarget,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.ptr.as_ptr() }
+    }
+}
+
+impl<T> Debug for SeastarRef<T>
+where
+    T: Debug + SeastarSharedPtrTarget,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&**self, formatter)
+    }
+}
+
+/// Panics if the SeastarSharedPtr is null, like dereferencing it does. Use the inherent
+/// [`SeastarSharedPtr::as_ref`] to check for null instead.
+impl<T> AsRef<T> for SeastarSharedPtr<T>
+where
+    T: SeastarSharedPtrTarget,
+{
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+/// Panics if the SeastarSharedPtr is null, like dereferencing it does.
+///
+/// For non-null pointers this agrees with the by-value comparisons, so a map
+/// keyed by SeastarSharedPtrs can be looked up by an owned object, as in
+/// `map.get(&value)`. Such a lookup borrows the keys it compares against, and
+/// panics on reaching a null key, so a map looked up by value must not have
+/// null keys. Lookups by another SeastarSharedPtr are unaffected.
+impl<T> Borrow<T> for SeastarSharedPtr<T>
+where
+    T: SeastarSharedPtrTarget,
+{
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+/// Yields the owned object if there is one, or nothing if the SeastarSharedPtr is
+/// null.
+impl<'a, T> IntoIterator for &'a SeastarSharedPtr<T>
+where
+    T: SeastarSharedPtrTarget,
+{
+    type Item = &'a T;
+    type IntoIter = option::IntoIter<&'a T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_ref().into_iter()
+    }
+}
+
+/// Compares the owned objects by value, not by address; use
+/// [`ptr_eq`][SeastarSharedPtr::ptr_eq] to compare identity. A null SeastarSharedPtr is equal
+/// only to another null one.
+impl<T> PartialEq for SeastarSharedPtr<T>
+where
+    T: PartialEq + SeastarSharedPtrTarget,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl<T> Eq for SeastarSharedPtr<T> where T: Eq + SeastarSharedPtrTarget {}
+
+/// Orders by the owned objects, with null sorting before every non-null
+/// SeastarSharedPtr, the same as comparing [`as_ref`][SeastarSharedPtr::as_ref] results.
+/// See the [`Borrow`] impl about null keys in ordered maps.
+impl<T> PartialOrd for SeastarSharedPtr<T>
+where
+    T: PartialOrd + SeastarSharedPtrTarget,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_ref().partial_cmp(&other.as_ref())
+    }
+}
+
+impl<T> Ord for SeastarSharedPtr<T>
+where
+    T: Ord + SeastarSharedPtrTarget,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ref().cmp(&other.as_ref())
+    }
+}
+
 impl<T> Debug for SeastarSharedPtr<T>
 where
     T: Debug + SeastarSharedPtrTarget,
 {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if formatter.alternate() {
+            // {:#?} additionally shows the ownership state, which is what
+            // matters when chasing reference counting bugs.
+            return formatter
+                .debug_struct("SeastarSharedPtr")
+                .field("ptr", &self.as_ptr())
+                .field("use_count", &self.use_count())
+                .field("value", &self.as_ref())
+                .finish();
+        }
         match self.as_ref() {
             None => formatter.write_str("nullptr"),
             Some(value) => Debug::fmt(value, formatter),
@@ -137,6 +904,16 @@ where
     }
 }
 
+// Called by the holder made in new_with_deleter, from its C++ destructor, with
+// the deleter that was passed as a data pointer.
+unsafe extern "C" fn call_deleter<T>(deleter: *mut c_void, value: *mut T) {
+    let deleter = unsafe { mem::transmute::<*mut c_void, fn(*mut T)>(deleter) };
+    #[cfg(feature = "std")]
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| deleter(value)));
+    #[cfg(not(feature = "std"))]
+    crate::unwind::prevent_unwind("SeastarSharedPtr deleter", || deleter(value));
+}
+
 /// Trait bound for types which may be used as the `T` inside of a
 /// `SeastarSharedPtr<T>` in generic code.
 ///
@@ -179,15 +956,140 @@ pub unsafe trait SeastarSharedPtrTarget {
         unreachable!()
     }
     #[doc(hidden)]
+    unsafe fn __new_with_deleter(
+        value: Self,
+        call: unsafe extern "C" fn(*mut c_void, *mut Self),
+        deleter: *mut c_void,
+        new: *mut c_void,
+    ) where
+        Self: Sized,
+    {
+        // Like __new, only targets that can exist by value get this method.
+        let _ = value;
+        let _ = call;
+        let _ = deleter;
+        let _ = new;
+        unreachable!()
+    }
+    #[doc(hidden)]
+    unsafe fn __take_unique(this: *mut c_void, value: *mut Self) -> bool
+    where
+        Self: Sized,
+    {
+        // Like __new, only targets that can exist by value get this method.
+        let _ = this;
+        let _ = value;
+        unreachable!()
+    }
+    #[doc(hidden)]
+    unsafe fn __with_len(len: usize, new: *mut c_void) {
+        // Only std::vector targets get this method, to back
+        // SeastarSharedSlice::new_array.
+        let _ = len;
+        let _ = new;
+        unreachable!()
+    }
+    #[doc(hidden)]
     unsafe fn __clone(this: *const c_void, new: *mut c_void);
     #[doc(hidden)]
     unsafe fn __get(this: *const c_void) -> *const Self;
     #[doc(hidden)]
+    unsafe fn __from_std(src: *const c_void, new: *mut c_void);
+    #[doc(hidden)]
+    unsafe fn __use_count(this: *const c_void) -> usize;
+    #[doc(hidden)]
     unsafe fn __drop(this: *mut c_void);
 }
 
+/// Trait bound for upcasting a `SeastarSharedPtr<Self>` to a
+/// `SeastarSharedPtr<Base>` with [`SeastarSharedPtr::upcast`].
+///
+/// The bridge implements it for an opaque C++ type for every base class named
+/// in a `#[seastar_upcast(...)]` attribute on its declaration. The C++ compiler
+/// checks that each is an unambiguous public base of the type.
+///
+/// ```no_run
+/// #[cxx::bridge]
+/// mod ffi {
+///     unsafe extern "C++" {
+///         type Shape;
+///         #[seastar_upcast(Shape)]
+///         type Circle;
+///
+///         fn area(self: &Shape) -> f64;
+///         fn make_circle(radius: f64) -> SeastarSharedPtr<Circle>;
+///     }
+/// }
+///
+/// let shape = ffi::make_circle(1.0).upcast::<ffi::Shape>();
+/// println!("{}", shape.area());
+/// ```
+///
+/// This trait has no publicly callable or implementable methods. Implementing
+/// it outside of the CXX codebase is not supported.
+pub unsafe trait SeastarUpcast<Base>: SeastarSharedPtrTarget
+where
+    Base: SeastarSharedPtrTarget,
+{
+    #[doc(hidden)]
+    unsafe fn __upcast(this: *mut c_void, new: *mut c_void);
+}
+
+macro_rules! shared_ptr_target_by_value_methods {
+    (opaque, $segment:expr, $ty:ty) => {};
+    (vector, $segment:expr, $ty:ty) => {
+        unsafe fn __with_len(len: usize, new: *mut c_void) {
+            extern "C" {
+                attr! {
+                    #[link_name = concat!("cxxbridge1$seastar$shared_ptr$", $segment, "$with_len")]
+                    fn __with_len(len: usize, new: *mut c_void);
+                }
+            }
+            unsafe { __with_len(len, new) }
+        }
+    };
+    (trivial, $segment:expr, $ty:ty) => {
+        unsafe fn __new(value: Self, new: *mut c_void) {
+            extern "C" {
+                attr! {
+                    #[link_name = concat!("cxxbridge1$seastar$shared_ptr$", $segment, "$uninit")]
+                    fn __uninit(new: *mut c_void) -> *mut c_void;
+                }
+            }
+            unsafe { __uninit(new).cast::<$ty>().write(value) }
+        }
+        unsafe fn __new_with_deleter(
+            value: Self,
+            call: unsafe extern "C" fn(*mut c_void, *mut Self),
+            deleter: *mut c_void,
+            new: *mut c_void,
+        ) {
+            extern "C" {
+                attr! {
+                    #[link_name = concat!("cxxbridge1$seastar$shared_ptr$", $segment, "$uninit_with_deleter")]
+                    fn __uninit_with_deleter(
+                        call: unsafe extern "C" fn(*mut c_void, *mut $ty),
+                        deleter: *mut c_void,
+                        new: *mut c_void,
+                    ) -> *mut c_void;
+                }
+            }
+            unsafe { __uninit_with_deleter(call, deleter, new).cast::<$ty>().write(value) }
+        }
+        unsafe fn __take_unique(this: *mut c_void, value: *mut Self) -> bool {
+            extern "C" {
+                attr! {
+                    #[link_name = concat!("cxxbridge1$seastar$shared_ptr$", $segment, "$take_unique")]
+                    fn __take_unique(this: *mut c_void, value: *mut $ty) -> bool;
+                }
+            }
+            unsafe { __take_unique(this, value) }
+        }
+    };
+}
+
 macro_rules! impl_shared_ptr_target {
-    ($segment:expr, $name:expr, $ty:ty) => {
+    ($kind:ident, $segment:expr, $name:expr, $ty:ty) => {
         unsafe impl SeastarSharedPtrTarget for $ty {
             fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
                 f.write_str($name)
@@ -201,15 +1103,7 @@ macro_rules! impl_shared_ptr_target {
                 }
                 unsafe { __null(new) }
             }
-            unsafe fn __new(value: Self, new: *mut c_void) {
-                extern "C" {
-                    attr! {
-                        #[link_name = concat!("cxxbridge1$seastar$shared_ptr$", $segment, "$uninit")]
-                        fn __uninit(new: *mut c_void) -> *mut c_void;
-                    }
-                }
-                unsafe { __uninit(new).cast::<$ty>().write(value) }
-            }
+            shared_ptr_target_by_value_methods!($kind, $segment, $ty);
             unsafe fn __clone(this: *const c_void, new: *mut c_void) {
                 extern "C" {
                     attr! {
@@ -228,6 +1122,24 @@ macro_rules! impl_shared_ptr_target {
                 }
                 unsafe { __get(this) }.cast()
             }
+            unsafe fn __from_std(src: *const c_void, new: *mut c_void) {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$shared_ptr$", $segment, "$from_std")]
+                        fn __from_std(src: *const c_void, new: *mut c_void);
+                    }
+                }
+                unsafe { __from_std(src, new) }
+            }
+            unsafe fn __use_count(this: *const c_void) -> usize {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge1$seastar$shared_ptr$", $segment, "$use_count")]
+                        fn __use_count(this: *const c_void) -> usize;
+                    }
+                }
+                unsafe { __use_count(this) }
+            }
             unsafe fn __drop(this: *mut c_void) {
                 extern "C" {
                     attr! {
@@ -241,24 +1153,55 @@ macro_rules! impl_shared_ptr_target {
     };
 }
 
+// Primitive targets are named by their C++ spelling in panic messages and
+// Debug output, so that they read the same as the C++ side's diagnostics.
 macro_rules! impl_shared_ptr_target_for_primitive {
-    ($ty:ident) => {
-        impl_shared_ptr_target!(stringify!($ty), stringify!($ty), $ty);
+    ($ty:ident, $name:expr) => {
+        impl_shared_ptr_target!(trivial, stringify!($ty), $name, $ty);
+    };
+}
+
+// There are deliberately no separate impls for C++ character types: `c_char`
+// is a type alias of `i8` or `u8`, and cxx has no distinct Rust type for
+// `char16_t`/`char32_t`, which it passes as `u16`/`u32`. Pointers to those
+// types are handled through the same-sized integer impls below.
+impl_shared_ptr_target_for_primitive!(bool, "bool");
+impl_shared_ptr_target_for_primitive!(u8, "uint8_t");
+impl_shared_ptr_target_for_primitive!(u16, "uint16_t");
+impl_shared_ptr_target_for_primitive!(u32, "uint32_t");
+impl_shared_ptr_target_for_primitive!(u64, "uint64_t");
+impl_shared_ptr_target_for_primitive!(usize, "size_t");
+impl_shared_ptr_target_for_primitive!(i8, "int8_t");
+impl_shared_ptr_target_for_primitive!(i16, "int16_t");
+impl_shared_ptr_target_for_primitive!(i32, "int32_t");
+impl_shared_ptr_target_for_primitive!(i64, "int64_t");
+impl_shared_ptr_target_for_primitive!(isize, "rust::isize");
+impl_shared_ptr_target_for_primitive!(f32, "float");
+impl_shared_ptr_target_for_primitive!(f64, "double");
+
+impl_shared_ptr_target!(opaque, "string", "std::string", CxxString);
+
+macro_rules! impl_shared_ptr_target_for_vector {
+    ($segment:expr, $name:expr, $ty:ty) => {
+        impl_shared_ptr_target!(
+            vector,
+            concat!("std$vector$", $segment),
+            concat!("std::vector<", $name, ">"),
+            CxxVector<$ty>
+        );
     };
 }
 
-impl_shared_ptr_target_for_primitive!(bool);
-impl_shared_ptr_target_for_primitive!(u8);
-impl_shared_ptr_target_for_primitive!(u16);
-impl_shared_ptr_target_for_primitive!(u32);
-impl_shared_ptr_target_for_primitive!(u64);
-impl_shared_ptr_target_for_primitive!(usize);
-impl_shared_ptr_target_for_primitive!(i8);
-impl_shared_ptr_target_for_primitive!(i16);
-impl_shared_ptr_target_for_primitive!(i32);
-impl_shared_ptr_target_for_primitive!(i64);
-impl_shared_ptr_target_for_primitive!(isize);
-impl_shared_ptr_target_for_primitive!(f32);
-impl_shared_ptr_target_for_primitive!(f64);
-
-impl_shared_ptr_target!("string", "CxxString", CxxString);
+impl_shared_ptr_target_for_vector!("u8", "uint8_t", u8);
+impl_shared_ptr_target_for_vector!("u16", "uint16_t", u16);
+impl_shared_ptr_target_for_vector!("u32", "uint32_t", u32);
+impl_shared_ptr_target_for_vector!("u64", "uint64_t", u64);
+impl_shared_ptr_target_for_vector!("usize", "size_t", usize);
+impl_shared_ptr_target_for_vector!("i8", "int8_t", i8);
+impl_shared_ptr_target_for_vector!("i16", "int16_t", i16);
+impl_shared_ptr_target_for_vector!("i32", "int32_t", i32);
+impl_shared_ptr_target_for_vector!("i64", "int64_t", i64);
+impl_shared_ptr_target_for_vector!("isize", "rust::isize", isize);
+impl_shared_ptr_target_for_vector!("f32", "float", f32);
+impl_shared_ptr_target_for_vector!("f64", "double", f64);
+impl_shared_ptr_target_for_vector!("string", "std::string", CxxString);
@@ -0,0 +1,177 @@
+use crate::cxx_vector::{CxxVector, VectorElement};
+use crate::kind::Trivial;
+use crate::seastar_shared_ptr::{SeastarSharedPtr, SeastarSharedPtrTarget};
+use crate::shared_mut_error::SharedMutError;
+use crate::ExternType;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::mem::MaybeUninit;
+use core::pin::Pin;
+use core::slice;
+
+/// A fixed length array shared by reference counting, backed by a C++
+/// `seastar::shared_ptr<std::vector<T>>`.
+///
+/// seastar::shared\_ptr has no array form, so the elements live in a
+/// std::vector, which also carries their count. A SeastarSharedSlice is never
+/// null, and can be handed to C++ as the underlying pointer with
+/// [`into_shared_ptr`][SeastarSharedSlice::into_shared_ptr].
+///
+/// So far the elements can be the primitive types supported by
+/// [`CxxVector`].
+pub struct SeastarSharedSlice<T>
+where
+    T: VectorElement,
+    CxxVector<T>: SeastarSharedPtrTarget,
+{
+    ptr: SeastarSharedPtr<CxxVector<T>>,
+}
+
+impl<T> SeastarSharedSlice<T>
+where
+    T: VectorElement,
+    CxxVector<T>: SeastarSharedPtrTarget,
+{
+    /// Allocates an array of `len` elements on the heap, setting the element
+    /// at each index `i` to `init(i)`.
+    ///
+    /// A zero length array is still a valid allocation owned by a non-null
+    /// pointer, just as `seastar::make_shared<std::vector<T>>(0)` is.
+    ///
+    /// The elements are zero initialized by C++ before `init` runs, so a
+    /// panic in `init` drops a fully initialized array and leaks nothing.
+    pub fn new_array<F>(len: usize, mut init: F) -> Self
+    where
+        T: ExternType<Kind = Trivial>,
+        F: FnMut(usize) -> T,
+    {
+        let mut ptr = MaybeUninit::<SeastarSharedPtr<CxxVector<T>>>::uninit();
+        let new = ptr.as_mut_ptr().cast();
+        let ptr = unsafe {
+            CxxVector::<T>::__with_len(len, new);
+            ptr.assume_init()
+        };
+        // The vector was just allocated, so this is its only owner.
+        let this = &ptr as *const SeastarSharedPtr<CxxVector<T>> as *const c_void;
+        let vector = unsafe { CxxVector::<T>::__get(this) } as *mut CxxVector<T>;
+        let elements = unsafe { Pin::new_unchecked(&mut *vector) }.as_mut_slice();
+        for (i, element) in elements.iter_mut().enumerate() {
+            *element = init(i);
+        }
+        SeastarSharedSlice { ptr }
+    }
+
+    /// Adopts a pointer to a vector, or returns None if it is null.
+    pub fn from_shared_ptr(ptr: SeastarSharedPtr<CxxVector<T>>) -> Option<Self> {
+        if ptr.is_null() {
+            None
+        } else {
+            Some(SeastarSharedSlice { ptr })
+        }
+    }
+
+    /// Consumes the SeastarSharedSlice, returning the pointer to the vector
+    /// holding its elements.
+    pub fn into_shared_ptr(self) -> SeastarSharedPtr<CxxVector<T>> {
+        self.ptr
+    }
+
+    /// Returns the number of elements in the array.
+    pub fn len(&self) -> usize {
+        self.vector().len()
+    }
+
+    /// Returns true if the array has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the elements as a Rust slice.
+    pub fn as_slice(&self) -> &[T]
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        self.vector().as_slice()
+    }
+
+    /// Returns a reference to the element at index `i`, or None if `i` is out
+    /// of bounds.
+    pub fn get(&self, i: usize) -> Option<&T>
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        self.as_slice().get(i)
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> slice::Iter<'_, T>
+    where
+        T: ExternType<Kind = Trivial>,
+    {
+        self.as_slice().iter()
+    }
+
+    /// Copies the elements into a new Rust `Vec`.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: ExternType<Kind = Trivial> + Clone,
+    {
+        self.as_slice().to_vec()
+    }
+
+    /// Overwrites the elements with copies of those in `src`, provided this
+    /// SeastarSharedSlice is the only owner of the array.
+    ///
+    /// Fails without copying anything if the array is shared with other
+    /// owners. Like the slice method of the same name, panics if `src` does
+    /// not have the same length as the array.
+    pub fn copy_from_slice(&mut self, src: &[T]) -> Result<(), SharedMutError>
+    where
+        T: ExternType<Kind = Trivial> + Copy,
+    {
+        if !self.ptr.is_unique() {
+            return Err(SharedMutError::Shared);
+        }
+        let vector = self.ptr.as_ptr() as *mut CxxVector<T>;
+        let elements = unsafe { Pin::new_unchecked(&mut *vector) }.as_mut_slice();
+        elements.copy_from_slice(src);
+        Ok(())
+    }
+
+    /// Returns the number of SeastarSharedSlice and SeastarSharedPtr
+    /// instances sharing this array, including this one.
+    pub fn use_count(&self) -> usize {
+        self.ptr.use_count()
+    }
+
+    fn vector(&self) -> &CxxVector<T> {
+        // Never null, by construction.
+        &self.ptr
+    }
+}
+
+impl<T> Clone for SeastarSharedSlice<T>
+where
+    T: VectorElement,
+    CxxVector<T>: SeastarSharedPtrTarget,
+{
+    fn clone(&self) -> Self {
+        SeastarSharedSlice {
+            ptr: self.ptr.clone(),
+        }
+    }
+}
+
+impl<T> Debug for SeastarSharedSlice<T>
+where
+    T: VectorElement + ExternType<Kind = Trivial> + Debug,
+    CxxVector<T>: SeastarSharedPtrTarget,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self.as_slice(), formatter)
+    }
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "std")]
+
+use crate::result::{self, PtrLen};
+use crate::seastar_future::{SeastarFuture, SeastarFutureTarget};
+use core::any::Any;
+use core::ffi::c_void;
+use core::ptr;
+use std::boxed::Box;
+use std::panic::{self, AssertUnwindSafe};
+use std::string::{String, ToString};
+
+/// Runs `f` on shard `shard`, returning a future on the current shard that
+/// resolves to what it returned, by way of C++ `seastar::smp::submit_to`.
+///
+/// The closure is moved to the target shard and run there, which is why it
+/// and its result must be `Send`. Submitting to the current shard runs it
+/// right away, and the returned future is then already resolved. If the
+/// closure panics, the panic is caught on the target shard and the future
+/// resolves to `Err` with the panic message instead.
+///
+/// The future must be awaited under an executor driven by the seastar
+/// reactor of the current shard. A `shard` that is not less than the number
+/// of shards, which seastar::smp::submit\_to would not check, is refused:
+/// the closure is dropped without running and the future resolves to `Err`.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), cxx::SeastarException> {
+/// let words = vec!["seastar", "rust"];
+/// let len = cxx::seastar_submit_to(1, move || words.concat().len()).await?;
+/// assert_eq!(len, 11);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub fn seastar_submit_to<F, R>(shard: u32, f: F) -> SeastarFuture<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: SeastarFutureTarget + Send,
+{
+    let data = Box::into_raw(Box::new(f)).cast::<c_void>();
+    unsafe { SeastarFuture::from_raw(R::__submit_to(shard, data, call::<F, R>)) }
+}
+
+// Runs on the target shard, exactly once. A null `value` means the shard was
+// refused, and the closure is only dropped.
+unsafe extern "C" fn call<F, R>(data: *mut c_void, value: *mut R, panic: *mut PtrLen) -> bool
+where
+    F: FnOnce() -> R,
+{
+    let f = unsafe { Box::from_raw(data.cast::<F>()) };
+    if value.is_null() {
+        return false;
+    }
+    match panic::catch_unwind(AssertUnwindSafe(*f)) {
+        Ok(ret) => {
+            unsafe { ptr::write(value, ret) }
+            true
+        }
+        Err(payload) => {
+            let msg = panic_message(&*payload);
+            unsafe { ptr::write(panic, result::copy_error(msg)) }
+            false
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "seastar_submit_to closure panicked".to_string()
+    }
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "std")]
+
+use core::ffi::c_void;
+use core::fmt::{self, Debug, Display};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+extern "C" {
+    #[link_name = "cxxbridge1$seastar$socket_address$new_v4"]
+    fn socket_address_new_v4(octets: *const u8, port: u16) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$socket_address$new_v6"]
+    fn socket_address_new_v6(octets: *const u8, port: u16, scope_id: u32) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$socket_address$clone"]
+    fn socket_address_clone(this: *const c_void) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$socket_address$port"]
+    fn socket_address_port(this: *const c_void) -> u16;
+    #[link_name = "cxxbridge1$seastar$socket_address$ip"]
+    fn socket_address_ip(this: *const c_void, octets: *mut [u8; 16], scope_id: *mut u32) -> bool;
+    #[link_name = "cxxbridge1$seastar$socket_address$drop"]
+    fn socket_address_drop(this: *mut c_void);
+}
+
+/// Binding to C++ `seastar::socket_address` holding an IPv4 or IPv6
+/// endpoint.
+///
+/// The address lives in its own C++ heap allocation and SeastarSocketAddress
+/// owns a pointer to it. Use [`to_socket_addr`][SeastarSocketAddress::to_socket_addr]
+/// and the [`From`] impls to convert from and to [`std::net::SocketAddr`].
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub struct SeastarSocketAddress {
+    repr: *mut c_void,
+}
+
+impl SeastarSocketAddress {
+    /// Makes a new socket address for an IPv4 address and port.
+    pub fn from_ipv4(addr: Ipv4Addr, port: u16) -> Self {
+        let octets = addr.octets();
+        SeastarSocketAddress {
+            repr: unsafe { socket_address_new_v4(octets.as_ptr(), port) },
+        }
+    }
+
+    /// Makes a new socket address for an IPv6 address, port and scope id.
+    ///
+    /// The scope id selects the interface for link-local addresses and is 0
+    /// otherwise, as in [`SocketAddrV6`].
+    pub fn from_ipv6(addr: Ipv6Addr, port: u16, scope_id: u32) -> Self {
+        let octets = addr.octets();
+        SeastarSocketAddress {
+            repr: unsafe { socket_address_new_v6(octets.as_ptr(), port, scope_id) },
+        }
+    }
+
+    /// Returns the port, in host byte order.
+    pub fn port(&self) -> u16 {
+        unsafe { socket_address_port(self.repr) }
+    }
+
+    /// Returns the IP address.
+    pub fn ip(&self) -> IpAddr {
+        self.ip_and_scope_id().0
+    }
+
+    /// Returns the scope id of an IPv6 address, or 0 for an IPv4 address.
+    pub fn scope_id(&self) -> u32 {
+        self.ip_and_scope_id().1
+    }
+
+    /// Converts to a [`SocketAddr`], keeping the scope id of an IPv6 address.
+    pub fn to_socket_addr(&self) -> SocketAddr {
+        match self.ip_and_scope_id() {
+            (IpAddr::V4(ip), _) => SocketAddr::V4(SocketAddrV4::new(ip, self.port())),
+            (IpAddr::V6(ip), scope_id) => {
+                SocketAddr::V6(SocketAddrV6::new(ip, self.port(), 0, scope_id))
+            }
+        }
+    }
+
+    fn ip_and_scope_id(&self) -> (IpAddr, u32) {
+        let mut octets = [0u8; 16];
+        let mut scope_id = 0;
+        let is_ipv6 = unsafe { socket_address_ip(self.repr, &mut octets, &mut scope_id) };
+        if is_ipv6 {
+            (IpAddr::V6(Ipv6Addr::from(octets)), scope_id)
+        } else {
+            let ip = Ipv4Addr::new(octets[0], octets[1], octets[2], octets[3]);
+            (IpAddr::V4(ip), 0)
+        }
+    }
+}
+
+impl From<SocketAddr> for SeastarSocketAddress {
+    fn from(addr: SocketAddr) -> Self {
+        match addr {
+            SocketAddr::V4(addr) => SeastarSocketAddress::from_ipv4(*addr.ip(), addr.port()),
+            SocketAddr::V6(addr) => {
+                SeastarSocketAddress::from_ipv6(*addr.ip(), addr.port(), addr.scope_id())
+            }
+        }
+    }
+}
+
+impl From<&SeastarSocketAddress> for SocketAddr {
+    fn from(addr: &SeastarSocketAddress) -> Self {
+        addr.to_socket_addr()
+    }
+}
+
+impl Clone for SeastarSocketAddress {
+    fn clone(&self) -> Self {
+        SeastarSocketAddress {
+            repr: unsafe { socket_address_clone(self.repr) },
+        }
+    }
+}
+
+impl Drop for SeastarSocketAddress {
+    fn drop(&mut self) {
+        unsafe { socket_address_drop(self.repr) }
+    }
+}
+
+impl Debug for SeastarSocketAddress {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.to_socket_addr(), formatter)
+    }
+}
+
+impl Display for SeastarSocketAddress {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.to_socket_addr(), formatter)
+    }
+}
@@ -0,0 +1,202 @@
+#![cfg(feature = "alloc")]
+
+use crate::seastar_future::SeastarFuture;
+use crate::seastar_temporary_buffer::SeastarTemporaryBuffer;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::ptr;
+
+extern "C" {
+    #[link_name = "cxxbridge1$seastar$pipe$new"]
+    fn pipe_new(buffer_size: usize, output: *mut *mut c_void, input: *mut *mut c_void);
+    #[link_name = "cxxbridge1$seastar$input_stream$from_raw"]
+    fn input_stream_from_raw(raw: *mut c_void) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$input_stream$read"]
+    fn input_stream_read(this: *mut c_void) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$input_stream$close"]
+    fn input_stream_close(this: *mut c_void) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$input_stream$drop"]
+    fn input_stream_drop(this: *mut c_void);
+    #[link_name = "cxxbridge1$seastar$output_stream$from_raw"]
+    fn output_stream_from_raw(raw: *mut c_void) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$output_stream$write"]
+    fn output_stream_write(this: *mut c_void, buf: *const u8, len: usize) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$output_stream$flush"]
+    fn output_stream_flush(this: *mut c_void) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$output_stream$close"]
+    fn output_stream_close(this: *mut c_void) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$output_stream$drop"]
+    fn output_stream_drop(this: *mut c_void);
+}
+
+/// Makes a connected pair of in-memory streams: the bytes written to the
+/// output stream are read back from the input stream, in order.
+///
+/// The output stream gathers writes in a buffer of `buffer_size` bytes, and
+/// passes them on to the input stream whenever the buffer fills up or is
+/// [flushed][SeastarOutputStream::flush]. [Closing][SeastarOutputStream::close]
+/// the output stream flushes it and makes the input stream reach its end once
+/// everything written has been read. This is useful for testing protocol
+/// code without a network connection.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), cxx::SeastarException> {
+/// let (output, input) = cxx::seastar_pipe(4096);
+/// output.write(b"seastar").await?;
+/// output.close().await?;
+///
+/// let buf = input.read().await?;
+/// assert_eq!(&*buf, b"seastar");
+/// assert!(input.read().await?.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn seastar_pipe(buffer_size: usize) -> (SeastarOutputStream, SeastarInputStream) {
+    let mut output = ptr::null_mut();
+    let mut input = ptr::null_mut();
+    unsafe { pipe_new(buffer_size, &mut output, &mut input) }
+    (
+        SeastarOutputStream { repr: output },
+        SeastarInputStream { repr: input },
+    )
+}
+
+/// Binding to C++ `seastar::input_stream<char>`.
+///
+/// The stream lives in its own C++ heap allocation and SeastarInputStream owns
+/// a reference counted pointer to it, which reads and closes still in flight
+/// hold on to as well. Like seastar::input\_stream itself it must be used from
+/// the shard that made it, and only one read may be in flight at a time.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct SeastarInputStream {
+    repr: *mut c_void,
+}
+
+impl SeastarInputStream {
+    /// Takes ownership of a heap allocated seastar::input\_stream, such as one
+    /// made for a connected socket by C++ code.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a `seastar::input_stream<char> *` allocated with C++
+    /// `new`, and must not be owned by anything else. It is freed before this
+    /// returns, after moving the stream out of it.
+    pub unsafe fn from_raw(raw: *mut c_void) -> Self {
+        SeastarInputStream {
+            repr: unsafe { input_stream_from_raw(raw) },
+        }
+    }
+
+    /// Starts reading the next chunk of the stream, returning a future that
+    /// resolves to a buffer with the bytes read.
+    ///
+    /// Matches the behavior of seastar::input\_stream::read. The buffer holds
+    /// however many bytes the underlying source produced next, which need not
+    /// match how they were written. An empty buffer means the stream has
+    /// reached its end, and every read after that resolves to an empty
+    /// buffer as well. The next read must not be started until the future
+    /// has resolved.
+    pub fn read(&self) -> SeastarFuture<SeastarTemporaryBuffer> {
+        unsafe { SeastarFuture::from_raw(input_stream_read(self.repr)) }
+    }
+
+    /// Starts closing the stream, returning a future that resolves once it
+    /// has been closed.
+    ///
+    /// Matches the behavior of seastar::input\_stream::close. Reads must not
+    /// be started on the stream afterwards.
+    pub fn close(&self) -> SeastarFuture<()> {
+        unsafe { SeastarFuture::from_raw(input_stream_close(self.repr)) }
+    }
+}
+
+impl Drop for SeastarInputStream {
+    fn drop(&mut self) {
+        unsafe { input_stream_drop(self.repr) }
+    }
+}
+
+impl Debug for SeastarInputStream {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("SeastarInputStream")
+    }
+}
+
+/// Binding to C++ `seastar::output_stream<char>`.
+///
+/// The stream lives in its own C++ heap allocation and SeastarOutputStream
+/// owns a reference counted pointer to it, which writes, flushes and closes
+/// still in flight hold on to as well. Like seastar::output\_stream itself it
+/// must be used from the shard that made it, and each operation must resolve
+/// before the next one is started.
+///
+/// The stream should be [closed][SeastarOutputStream::close] once done with,
+/// to learn whether everything written got through. If it is dropped while
+/// still open instead, it is closed in the background once no operation is
+/// in flight, since seastar::output\_stream must not be destroyed with bytes
+/// still in its buffer. Any error from that close is ignored.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct SeastarOutputStream {
+    repr: *mut c_void,
+}
+
+impl SeastarOutputStream {
+    /// Takes ownership of a heap allocated seastar::output\_stream, such as
+    /// one made for a connected socket by C++ code.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a `seastar::output_stream<char> *` allocated with C++
+    /// `new`, and must not be owned by anything else. It is freed before this
+    /// returns, after moving the stream out of it.
+    pub unsafe fn from_raw(raw: *mut c_void) -> Self {
+        SeastarOutputStream {
+            repr: unsafe { output_stream_from_raw(raw) },
+        }
+    }
+
+    /// Starts writing `buf` to the stream, returning a future that resolves
+    /// once the stream has taken all of it.
+    ///
+    /// Matches the behavior of seastar::output\_stream::write. `buf` is
+    /// copied before this returns, so it need not outlive the future. There
+    /// are no partial writes: the future resolves to `Ok` once every byte has
+    /// been accepted, which for small writes only means they were buffered,
+    /// see [`flush`][SeastarOutputStream::flush]. If it resolves to `Err`,
+    /// any part of `buf` may have been written, and the stream should be
+    /// closed.
+    pub fn write(&self, buf: &[u8]) -> SeastarFuture<()> {
+        unsafe { SeastarFuture::from_raw(output_stream_write(self.repr, buf.as_ptr(), buf.len())) }
+    }
+
+    /// Starts passing everything buffered by earlier writes on to the
+    /// underlying sink, returning a future that resolves once it has done
+    /// so.
+    ///
+    /// Matches the behavior of seastar::output\_stream::flush.
+    pub fn flush(&self) -> SeastarFuture<()> {
+        unsafe { SeastarFuture::from_raw(output_stream_flush(self.repr)) }
+    }
+
+    /// Starts flushing and closing the stream, returning a future that
+    /// resolves once it has been closed.
+    ///
+    /// Matches the behavior of seastar::output\_stream::close. Writes must
+    /// not be started on the stream afterwards.
+    pub fn close(&self) -> SeastarFuture<()> {
+        unsafe { SeastarFuture::from_raw(output_stream_close(self.repr)) }
+    }
+}
+
+impl Drop for SeastarOutputStream {
+    fn drop(&mut self) {
+        unsafe { output_stream_drop(self.repr) }
+    }
+}
+
+impl Debug for SeastarOutputStream {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("SeastarOutputStream")
+    }
+}
@@ -0,0 +1,175 @@
+#[cfg(feature = "alloc")]
+use crate::result::PtrLen;
+use crate::seastar_future::SeastarFutureTarget;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::mem;
+use core::ops::Deref;
+use core::slice;
+
+extern "C" {
+    #[link_name = "cxxbridge1$seastar$temporary_buffer$new"]
+    fn temporary_buffer_new(data: *const u8, len: usize) -> *mut c_void;
+    #[link_name = "cxxbridge1$seastar$temporary_buffer$data"]
+    fn temporary_buffer_data(this: *const c_void) -> *const u8;
+    #[link_name = "cxxbridge1$seastar$temporary_buffer$size"]
+    fn temporary_buffer_size(this: *const c_void) -> usize;
+    #[link_name = "cxxbridge1$seastar$temporary_buffer$drop"]
+    fn temporary_buffer_drop(this: *mut c_void);
+}
+
+/// Binding to C++ `seastar::temporary_buffer<char>`.
+///
+/// The buffer lives in its own C++ heap allocation and SeastarTemporaryBuffer
+/// owns a pointer to it. The bytes can be read through [`Deref`] to `[u8]`.
+/// Streams hand out the data they read in these buffers, with an empty one
+/// marking the end of the stream.
+#[repr(C)]
+pub struct SeastarTemporaryBuffer {
+    repr: *mut c_void,
+}
+
+impl SeastarTemporaryBuffer {
+    /// Makes an empty buffer.
+    ///
+    /// Matches the behavior of default-constructing a
+    /// seastar::temporary\_buffer.
+    pub fn new() -> Self {
+        SeastarTemporaryBuffer::from_slice(&[])
+    }
+
+    /// Allocates a buffer holding a copy of `data`.
+    pub fn from_slice(data: &[u8]) -> Self {
+        SeastarTemporaryBuffer {
+            repr: unsafe { temporary_buffer_new(data.as_ptr(), data.len()) },
+        }
+    }
+
+    /// Returns the number of bytes in the buffer.
+    ///
+    /// Matches the behavior of seastar::temporary\_buffer::size.
+    pub fn len(&self) -> usize {
+        unsafe { temporary_buffer_size(self.repr) }
+    }
+
+    /// Checks whether the buffer holds no bytes, which is how a stream
+    /// reports that it has reached its end.
+    ///
+    /// Matches the behavior of seastar::temporary\_buffer::empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the bytes in the buffer.
+    pub fn as_slice(&self) -> &[u8] {
+        let len = self.len();
+        if len == 0 {
+            // An empty buffer may not point anywhere.
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(temporary_buffer_data(self.repr), len) }
+    }
+}
+
+impl Default for SeastarTemporaryBuffer {
+    fn default() -> Self {
+        SeastarTemporaryBuffer::new()
+    }
+}
+
+impl Deref for SeastarTemporaryBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for SeastarTemporaryBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl Drop for SeastarTemporaryBuffer {
+    fn drop(&mut self) {
+        unsafe { temporary_buffer_drop(self.repr) }
+    }
+}
+
+impl Debug for SeastarTemporaryBuffer {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("SeastarTemporaryBuffer")
+    }
+}
+
+// The future hands the buffer over as a pointer to its heap allocation, which
+// is the whole representation of a SeastarTemporaryBuffer.
+unsafe impl SeastarFutureTarget for SeastarTemporaryBuffer {
+    fn __typename(f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("seastar::temporary_buffer<char>")
+    }
+    unsafe fn __ready(value: Self) -> *mut c_void {
+        extern "C" {
+            #[link_name = "cxxbridge1$seastar$future$temporary_buffer$ready"]
+            fn __ready(value: *mut c_void) -> *mut c_void;
+        }
+        let repr = value.repr;
+        mem::forget(value);
+        unsafe { __ready(repr) }
+    }
+    #[cfg(feature = "alloc")]
+    unsafe fn __poll(
+        this: *mut c_void,
+        waker: *mut c_void,
+        wake: unsafe extern "C" fn(*mut c_void),
+        value: *mut Self,
+        exception: *mut PtrLen,
+    ) -> u8 {
+        extern "C" {
+            #[link_name = "cxxbridge1$seastar$future$temporary_buffer$poll"]
+            fn __poll(
+                this: *mut c_void,
+                waker: *mut c_void,
+                wake: unsafe extern "C" fn(*mut c_void),
+                value: *mut SeastarTemporaryBuffer,
+                exception: *mut PtrLen,
+            ) -> u8;
+        }
+        unsafe { __poll(this, waker, wake, value, exception) }
+    }
+    #[cfg(feature = "std")]
+    unsafe fn __submit_to(
+        shard: u32,
+        data: *mut c_void,
+        call: unsafe extern "C" fn(*mut c_void, *mut Self, *mut PtrLen) -> bool,
+    ) -> *mut c_void {
+        extern "C" {
+            #[link_name = "cxxbridge1$seastar$future$temporary_buffer$submit_to"]
+            fn __submit_to(
+                shard: u32,
+                data: *mut c_void,
+                call: unsafe extern "C" fn(
+                    *mut c_void,
+                    *mut SeastarTemporaryBuffer,
+                    *mut PtrLen,
+                ) -> bool,
+            ) -> *mut c_void;
+        }
+        unsafe { __submit_to(shard, data, call) }
+    }
+    unsafe fn __available(this: *const c_void) -> bool {
+        extern "C" {
+            #[link_name = "cxxbridge1$seastar$future$temporary_buffer$available"]
+            fn __available(this: *const c_void) -> bool;
+        }
+        unsafe { __available(this) }
+    }
+    unsafe fn __drop(this: *mut c_void) {
+        extern "C" {
+            #[link_name = "cxxbridge1$seastar$future$temporary_buffer$drop"]
+            fn __drop(this: *mut c_void);
+        }
+        unsafe { __drop(this) }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::seastar_future::SeastarFuture;
+use core::convert::TryFrom;
+use core::ffi::c_void;
+use core::time::Duration;
+
+extern "C" {
+    #[link_name = "cxxbridge1$seastar$sleep"]
+    fn sleep(nanos: i64) -> *mut c_void;
+}
+
+/// Returns a future that resolves after `duration`, by way of C++
+/// `seastar::sleep`.
+///
+/// The duration is passed to the reactor's steady clock in nanoseconds, so a
+/// sub-millisecond duration is not rounded down to zero. A duration reaching
+/// past the last time point the clock can represent, such as
+/// `Duration::MAX`, sleeps until that time point instead, which in practice
+/// never comes. Like any seastar timer the future must be awaited on the shard
+/// that created it, under an executor driven by the seastar reactor.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), cxx::SeastarException> {
+/// use std::time::Duration;
+///
+/// cxx::seastar_sleep(Duration::from_micros(500)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn seastar_sleep(duration: Duration) -> SeastarFuture<()> {
+    let nanos = i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX);
+    unsafe { SeastarFuture::from_raw(sleep(nanos)) }
+}
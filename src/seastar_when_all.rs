@@ -0,0 +1,153 @@
+#![cfg(feature = "alloc")]
+
+use crate::seastar_exception::SeastarException;
+use crate::seastar_future::{SeastarFuture, SeastarFutureTarget};
+use core::fmt::{self, Debug};
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+enum MaybeDone<T>
+where
+    T: SeastarFutureTarget,
+{
+    Pending(SeastarFuture<T>),
+    Done(Result<T, SeastarException>),
+    Taken,
+}
+
+impl<T> MaybeDone<T>
+where
+    T: SeastarFutureTarget,
+{
+    fn is_ready(&self) -> bool {
+        match self {
+            MaybeDone::Pending(future) => future.is_ready(),
+            MaybeDone::Done(_) | MaybeDone::Taken => true,
+        }
+    }
+
+    // Returns whether the future has completed.
+    fn poll(&mut self, cx: &mut Context) -> bool {
+        if let MaybeDone::Pending(future) = self {
+            match Pin::new(future).poll(cx) {
+                Poll::Ready(result) => *self = MaybeDone::Done(result),
+                Poll::Pending => return false,
+            }
+        }
+        true
+    }
+
+    fn take(&mut self) -> Result<T, SeastarException> {
+        match mem::replace(self, MaybeDone::Taken) {
+            MaybeDone::Done(result) => result,
+            MaybeDone::Pending(_) | MaybeDone::Taken => unreachable!(),
+        }
+    }
+}
+
+macro_rules! when_all {
+    ($(#[$doc:meta])* $name:ident, $future:ident, $($T:ident $f:ident),+) => {
+        $(#[$doc])*
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        pub fn $name<$($T),+>($($f: SeastarFuture<$T>),+) -> $future<$($T),+>
+        where
+            $($T: SeastarFutureTarget,)+
+        {
+            $future {
+                $($f: MaybeDone::Pending($f),)+
+                done: false,
+            }
+        }
+
+        #[doc = concat!("Future returned by [`", stringify!($name), "`](crate::", stringify!($name), ").")]
+        ///
+        /// Resolves to a tuple holding the result of every future, in the
+        /// order they were passed in, once all of them have completed.
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        pub struct $future<$($T),+>
+        where
+            $($T: SeastarFutureTarget,)+
+        {
+            $($f: MaybeDone<$T>,)+
+            done: bool,
+        }
+
+        impl<$($T),+> $future<$($T),+>
+        where
+            $($T: SeastarFutureTarget,)+
+        {
+            /// Checks whether every future has completed, so that awaiting
+            /// would complete without waiting.
+            pub fn is_ready(&self) -> bool {
+                true $(&& self.$f.is_ready())+
+            }
+        }
+
+        impl<$($T),+> Future for $future<$($T),+>
+        where
+            $($T: SeastarFutureTarget + Unpin,)+
+        {
+            type Output = ($(Result<$T, SeastarException>,)+);
+
+            fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+                let this = self.get_mut();
+                assert!(
+                    !this.done,
+                    concat!(stringify!($future), " polled after completion"),
+                );
+                // Poll every future, so that each pending one registers the
+                // waker, before checking whether all have completed.
+                let mut ready = true;
+                $(ready &= this.$f.poll(cx);)+
+                if !ready {
+                    return Poll::Pending;
+                }
+                this.done = true;
+                Poll::Ready(($(this.$f.take(),)+))
+            }
+        }
+
+        impl<$($T),+> Debug for $future<$($T),+>
+        where
+            $($T: SeastarFutureTarget,)+
+        {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str(stringify!($future))
+            }
+        }
+    };
+}
+
+when_all! {
+    /// Waits for both futures to complete, resolving to both of their
+    /// results.
+    ///
+    /// This is a join written in Rust, which polls each future in turn from
+    /// the returned one, rather than a binding to seastar::when\_all. It
+    /// behaves the same way though: a future failing does not cut the wait
+    /// short, and each result is returned as it was, whether `Ok` or `Err`.
+    /// The futures may resolve to different types.
+    ///
+    /// ```no_run
+    /// # async fn example(
+    /// #     a: cxx::SeastarFuture<i32>,
+    /// #     b: cxx::SeastarFuture<bool>,
+    /// # ) -> Result<(), cxx::SeastarException> {
+    /// let (a, b) = cxx::seastar_when_all2(a, b).await;
+    /// println!("{} {}", a?, b?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    seastar_when_all2, WhenAll2, A a, B b
+}
+
+when_all! {
+    /// Waits for all three futures to complete, resolving to all of their
+    /// results.
+    ///
+    /// Like [`seastar_when_all2`](crate::seastar_when_all2), this is a join
+    /// written in Rust that behaves the same way as seastar::when\_all.
+    seastar_when_all3, WhenAll3, A a, B b, C c
+}
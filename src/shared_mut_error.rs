@@ -0,0 +1,27 @@
+use core::fmt::{self, Display};
+
+/// Error returned when mutating the object of a [`SeastarSharedPtr`] or
+/// [`SeastarLwSharedPtr`] that cannot be mutated in place.
+///
+/// [`SeastarSharedPtr`]: crate::SeastarSharedPtr
+/// [`SeastarLwSharedPtr`]: crate::SeastarLwSharedPtr
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SharedMutError {
+    /// The pointer does not own an object.
+    Null,
+    /// The object is also owned by other pointers.
+    Shared,
+}
+
+impl Display for SharedMutError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(match self {
+            SharedMutError::Null => "pointer is null",
+            SharedMutError::Shared => "pointer is not the unique owner of its object",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for SharedMutError {}
@@ -2,7 +2,7 @@ use crate::syntax::cfg::CfgExpr;
 use crate::syntax::namespace::Namespace;
 use crate::syntax::report::Errors;
 use crate::syntax::Atom::{self, *};
-use crate::syntax::{cfg, Derive, Doc, ForeignName};
+use crate::syntax::{cfg, Derive, Doc, ForeignName, SeastarUpcast};
 use proc_macro2::{Ident, TokenStream};
 use quote::ToTokens;
 use syn::parse::{Nothing, Parse, ParseStream, Parser as _};
@@ -36,6 +36,8 @@ pub struct Parser<'a> {
     pub cxx_name: Option<&'a mut Option<ForeignName>>,
     pub rust_name: Option<&'a mut Option<Ident>>,
     pub variants_from_header: Option<&'a mut Option<Attribute>>,
+    pub enable_shared_from_this: Option<&'a mut Option<Attribute>>,
+    pub seastar_upcast: Option<&'a mut Option<SeastarUpcast>>,
     pub ignore_unrecognized: bool,
 
     // Suppress clippy needless_update lint ("struct update has no effect, all
@@ -152,6 +154,27 @@ pub fn parse(cx: &mut Errors, attrs: Vec<Attribute>, mut parser: Parser) -> Othe
                 **variants_from_header = Some(attr);
                 continue;
             }
+        } else if attr.path.is_ident("enable_shared_from_this") {
+            if let Err(err) = Nothing::parse.parse2(attr.tokens.clone()) {
+                cx.push(err);
+            }
+            if let Some(enable_shared_from_this) = &mut parser.enable_shared_from_this {
+                **enable_shared_from_this = Some(attr);
+                continue;
+            }
+        } else if attr.path.is_ident("seastar_upcast") {
+            match attr.parse_args_with(parse_seastar_upcast_attribute) {
+                Ok(bases) => {
+                    if let Some(seastar_upcast) = &mut parser.seastar_upcast {
+                        **seastar_upcast = Some(SeastarUpcast { attr, bases });
+                        continue;
+                    }
+                }
+                Err(err) => {
+                    cx.push(err);
+                    break;
+                }
+            }
         } else if attr.path.is_ident("allow")
             || attr.path.is_ident("warn")
             || attr.path.is_ident("deny")
@@ -224,6 +247,14 @@ fn parse_derive_attribute(cx: &mut Errors, input: ParseStream) -> Result<Vec<Der
     Ok(derives)
 }
 
+fn parse_seastar_upcast_attribute(input: ParseStream) -> Result<Vec<Ident>> {
+    let bases = input.parse_terminated::<Ident, Token![,]>(Ident::parse)?;
+    if bases.is_empty() {
+        return Err(input.error("expected the base classes to upcast to"));
+    }
+    Ok(bases.into_iter().collect())
+}
+
 fn parse_repr_attribute(input: ParseStream) -> Result<Atom> {
     let begin = input.cursor();
     let ident: Ident = input.parse()?;
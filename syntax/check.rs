@@ -3,7 +3,8 @@ use crate::syntax::report::Errors;
 use crate::syntax::visit::{self, Visit};
 use crate::syntax::{
     error, ident, trivial, Api, Array, Enum, ExternFn, ExternType, Impl, Lang, Lifetimes,
-    NamedType, Ptr, Receiver, Ref, Signature, SliceRef, Struct, Trait, Ty1, Type, TypeAlias, Types,
+    NamedType, Ptr, Receiver, Ref, SeastarUpcast, Signature, SliceRef, Struct, Trait, Ty1, Type,
+    TypeAlias, Types,
 };
 use proc_macro2::{Delimiter, Group, Ident, TokenStream};
 use quote::{quote, ToTokens};
@@ -51,6 +52,8 @@ fn do_typecheck(cx: &mut Check) {
             Type::SharedPtr(ptr) => check_type_shared_ptr(cx, ptr),
             Type::SeastarLwSharedPtr(ptr) => check_type_seastar_lw_shared_ptr(cx, ptr),
             Type::SeastarSharedPtr(ptr) => check_type_seastar_shared_ptr(cx, ptr),
+            Type::SeastarFuture(ty) => check_type_seastar_future(cx, ty),
+            Type::SeastarCircularBuffer(ty) => check_type_seastar_circular_buffer(cx, ty),
             Type::WeakPtr(ptr) => check_type_weak_ptr(cx, ptr),
             Type::CxxVector(ptr) => check_type_cxx_vector(cx, ptr),
             Type::Ref(ty) => check_type_ref(cx, ty),
@@ -132,6 +135,14 @@ fn check_type_rust_vec(cx: &mut Check, ty: &Ty1) {
             }
         }
         Type::Str(_) => return,
+        Type::SeastarLwSharedPtr(ptr) => {
+            // The pointer itself is checked as a type of its own.
+            if let Type::Ident(ident) = &ptr.inner {
+                if Atom::from(&ident.rust).is_none() {
+                    return;
+                }
+            }
+        }
         _ => {}
     }
 
@@ -220,14 +231,55 @@ fn check_type_seastar_shared_ptr(cx: &mut Check, ptr: &Ty1) {
             | Some(F64) | Some(CxxString) => return,
             Some(Char) | Some(RustString) => {}
         }
-    } else if let Type::CxxVector(_) = &ptr.inner {
-        cx.error(ptr, "seastar::shared_ptr<std::vector> is not supported yet");
+    } else if let Type::CxxVector(ty) = &ptr.inner {
+        if let Type::Ident(inner) = &ty.inner {
+            match Atom::from(&inner.rust) {
+                Some(U8) | Some(U16) | Some(U32) | Some(U64) | Some(Usize) | Some(I8)
+                | Some(I16) | Some(I32) | Some(I64) | Some(Isize) | Some(F32) | Some(F64)
+                | Some(CxxString) => return,
+                None | Some(Bool) | Some(Char) | Some(RustString) => {}
+            }
+        }
+        cx.error(
+            ptr,
+            "seastar::shared_ptr<std::vector> is only supported for primitive element types so far",
+        );
         return;
     }
 
     cx.error(ptr, "unsupported seastar::shared_ptr target type");
 }
 
+fn check_type_seastar_future(cx: &mut Check, ty: &Ty1) {
+    if let Type::Ident(ident) = &ty.inner {
+        match Atom::from(&ident.rust) {
+            Some(Bool) | Some(U8) | Some(U16) | Some(U32) | Some(U64) | Some(Usize) | Some(I8)
+            | Some(I16) | Some(I32) | Some(I64) | Some(Isize) | Some(F32) | Some(F64) => return,
+            None | Some(Char) | Some(CxxString) | Some(RustString) => {}
+        }
+    }
+
+    cx.error(
+        ty,
+        "seastar::future is only supported for primitive value types so far",
+    );
+}
+
+fn check_type_seastar_circular_buffer(cx: &mut Check, ty: &Ty1) {
+    if let Type::Ident(ident) = &ty.inner {
+        match Atom::from(&ident.rust) {
+            Some(U8) | Some(U16) | Some(U32) | Some(U64) | Some(Usize) | Some(I8) | Some(I16)
+            | Some(I32) | Some(I64) | Some(Isize) | Some(F32) | Some(F64) => return,
+            None | Some(Bool) | Some(Char) | Some(CxxString) | Some(RustString) => {}
+        }
+    }
+
+    cx.error(
+        ty,
+        "seastar::circular_buffer is only supported for primitive numeric element types so far",
+    );
+}
+
 fn check_type_weak_ptr(cx: &mut Check, ptr: &Ty1) {
     if let Type::Ident(ident) = &ptr.inner {
         if cx.types.rust.contains(&ident.rust) {
@@ -278,6 +330,7 @@ fn check_type_ref(cx: &mut Check, ty: &Ref) {
                 Some(ident.rust.to_string())
             }
             Type::CxxVector(_) => Some("CxxVector<...>".to_owned()),
+            Type::SeastarCircularBuffer(_) => Some("SeastarCircularBuffer<...>".to_owned()),
             _ => None,
         } {
             cx.error(
@@ -391,6 +444,11 @@ fn check_api_struct(cx: &mut Check, strct: &Struct) {
                 field,
                 "function pointers in a struct field are not implemented yet",
             );
+        } else if let Type::SeastarFuture(_) = field.ty {
+            cx.error(
+                field,
+                "seastar::future in a struct field is not supported yet",
+            );
         } else if is_unsized(cx, &field.ty) {
             let desc = describe(cx, &field.ty);
             let msg = format!("using {} by value is not supported", desc);
@@ -444,6 +502,25 @@ fn check_api_type(cx: &mut Check, ety: &ExternType) {
         cx.error(span, "extern type bounds are not implemented yet");
     }
 
+    if let Some(attr) = &ety.enable_shared_from_this {
+        let ident = &ety.name.rust;
+        if ety.lang == Lang::Rust {
+            cx.error(
+                attr,
+                "enable_shared_from_this on a Rust type is not supported",
+            );
+        } else if cx.types.structs.contains_key(ident) || cx.types.enums.contains_key(ident) {
+            cx.error(
+                attr,
+                "enable_shared_from_this is only supported on opaque C++ types",
+            );
+        }
+    }
+
+    if let Some(upcast) = &ety.seastar_upcast {
+        check_seastar_upcast(cx, ety, upcast);
+    }
+
     if let Some(reasons) = cx.types.required_trivial.get(&ety.name.rust) {
         let msg = format!(
             "needs a cxx::ExternType impl in order to be used as {}",
@@ -524,6 +601,11 @@ fn check_api_fn(cx: &mut Check, efn: &ExternFn) {
                     "pointer argument requires that the function be marked unsafe",
                 );
             }
+        } else if let Type::SeastarFuture(_) = arg.ty {
+            cx.error(
+                arg,
+                "passing seastar::future as an argument is not supported yet",
+            );
         } else if is_unsized(cx, &arg.ty) {
             let desc = describe(cx, &arg.ty);
             let msg = format!("passing {} by value is not supported", desc);
@@ -534,6 +616,13 @@ fn check_api_fn(cx: &mut Check, efn: &ExternFn) {
     if let Some(ty) = &efn.ret {
         if let Type::Fn(_) = ty {
             cx.error(ty, "returning a function pointer is not implemented yet");
+        } else if let Type::SeastarFuture(_) = ty {
+            if efn.throws {
+                cx.error(
+                    ty,
+                    "returning seastar::future from a fallible function is not supported yet",
+                );
+            }
         } else if is_unsized(cx, ty) {
             let desc = describe(cx, ty);
             let msg = format!("returning {} by value is not supported", desc);
@@ -555,6 +644,41 @@ fn check_api_type_alias(cx: &mut Check, alias: &TypeAlias) {
     }
 }
 
+fn check_seastar_upcast(cx: &mut Check, ety: &ExternType, upcast: &SeastarUpcast) {
+    let ident = &ety.name.rust;
+    if ety.lang == Lang::Rust {
+        cx.error(
+            &upcast.attr,
+            "seastar_upcast on a Rust type is not supported",
+        );
+        return;
+    }
+    if !is_opaque_cxx_type(cx.types, ident) {
+        cx.error(
+            &upcast.attr,
+            "seastar_upcast is only supported on opaque C++ types",
+        );
+        return;
+    }
+    for base in &upcast.bases {
+        if base == ident {
+            cx.error(base, "seastar_upcast to the type itself is not supported");
+        } else if !is_opaque_cxx_type(cx.types, base) {
+            cx.error(
+                base,
+                "seastar_upcast base must be an opaque C++ type declared in the bridge",
+            );
+        }
+    }
+}
+
+fn is_opaque_cxx_type(types: &Types, ident: &Ident) -> bool {
+    types.cxx.contains(ident)
+        && !types.structs.contains_key(ident)
+        && !types.enums.contains_key(ident)
+        && !types.aliases.contains_key(ident)
+}
+
 fn check_api_impl(cx: &mut Check, imp: &Impl) {
     let ty = &imp.ty;
 
@@ -656,6 +780,8 @@ fn check_reserved_name(cx: &mut Check, ident: &Ident) {
         || ident == "SharedPtr"
         || ident == "SeastarLwSharedPtr"
         || ident == "SeastarSharedPtr"
+        || ident == "SeastarFuture"
+        || ident == "SeastarCircularBuffer"
         || ident == "WeakPtr"
         || ident == "Vec"
         || ident == "CxxVector"
@@ -698,13 +824,14 @@ fn is_unsized(cx: &mut Check, ty: &Type) -> bool {
             ident == CxxString || is_opaque_cxx(cx, ident) || cx.types.rust.contains(ident)
         }
         Type::Array(array) => is_unsized(cx, &array.inner),
-        Type::CxxVector(_) | Type::Fn(_) | Type::Void(_) => true,
+        Type::CxxVector(_) | Type::SeastarCircularBuffer(_) | Type::Fn(_) | Type::Void(_) => true,
         Type::RustBox(_)
         | Type::RustVec(_)
         | Type::UniquePtr(_)
         | Type::SharedPtr(_)
         | Type::SeastarLwSharedPtr(_)
         | Type::SeastarSharedPtr(_)
+        | Type::SeastarFuture(_)
         | Type::WeakPtr(_)
         | Type::Ref(_)
         | Type::Ptr(_)
@@ -781,6 +908,8 @@ fn describe(cx: &mut Check, ty: &Type) -> String {
         Type::SharedPtr(_) => "shared_ptr".to_owned(),
         Type::SeastarLwSharedPtr(_) => "lw_shared_ptr".to_owned(),
         Type::SeastarSharedPtr(_) => "shared_ptr".to_owned(),
+        Type::SeastarFuture(_) => "seastar::future".to_owned(),
+        Type::SeastarCircularBuffer(_) => "seastar::circular_buffer".to_owned(),
         Type::WeakPtr(_) => "weak_ptr".to_owned(),
         Type::Ref(_) => "reference".to_owned(),
         Type::Ptr(_) => "raw pointer".to_owned(),
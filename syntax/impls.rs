@@ -49,6 +49,8 @@ impl Hash for Type {
             Type::SharedPtr(t) => t.hash(state),
             Type::SeastarLwSharedPtr(t) => t.hash(state),
             Type::SeastarSharedPtr(t) => t.hash(state),
+            Type::SeastarFuture(t) => t.hash(state),
+            Type::SeastarCircularBuffer(t) => t.hash(state),
             Type::WeakPtr(t) => t.hash(state),
             Type::Ref(t) => t.hash(state),
             Type::Ptr(t) => t.hash(state),
@@ -74,6 +76,8 @@ impl PartialEq for Type {
             (Type::SharedPtr(lhs), Type::SharedPtr(rhs)) => lhs == rhs,
             (Type::SeastarLwSharedPtr(lhs), Type::SeastarLwSharedPtr(rhs)) => lhs == rhs,
             (Type::SeastarSharedPtr(lhs), Type::SeastarSharedPtr(rhs)) => lhs == rhs,
+            (Type::SeastarFuture(lhs), Type::SeastarFuture(rhs)) => lhs == rhs,
+            (Type::SeastarCircularBuffer(lhs), Type::SeastarCircularBuffer(rhs)) => lhs == rhs,
             (Type::WeakPtr(lhs), Type::WeakPtr(rhs)) => lhs == rhs,
             (Type::Ref(lhs), Type::Ref(rhs)) => lhs == rhs,
             (Type::Str(lhs), Type::Str(rhs)) => lhs == rhs,
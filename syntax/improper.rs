@@ -32,6 +32,8 @@ impl<'a> Types<'a> {
             | Type::SharedPtr(_)
             | Type::SeastarLwSharedPtr(_)
             | Type::SeastarSharedPtr(_)
+            | Type::SeastarFuture(_)
+            | Type::SeastarCircularBuffer(_)
             | Type::WeakPtr(_)
             | Type::CxxVector(_) => Definite(false),
             Type::Ref(ty) => self.determine_improper_ctype(&ty.inner),
@@ -7,6 +7,7 @@ use syn::Token;
 pub enum ImplKey<'a> {
     RustBox(NamedImplKey<'a>),
     RustVec(NamedImplKey<'a>),
+    RustVecSeastarLwSharedPtr(NamedImplKey<'a>),
     UniquePtr(NamedImplKey<'a>),
     SharedPtr(NamedImplKey<'a>),
     SeastarLwSharedPtr(NamedImplKey<'a>),
@@ -33,6 +34,12 @@ impl Type {
         } else if let Type::RustVec(ty) = self {
             if let Type::Ident(ident) = &ty.inner {
                 return Some(ImplKey::RustVec(NamedImplKey::new(ty, ident)));
+            } else if let Type::SeastarLwSharedPtr(ptr) = &ty.inner {
+                if let Type::Ident(ident) = &ptr.inner {
+                    return Some(ImplKey::RustVecSeastarLwSharedPtr(NamedImplKey::new(
+                        ty, ident,
+                    )));
+                }
             }
         } else if let Type::UniquePtr(ty) = self {
             if let Type::Ident(ident) = &ty.inner {
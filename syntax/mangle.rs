@@ -21,6 +21,8 @@
 //          examples:
 //             - cxxbridge1$box$org$rust$Struct$alloc
 //             - cxxbridge1$unique_ptr$std$vector$u8$drop
+//             - cxxbridge1$seastar_shared_ptr$foo$Bar$drop
+//             - cxxbridge1$seastar_lw_shared_ptr$foo$Bar$get
 //          defining characteristics:
 //             - 4+ segments
 //             - starts with cxxbridge
@@ -109,6 +111,31 @@ pub fn operator(receiver: &Pair, operator: &'static str) -> Symbol {
     )
 }
 
+// Prefix of the shims behind SeastarSharedPtr<T> and SeastarLwSharedPtr<T>
+// for a T declared in a bridge, followed by `$` and the shim name. PARAM is
+// the namespace and C++ name of T, so `foo::Bar` gives
+// cxxbridge1$seastar_shared_ptr$foo$Bar. Both the C++ definitions written by
+// cxx-build and the Rust declarations expanded by the bridge macro take
+// their link names from here, and must agree to link.
+//
+// The builtin targets instantiated by cxx.cc and by the seastar primitive
+// shims use cxxbridge1$seastar$shared_ptr$ and
+// cxxbridge1$seastar$lw_shared_ptr$ followed by the Rust name of the target
+// instead, so a bridged type cannot collide with one of them.
+pub fn seastar_shared_ptr(name: &Pair) -> Symbol {
+    join!(CXXBRIDGE, "seastar_shared_ptr", *name)
+}
+
+pub fn seastar_lw_shared_ptr(name: &Pair) -> Symbol {
+    join!(CXXBRIDGE, "seastar_lw_shared_ptr", *name)
+}
+
+// Converts a SeastarSharedPtr to a derived class into one to its base class,
+// e.g. cxxbridge1$seastar_shared_ptr$foo$Derived$upcast$foo$Base.
+pub fn seastar_upcast(derived: &Pair, base: &Pair) -> Symbol {
+    join!(seastar_shared_ptr(derived), "upcast", *base)
+}
+
 // The C half of a function pointer trampoline.
 pub fn c_trampoline(efn: &ExternFn, var: &Pair, types: &Types) -> Symbol {
     join!(extern_fn(efn, types), var.rust, 0)
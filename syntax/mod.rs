@@ -91,6 +91,13 @@ pub struct ExternType {
     pub bounds: Vec<Derive>,
     pub semi_token: Token![;],
     pub trusted: bool,
+    pub enable_shared_from_this: Option<Attribute>,
+    pub seastar_upcast: Option<SeastarUpcast>,
+}
+
+pub struct SeastarUpcast {
+    pub attr: Attribute,
+    pub bases: Vec<Ident>,
 }
 
 pub struct Struct {
@@ -231,6 +238,8 @@ pub enum Type {
     SharedPtr(Box<Ty1>),
     SeastarLwSharedPtr(Box<Ty1>),
     SeastarSharedPtr(Box<Ty1>),
+    SeastarFuture(Box<Ty1>),
+    SeastarCircularBuffer(Box<Ty1>),
     WeakPtr(Box<Ty1>),
     Ref(Box<Ref>),
     Ptr(Box<Ptr>),
@@ -471,6 +471,8 @@ fn parse_extern_type(
     let mut namespace = namespace.clone();
     let mut cxx_name = None;
     let mut rust_name = None;
+    let mut enable_shared_from_this = None;
+    let mut seastar_upcast = None;
     let mut attrs = attrs.clone();
     attrs.extend(attrs::parse(
         cx,
@@ -482,6 +484,8 @@ fn parse_extern_type(
             namespace: Some(&mut namespace),
             cxx_name: Some(&mut cxx_name),
             rust_name: Some(&mut rust_name),
+            enable_shared_from_this: Some(&mut enable_shared_from_this),
+            seastar_upcast: Some(&mut seastar_upcast),
             ..Default::default()
         },
     ));
@@ -515,6 +519,8 @@ fn parse_extern_type(
         bounds,
         semi_token,
         trusted,
+        enable_shared_from_this,
+        seastar_upcast,
     })
 }
 
@@ -951,6 +957,8 @@ fn parse_extern_type_bounded(
     let mut namespace = namespace.clone();
     let mut cxx_name = None;
     let mut rust_name = None;
+    let mut enable_shared_from_this = None;
+    let mut seastar_upcast = None;
     let mut attrs = attrs.clone();
     attrs.extend(attrs::parse(
         cx,
@@ -962,6 +970,8 @@ fn parse_extern_type_bounded(
             namespace: Some(&mut namespace),
             cxx_name: Some(&mut cxx_name),
             rust_name: Some(&mut rust_name),
+            enable_shared_from_this: Some(&mut enable_shared_from_this),
+            seastar_upcast: Some(&mut seastar_upcast),
             ..Default::default()
         },
     ));
@@ -986,6 +996,8 @@ fn parse_extern_type_bounded(
         bounds,
         semi_token,
         trusted,
+        enable_shared_from_this,
+        seastar_upcast,
     }))
 }
 
@@ -1076,6 +1088,8 @@ fn parse_impl(cx: &mut Errors, imp: ItemImpl) -> Result<Api> {
             _ => Lifetimes::default(),
         },
         Type::Ident(_)
+        | Type::SeastarFuture(_)
+        | Type::SeastarCircularBuffer(_)
         | Type::Ref(_)
         | Type::Ptr(_)
         | Type::Str(_)
@@ -1266,6 +1280,26 @@ fn parse_type_path(ty: &TypePath) -> Result<Type> {
                             rangle: generic.gt_token,
                         })));
                     }
+                } else if ident == "SeastarFuture" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::SeastarFuture(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
+                } else if ident == "SeastarCircularBuffer" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::SeastarCircularBuffer(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
                 } else if ident == "WeakPtr" && generic.args.len() == 1 {
                     if let GenericArgument::Type(arg) = &generic.args[0] {
                         let inner = parse_type(arg)?;
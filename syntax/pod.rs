@@ -28,6 +28,8 @@ impl<'a> Types<'a> {
             | Type::SharedPtr(_)
             | Type::SeastarLwSharedPtr(_)
             | Type::SeastarSharedPtr(_)
+            | Type::SeastarFuture(_)
+            | Type::SeastarCircularBuffer(_)
             | Type::WeakPtr(_)
             | Type::CxxVector(_)
             | Type::Void(_) => false,
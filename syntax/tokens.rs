@@ -28,6 +28,8 @@ impl ToTokens for Type {
             | Type::SharedPtr(ty)
             | Type::SeastarLwSharedPtr(ty)
             | Type::SeastarSharedPtr(ty)
+            | Type::SeastarFuture(ty)
+            | Type::SeastarCircularBuffer(ty)
             | Type::WeakPtr(ty)
             | Type::CxxVector(ty)
             | Type::RustVec(ty) => ty.to_tokens(tokens),
@@ -68,7 +70,14 @@ impl ToTokens for Ty1 {
         } = self;
         let span = name.span();
         match name.to_string().as_str() {
-            "UniquePtr" | "SeastarLwSharedPtr" | "SeastarSharedPtr" | "SharedPtr" | "WeakPtr" | "CxxVector" => {
+            "UniquePtr"
+            | "SeastarLwSharedPtr"
+            | "SeastarSharedPtr"
+            | "SeastarFuture"
+            | "SeastarCircularBuffer"
+            | "SharedPtr"
+            | "WeakPtr"
+            | "CxxVector" => {
                 tokens.extend(quote_spanned!(span=> ::cxx::));
             }
             "Box" => {
@@ -1,5 +1,5 @@
 use crate::syntax::improper::ImproperCtype;
-use crate::syntax::instantiate::ImplKey;
+use crate::syntax::instantiate::{ImplKey, NamedImplKey};
 use crate::syntax::map::{OrderedMap, UnorderedMap};
 use crate::syntax::report::Errors;
 use crate::syntax::resolve::Resolution;
@@ -11,6 +11,7 @@ use crate::syntax::{
 };
 use proc_macro2::Ident;
 use quote::ToTokens;
+use std::iter;
 
 pub struct Types<'a> {
     pub all: OrderedSet<&'a Type>,
@@ -20,6 +21,8 @@ pub struct Types<'a> {
     pub rust: UnorderedSet<&'a Ident>,
     pub aliases: UnorderedMap<&'a Ident, &'a TypeAlias>,
     pub untrusted: UnorderedMap<&'a Ident, &'a ExternType>,
+    pub shared_from_this: UnorderedSet<&'a Ident>,
+    pub seastar_upcasts: UnorderedMap<&'a Ident, &'a [Ident]>,
     pub required_trivial: UnorderedMap<&'a Ident, Vec<TrivialReason<'a>>>,
     pub impls: OrderedMap<ImplKey<'a>, Option<&'a Impl>>,
     pub resolutions: UnorderedMap<&'a Ident, Resolution<'a>>,
@@ -36,6 +39,10 @@ impl<'a> Types<'a> {
         let mut rust = UnorderedSet::new();
         let mut aliases = UnorderedMap::new();
         let mut untrusted = UnorderedMap::new();
+        let mut shared_from_this = UnorderedSet::new();
+        let mut shared_from_this_types = Vec::new();
+        let mut seastar_upcasts = UnorderedMap::new();
+        let mut seastar_upcast_types = Vec::new();
         let mut impls = OrderedMap::new();
         let mut resolutions = UnorderedMap::new();
         let struct_improper_ctypes = UnorderedSet::new();
@@ -129,6 +136,14 @@ impl<'a> Types<'a> {
                     if !ety.trusted {
                         untrusted.insert(ident, ety);
                     }
+                    if ety.enable_shared_from_this.is_some() {
+                        shared_from_this.insert(ident);
+                        shared_from_this_types.push(ety);
+                    }
+                    if let Some(upcast) = &ety.seastar_upcast {
+                        seastar_upcasts.insert(ident, upcast.bases.as_slice());
+                        seastar_upcast_types.push(ety);
+                    }
                     add_resolution(&ety.name, &ety.generics);
                 }
                 Api::RustType(ety) => {
@@ -178,6 +193,7 @@ impl<'a> Types<'a> {
             let implicit_impl = match impl_key {
                 ImplKey::RustBox(ident)
                 | ImplKey::RustVec(ident)
+                | ImplKey::RustVecSeastarLwSharedPtr(ident)
                 | ImplKey::UniquePtr(ident)
                 | ImplKey::SharedPtr(ident)
                 | ImplKey::SeastarLwSharedPtr(ident)
@@ -192,6 +208,48 @@ impl<'a> Types<'a> {
             }
         }
 
+        // The shared_from_this shim returns a seastar::shared_ptr, whose
+        // bindings are needed even if the bridge never names the pointer type.
+        for ety in shared_from_this_types {
+            let impl_key = ImplKey::SeastarSharedPtr(NamedImplKey {
+                begin_span: ety.name.rust.span(),
+                rust: &ety.name.rust,
+                lt_token: ety.generics.lt_token,
+                gt_token: ety.generics.gt_token,
+                end_span: ety.semi_token.span,
+            });
+            if !impls.contains_key(&impl_key) {
+                impls.insert(impl_key, None);
+            }
+        }
+
+        // Likewise the upcast shims convert between the seastar::shared_ptr of
+        // the derived class and those of its bases.
+        for ety in seastar_upcast_types {
+            let upcast = ety.seastar_upcast.as_ref().unwrap();
+            let derived = ImplKey::SeastarSharedPtr(NamedImplKey {
+                begin_span: ety.name.rust.span(),
+                rust: &ety.name.rust,
+                lt_token: ety.generics.lt_token,
+                gt_token: ety.generics.gt_token,
+                end_span: ety.semi_token.span,
+            });
+            let bases = upcast.bases.iter().map(|base| {
+                ImplKey::SeastarSharedPtr(NamedImplKey {
+                    begin_span: base.span(),
+                    rust: base,
+                    lt_token: None,
+                    gt_token: None,
+                    end_span: base.span(),
+                })
+            });
+            for impl_key in iter::once(derived).chain(bases) {
+                if !impls.contains_key(&impl_key) {
+                    impls.insert(impl_key, None);
+                }
+            }
+        }
+
         // All these APIs may contain types passed by value. We need to ensure
         // we check that this is permissible. We do this _after_ scanning all
         // the APIs above, in case some function or struct references a type
@@ -207,6 +265,8 @@ impl<'a> Types<'a> {
             rust,
             aliases,
             untrusted,
+            shared_from_this,
+            seastar_upcasts,
             required_trivial,
             impls,
             resolutions,
@@ -245,7 +305,7 @@ impl<'a> Types<'a> {
 
     pub fn needs_indirect_abi(&self, ty: &Type) -> bool {
         match ty {
-            Type::RustBox(_) | Type::UniquePtr(_) => false,
+            Type::RustBox(_) | Type::UniquePtr(_) | Type::SeastarFuture(_) => false,
             Type::Array(_) => true,
             _ => !self.is_guaranteed_pod(ty),
         }
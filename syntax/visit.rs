@@ -17,6 +17,8 @@ where
         | Type::SharedPtr(ty)
         | Type::SeastarLwSharedPtr(ty)
         | Type::SeastarSharedPtr(ty)
+        | Type::SeastarFuture(ty)
+        | Type::SeastarCircularBuffer(ty)
         | Type::WeakPtr(ty)
         | Type::CxxVector(ty)
         | Type::RustVec(ty) => visitor.visit_type(&ty.inner),
@@ -0,0 +1,56 @@
+// Helpers for polling futures from the integration tests, which have no
+// executor of their own. Each test file picks the ones it needs.
+#![allow(dead_code)]
+
+use cxx_test_suite::ffi;
+use std::future::Future;
+use std::pin::Pin;
+use std::ptr;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+// Seastar futures do not call the waker until a reactor runs their
+// continuations, and the tests poll again themselves, so it does nothing.
+pub fn noop_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(ptr::null(), &VTABLE),
+        |_| {},
+        |_| {},
+        |_| {},
+    );
+    unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+}
+
+pub fn poll_once<F>(mut future: F) -> Poll<F::Output>
+where
+    F: Future + Unpin,
+{
+    let waker = noop_waker();
+    Pin::new(&mut future).poll(&mut Context::from_waker(&waker))
+}
+
+// Only polls futures that are already ready: polling a pending one chains a
+// continuation that needs a running seastar reactor.
+pub fn poll_ready<F>(future: F) -> F::Output
+where
+    F: Future + Unpin,
+{
+    match poll_once(future) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future is pending"),
+    }
+}
+
+// Polls the future on a seastar thread, yielding to the reactor in between so
+// that whatever it waits for can happen. Only usable inside
+// ffi::c_run_seastar_thread.
+pub fn wait<F>(mut future: F) -> F::Output
+where
+    F: Future + Unpin,
+{
+    loop {
+        match poll_once(&mut future) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => ffi::c_seastar_thread_yield(),
+        }
+    }
+}
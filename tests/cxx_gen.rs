@@ -32,3 +32,180 @@ fn test_impl_annotation() {
     let output = str::from_utf8(&generated.implementation).unwrap();
     assert!(output.contains("ANNOTATION void cxxbridge1$do_cpp_thing(::rust::Str foo)"));
 }
+
+const BRIDGE1: &str = r#"
+    #[cxx::bridge]
+    mod ffi {
+        struct Holder {
+            lw: SeastarLwSharedPtr<C>,
+            shared: SeastarSharedPtr<C>,
+        }
+
+        extern "C++" {
+            type C;
+        }
+    }
+"#;
+
+#[test]
+fn test_seastar_ptr_struct_field_layout() {
+    let opt = Opt::default();
+    let source = BRIDGE1.parse().unwrap();
+    let generated = generate_header_and_cc(source, &opt).unwrap();
+    let output = str::from_utf8(&generated.header).unwrap();
+    assert!(
+        output.contains("static_assert(sizeof(::seastar::lw_shared_ptr<::C>) == sizeof(void *), ")
+    );
+    assert!(
+        output.contains("static_assert(sizeof(::seastar::shared_ptr<::C>) == 2 * sizeof(void *), ")
+    );
+}
+
+const BRIDGE2: &str = r#"
+    #[cxx::bridge]
+    mod ffi {
+        unsafe extern "C++" {
+            fn c_take_seastar_shared_ptr(ptr: SeastarSharedPtr<i32>);
+        }
+    }
+"#;
+
+#[test]
+fn test_seastar_primitive_shims_on_demand() {
+    let opt = Opt::default();
+    let source = BRIDGE2.parse().unwrap();
+    let generated = generate_header_and_cc(source, &opt).unwrap();
+    let output = str::from_utf8(&generated.implementation).unwrap();
+    assert!(output.contains(
+        "__attribute__((weak)) void cxxbridge1$seastar$shared_ptr$i32$null(::seastar::shared_ptr<::std::int32_t> *ptr) noexcept {"
+    ));
+    assert!(!output.contains("cxxbridge1$seastar$lw_shared_ptr$"));
+    assert!(!output.contains("cxxbridge1$seastar$shared_ptr$u8$"));
+}
+
+const BRIDGE3: &str = r#"
+    #[cxx::bridge]
+    mod ffi {
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        unsafe extern "C++" {
+            fn c_swap_point(point: SeastarLwSharedPtr<Point>) -> SeastarLwSharedPtr<Point>;
+        }
+    }
+"#;
+
+#[test]
+fn test_seastar_lw_shared_ptr_shared_struct() {
+    let opt = Opt::default();
+    let source = BRIDGE3.parse().unwrap();
+    let generated = generate_header_and_cc(source, &opt).unwrap();
+    let output = str::from_utf8(&generated.implementation).unwrap();
+    assert!(output.contains(
+        "::Point *cxxbridge1$seastar_lw_shared_ptr$Point$uninit(::seastar::lw_shared_ptr<::Point> *ptr) noexcept {"
+    ));
+}
+
+const BRIDGE4: &str = r#"
+    #[cxx::bridge(namespace = "foo")]
+    mod ffi {
+        unsafe extern "C++" {
+            type Bar;
+
+            fn c_take_bar(lw: SeastarLwSharedPtr<Bar>, shared: SeastarSharedPtr<Bar>);
+        }
+    }
+"#;
+
+#[test]
+fn test_seastar_ptr_namespaced_link_names() {
+    let opt = Opt::default();
+    let source = BRIDGE4.parse().unwrap();
+    let generated = generate_header_and_cc(source, &opt).unwrap();
+    let output = str::from_utf8(&generated.implementation).unwrap();
+    assert!(output.contains(
+        "void cxxbridge1$seastar_shared_ptr$foo$Bar$null(::seastar::shared_ptr<::foo::Bar> *ptr) noexcept {"
+    ));
+    assert!(output.contains(
+        "void cxxbridge1$seastar_lw_shared_ptr$foo$Bar$drop(::seastar::lw_shared_ptr<::foo::Bar> *self) noexcept {"
+    ));
+    assert!(!output.contains("cxxbridge1$shared_ptr$foo$Bar$"));
+}
+
+const BRIDGE5: &str = r#"
+    #[cxx::bridge(namespace = "foo")]
+    mod ffi {
+        unsafe extern "C++" {
+            type Bar;
+
+            fn c_return_bars() -> Vec<SeastarLwSharedPtr<Bar>>;
+        }
+    }
+"#;
+
+#[test]
+fn test_seastar_lw_shared_ptr_rust_vec() {
+    let opt = Opt::default();
+    let source = BRIDGE5.parse().unwrap();
+    let generated = generate_header_and_cc(source, &opt).unwrap();
+    let output = str::from_utf8(&generated.implementation).unwrap();
+    assert!(output.contains(
+        "void cxxbridge1$rust_vec$seastar_lw_shared_ptr$foo$Bar$drop(::rust::Vec<::seastar::lw_shared_ptr<::foo::Bar>> *ptr) noexcept;"
+    ));
+    assert!(output.contains("Vec<::seastar::lw_shared_ptr<::foo::Bar>>::Vec() noexcept {"));
+}
+
+const BRIDGE6: &str = r#"
+    #[cxx::bridge(namespace = "foo")]
+    mod ffi {
+        unsafe extern "C++" {
+            #[enable_shared_from_this]
+            type Node;
+        }
+    }
+"#;
+
+#[test]
+fn test_seastar_shared_from_this() {
+    let opt = Opt::default();
+    let source = BRIDGE6.parse().unwrap();
+    let generated = generate_header_and_cc(source, &opt).unwrap();
+    let output = str::from_utf8(&generated.implementation).unwrap();
+    assert!(output.contains(
+        "void cxxbridge1$seastar_shared_ptr$foo$Node$shared_from_this(::foo::Node const &self, ::seastar::shared_ptr<::foo::Node> *ptr) noexcept {"
+    ));
+    assert!(output.contains(
+        "void cxxbridge1$seastar_shared_ptr$foo$Node$drop(::seastar::shared_ptr<::foo::Node> *self) noexcept {"
+    ));
+}
+
+const BRIDGE7: &str = r#"
+    #[cxx::bridge(namespace = "foo")]
+    mod ffi {
+        unsafe extern "C++" {
+            type Base;
+            #[seastar_upcast(Base)]
+            type Derived;
+        }
+    }
+"#;
+
+#[test]
+fn test_seastar_upcast() {
+    let opt = Opt::default();
+    let source = BRIDGE7.parse().unwrap();
+    let generated = generate_header_and_cc(source, &opt).unwrap();
+    let output = str::from_utf8(&generated.implementation).unwrap();
+    assert!(output.contains(
+        "void cxxbridge1$seastar_shared_ptr$foo$Derived$upcast$foo$Base(::seastar::shared_ptr<::foo::Derived> *self, ::seastar::shared_ptr<::foo::Base> *ptr) noexcept {"
+    ));
+    // Both pointer types get their bindings without being named in the bridge.
+    assert!(output.contains(
+        "void cxxbridge1$seastar_shared_ptr$foo$Derived$drop(::seastar::shared_ptr<::foo::Derived> *self) noexcept {"
+    ));
+    assert!(output.contains(
+        "void cxxbridge1$seastar_shared_ptr$foo$Base$drop(::seastar::shared_ptr<::foo::Base> *self) noexcept {"
+    ));
+}
@@ -1,6 +1,9 @@
 use cxx_build::CFG;
+use std::env;
 
 fn main() {
+    prepend_seastar_pkg_config_path();
+
     let seastar = pkg_config::Config::new()
         .statik(true)
         .probe("seastar")
@@ -40,3 +43,19 @@ fn main() {
     println!("cargo:rerun-if-changed=tests.cc");
     println!("cargo:rerun-if-changed=tests.h");
 }
+
+// Same as in the cxx crate's build script, so that the test suite finds the
+// seastar that cxx itself was built against.
+fn prepend_seastar_pkg_config_path() {
+    println!("cargo:rerun-if-env-changed=SEASTAR_PKG_CONFIG_PATH");
+    let seastar_path = match env::var_os("SEASTAR_PKG_CONFIG_PATH") {
+        Some(path) if !path.is_empty() => path,
+        _ => return,
+    };
+    let mut paths = env::split_paths(&seastar_path).collect::<Vec<_>>();
+    if let Some(path) = env::var_os("PKG_CONFIG_PATH") {
+        paths.extend(env::split_paths(&path));
+    }
+    let path = env::join_paths(paths).expect("invalid SEASTAR_PKG_CONFIG_PATH");
+    env::set_var("PKG_CONFIG_PATH", path);
+}
@@ -15,7 +15,8 @@
 pub mod cast;
 pub mod module;
 
-use cxx::{type_id, CxxString, CxxVector, ExternType, SeastarSharedPtr, SeastarLwSharedPtr ,SharedPtr, UniquePtr};
+use cxx::memory::SeastarGraphNode;
+use cxx::{type_id, CxxString, CxxVector, ExternType, SeastarFuture, SeastarSharedPtr, SeastarLwSharedPtr ,SharedPtr, UniquePtr};
 use std::fmt::{self, Display};
 use std::mem::MaybeUninit;
 use std::os::raw::c_char;
@@ -27,6 +28,12 @@ pub mod ffi {
         z: usize,
     }
 
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
     #[derive(PartialEq, PartialOrd)]
     struct SharedString {
         msg: String,
@@ -84,6 +91,11 @@ pub mod ffi {
         b: Buffer,
     }
 
+    pub struct LargeStruct {
+        data: [u64; 1024],
+        tag: u32,
+    }
+
     #[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
     pub struct StructWithLifetime<'a> {
         s: &'a str,
@@ -101,6 +113,12 @@ pub mod ffi {
         fn c_return_shared_ptr() -> SharedPtr<C>;
         fn c_return_seastar_lw_shared_ptr() -> SeastarLwSharedPtr<C>;
         fn c_return_seastar_shared_ptr() -> SeastarSharedPtr<C>;
+        fn c_return_seastar_shared_ptr_vector_u8() -> SeastarSharedPtr<CxxVector<u8>>;
+        fn c_return_seastar_future() -> SeastarFuture<i32>;
+        fn c_return_seastar_exception_future() -> SeastarFuture<i32>;
+        fn c_run_seastar_thread(main: fn()) -> i32;
+        fn c_seastar_thread_yield();
+        fn c_swap_seastar_point(point: SeastarLwSharedPtr<Point>) -> SeastarLwSharedPtr<Point>;
         fn c_return_ref(shared: &Shared) -> &usize;
         fn c_return_mut(shared: &mut Shared) -> &mut usize;
         fn c_return_str(shared: &Shared) -> &str;
@@ -116,6 +134,7 @@ pub mod ffi {
         fn c_return_unique_ptr_vector_opaque() -> UniquePtr<CxxVector<C>>;
         fn c_return_ref_vector(c: &C) -> &CxxVector<u8>;
         fn c_return_mut_vector(c: Pin<&mut C>) -> Pin<&mut CxxVector<u8>>;
+        fn c_return_ref_circular_buffer() -> &'static SeastarCircularBuffer<i32>;
         fn c_return_rust_vec_u8() -> Vec<u8>;
         fn c_return_ref_rust_vec(c: &C) -> &Vec<u8>;
         fn c_return_mut_rust_vec(c: Pin<&mut C>) -> &mut Vec<u8>;
@@ -178,6 +197,7 @@ pub mod ffi {
         fn c_take_rust_vec_nested_ns_shared(v: Vec<ABShared>);
         unsafe fn c_take_const_ptr(c: *const C) -> usize;
         unsafe fn c_take_mut_ptr(c: *mut C) -> usize;
+        unsafe fn c_emplace_c(storage: *mut C, n: usize);
 
         fn c_try_return_void() -> Result<()>;
         fn c_try_return_primitive() -> Result<usize>;
@@ -196,6 +216,8 @@ pub mod ffi {
         fn get(self: &C) -> usize;
         fn set(self: Pin<&mut C>, n: usize) -> usize;
         fn get2(&self) -> usize;
+        #[rust_name = "get_pinned"]
+        fn get(self: Pin<&C>) -> usize;
         fn getRef(self: &C) -> &usize;
         fn getMut(self: Pin<&mut C>) -> &mut usize;
         fn set_succeed(self: Pin<&mut C>, n: usize) -> Result<usize>;
@@ -220,6 +242,67 @@ pub mod ffi {
         fn ns_c_take_ns_shared(shared: AShared);
     }
 
+    unsafe extern "C++" {
+        type Base;
+        type Derived;
+
+        fn c_return_seastar_shared_ptr_derived() -> SeastarSharedPtr<Base>;
+        fn get_base(self: &Base) -> usize;
+        fn get_derived(self: &Derived) -> usize;
+    }
+
+    unsafe extern "C++" {
+        type GraphNode;
+
+        fn c_new_graph_node(value: u32) -> SeastarSharedPtr<GraphNode>;
+        fn get_value(self: &GraphNode) -> u32;
+        fn child_count(self: &GraphNode) -> usize;
+        fn get_child(self: &GraphNode, i: usize) -> SeastarSharedPtr<GraphNode>;
+        fn add_child(self: &GraphNode, child: SeastarSharedPtr<GraphNode>);
+        fn clear_children(self: &GraphNode);
+    }
+
+    unsafe extern "C++" {
+        type DropCounter;
+
+        fn get_id(self: &DropCounter) -> usize;
+        unsafe fn c_return_seastar_lw_shared_ptr_vec(
+            drops: *mut usize,
+        ) -> Vec<SeastarLwSharedPtr<DropCounter>>;
+        unsafe fn c_emplace_drop_counter(storage: *mut DropCounter, drops: *mut usize);
+
+        #[enable_shared_from_this]
+        type SelfRef;
+
+        fn c_new_self_ref(value: usize) -> SeastarSharedPtr<SelfRef>;
+        fn get_value(self: &SelfRef) -> usize;
+
+        type UpcastBase;
+        #[seastar_upcast(UpcastBase)]
+        type UpcastMiddle;
+        #[seastar_upcast(UpcastMiddle, UpcastBase)]
+        type UpcastDerived;
+
+        fn c_new_upcast_derived(value: usize) -> SeastarSharedPtr<UpcastDerived>;
+        fn get_base_value(self: &UpcastBase) -> usize;
+        fn get_middle_value(self: &UpcastMiddle) -> usize;
+    }
+
+    #[namespace = "seastar"]
+    extern "C++" {
+        type stop_iteration = crate::StopIteration;
+    }
+
+    unsafe extern "C++" {
+        fn c_negate_stop_iteration(stop: stop_iteration) -> stop_iteration;
+    }
+
+    unsafe extern "C++" {
+        type Conn = crate::Conn;
+
+        fn c_return_conn_with(conn: Conn, n: usize) -> Conn;
+    }
+
     extern "C++" {
         include!("tests/ffi/module.rs.h");
 
@@ -267,6 +350,7 @@ pub mod ffi {
         fn r_return_shared_ptr() -> SharedPtr<C>;
         fn r_return_seastar_lw_shared_ptr() -> SeastarLwSharedPtr<C>;
         fn r_return_seastar_shared_ptr() -> SeastarSharedPtr<C>;
+        fn r_return_seastar_future() -> SeastarFuture<i32>;
         fn r_return_ref(shared: &Shared) -> &usize;
         fn r_return_mut(shared: &mut Shared) -> &mut usize;
         fn r_return_str(shared: &Shared) -> &str;
@@ -290,6 +374,7 @@ pub mod ffi {
         fn r_take_shared_ptr(c: SharedPtr<C>);
         fn r_take_seastar_lw_shared_ptr(c: SeastarLwSharedPtr<C>);
         fn r_take_seastar_shared_ptr(c: SeastarSharedPtr<C>);
+        fn r_take_seastar_shared_ptr_drop_counter(c: SeastarSharedPtr<DropCounter>);
         fn r_take_ref_r(r: &R);
         fn r_take_ref_c(c: &C);
         fn r_take_str(s: &str);
@@ -342,6 +427,8 @@ pub mod ffi {
     }
 
     impl Box<Shared> {}
+    impl SeastarSharedPtr<Derived> {}
+    impl SeastarLwSharedPtr<LargeStruct> {}
     impl CxxVector<SharedString> {}
 }
 
@@ -434,6 +521,38 @@ unsafe impl ExternType for Buffer {
     type Kind = cxx::kind::Trivial;
 }
 
+impl SeastarGraphNode for ffi::GraphNode {
+    type Value = u32;
+
+    fn value(&self) -> u32 {
+        self.get_value()
+    }
+
+    fn children(&self) -> Vec<SeastarSharedPtr<Self>> {
+        (0..self.child_count()).map(|i| self.get_child(i)).collect()
+    }
+
+    fn new_node(value: u32) -> SeastarSharedPtr<Self> {
+        ffi::c_new_graph_node(value)
+    }
+
+    fn push_child(node: &SeastarSharedPtr<Self>, child: SeastarSharedPtr<Self>) {
+        node.add_child(child);
+    }
+}
+
+#[repr(transparent)]
+pub struct Conn(pub SeastarLwSharedPtr<ffi::C>);
+
+unsafe impl ExternType for Conn {
+    type Id = type_id!("tests::Conn");
+    type Kind = cxx::kind::Relocatable;
+}
+
+cxx::seastar_bool_class! {
+    pub struct StopIteration = "seastar::stop_iteration";
+}
+
 #[derive(Debug)]
 struct Error;
 
@@ -500,6 +619,10 @@ fn r_return_seastar_shared_ptr() -> SeastarSharedPtr<ffi::C> {
     }
 }
 
+fn r_return_seastar_future() -> SeastarFuture<i32> {
+    SeastarFuture::ready(2020)
+}
+
 fn r_return_ref(shared: &ffi::Shared) -> &usize {
     &shared.z
 }
@@ -601,6 +724,10 @@ fn r_take_seastar_shared_ptr(c: SeastarSharedPtr<ffi::C>) {
     let _ = c;
 }
 
+fn r_take_seastar_shared_ptr_drop_counter(c: SeastarSharedPtr<ffi::DropCounter>) {
+    assert!(!c.is_null());
+}
+
 fn r_take_ref_r(r: &R) {
     let _ = r;
 }
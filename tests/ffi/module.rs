@@ -46,6 +46,7 @@ pub mod ffi2 {
         fn c_take_opaque_ref_method(self: &E);
         fn c_take_opaque_mut_ref_method(self: Pin<&mut E>);
         fn c_take_opaque_ns_ptr(e: UniquePtr<F>);
+        fn c_take_seastar_shared_ptr_opaque(e: SeastarSharedPtr<E>);
         fn c_take_opaque_ns_ref(e: &F);
         fn c_return_trivial_ptr() -> UniquePtr<D>;
         fn c_return_trivial() -> D;
@@ -53,6 +54,8 @@ pub mod ffi2 {
         fn c_return_trivial_ns() -> G;
         fn c_return_opaque_ptr() -> UniquePtr<E>;
         fn c_return_opaque_mut_pin(e: Pin<&mut E>) -> Pin<&mut E>;
+        fn c_return_seastar_shared_ptr_opaque() -> SeastarSharedPtr<E>;
+        fn c_return_seastar_lw_shared_ptr_opaque() -> SeastarLwSharedPtr<E>;
         fn c_return_ns_opaque_ptr() -> UniquePtr<F>;
         fn c_return_ns_unique_ptr() -> UniquePtr<H>;
         fn c_take_ref_ns_c(h: &H);
@@ -75,4 +78,6 @@ pub mod ffi2 {
     impl UniquePtr<E> {}
     impl UniquePtr<F> {}
     impl UniquePtr<G> {}
+    impl SeastarSharedPtr<E> {}
+    impl SeastarLwSharedPtr<E> {}
 }
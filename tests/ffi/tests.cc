@@ -5,6 +5,8 @@
 #include <iterator>
 #include <memory>
 #include <numeric>
+#include <seastar/core/app-template.hh>
+#include <seastar/core/thread.hh>
 #include <stdexcept>
 #include <string>
 #include <tuple>
@@ -36,6 +38,56 @@ size_t C::set_succeed(size_t n) { return this->set(n); }
 
 size_t C::get_fail() { throw std::runtime_error("unimplemented"); }
 
+Base::Base(size_t n) : n(n) {}
+
+size_t Base::get_base() const { return this->n; }
+
+Derived::Derived(size_t n, size_t m) : Base(n), m(m) {}
+
+size_t Derived::get_derived() const { return this->m; }
+
+GraphNode::GraphNode(uint32_t value) : value(value) {}
+
+uint32_t GraphNode::get_value() const { return this->value; }
+
+size_t GraphNode::child_count() const { return this->children.size(); }
+
+seastar::shared_ptr<GraphNode> GraphNode::get_child(size_t i) const {
+  return this->children.at(i);
+}
+
+void GraphNode::add_child(seastar::shared_ptr<GraphNode> child) const {
+  this->children.push_back(std::move(child));
+}
+
+void GraphNode::clear_children() const { this->children.clear(); }
+
+DropCounter::DropCounter(size_t *drops, size_t id) : drops(drops), id(id) {}
+
+DropCounter::~DropCounter() { ++*this->drops; }
+
+size_t DropCounter::get_id() const { return this->id; }
+
+SelfRef::SelfRef(size_t value) : value(value) {}
+
+size_t SelfRef::get_value() const { return this->value; }
+
+UpcastOther::~UpcastOther() = default;
+
+UpcastBase::UpcastBase(size_t value) : value(value) {}
+
+UpcastBase::~UpcastBase() = default;
+
+size_t UpcastBase::get_base_value() const { return this->value; }
+
+UpcastMiddle::UpcastMiddle(size_t value) : UpcastBase(value) {}
+
+size_t UpcastMiddle::get_middle_value() const {
+  return this->get_base_value() + this->other;
+}
+
+UpcastDerived::UpcastDerived(size_t value) : UpcastMiddle(value) {}
+
 size_t Shared::c_method_on_shared() const noexcept { return 2021; }
 
 const size_t &Shared::c_method_ref_on_shared() const noexcept {
@@ -88,6 +140,80 @@ seastar::shared_ptr<C> c_return_seastar_shared_ptr() {
   return seastar::make_shared<C>(C(2020));
 }
 
+seastar::shared_ptr<std::vector<uint8_t>>
+c_return_seastar_shared_ptr_vector_u8() {
+  return seastar::make_shared<std::vector<uint8_t>>(
+      std::vector<uint8_t>{86, 75, 30, 9});
+}
+
+seastar::future<int32_t> c_return_seastar_future() {
+  return seastar::make_ready_future<int32_t>(2020);
+}
+
+seastar::future<int32_t> c_return_seastar_exception_future() {
+  return seastar::make_exception_future<int32_t>(
+      std::runtime_error("seastar future failed"));
+}
+
+// Starts a single-shard seastar app and calls main on a seastar thread of it,
+// where c_seastar_thread_yield hands control back to the reactor. The reactor
+// only starts once per process.
+int32_t c_run_seastar_thread(rust::Fn<void()> main) {
+  char name[] = "cxx-test-suite";
+  char smp[] = "--smp=1";
+  char memory[] = "--memory=256M";
+  char *argv[] = {name, smp, memory, nullptr};
+  seastar::app_template app;
+  return app.run(3, argv, [main] {
+    return seastar::async([main] { main(); }).then([] { return 0; });
+  });
+}
+
+void c_seastar_thread_yield() { seastar::thread::yield(); }
+
+seastar::lw_shared_ptr<Point>
+c_swap_seastar_point(seastar::lw_shared_ptr<Point> point) {
+  return seastar::make_lw_shared<Point>(Point{point->y, point->x});
+}
+
+seastar::shared_ptr<Base> c_return_seastar_shared_ptr_derived() {
+  return seastar::make_shared<Derived>(2020, 2021);
+}
+
+seastar::stop_iteration c_negate_stop_iteration(seastar::stop_iteration stop) {
+  return !stop;
+}
+
+seastar::shared_ptr<GraphNode> c_new_graph_node(uint32_t value) {
+  return seastar::make_shared<GraphNode>(value);
+}
+
+rust::Vec<seastar::lw_shared_ptr<DropCounter>>
+c_return_seastar_lw_shared_ptr_vec(size_t *drops) {
+  rust::Vec<seastar::lw_shared_ptr<DropCounter>> vec;
+  for (size_t id = 0; id < 3; id++) {
+    vec.push_back(seastar::make_lw_shared<DropCounter>(drops, id));
+  }
+  return vec;
+}
+
+void c_emplace_drop_counter(DropCounter *storage, size_t *drops) {
+  new (storage) DropCounter(drops);
+}
+
+seastar::shared_ptr<SelfRef> c_new_self_ref(size_t value) {
+  return seastar::make_shared<SelfRef>(value);
+}
+
+seastar::shared_ptr<UpcastDerived> c_new_upcast_derived(size_t value) {
+  return seastar::make_shared<UpcastDerived>(value);
+}
+
+Conn c_return_conn_with(Conn conn, size_t n) {
+  conn->set(n);
+  return conn;
+}
+
 std::unique_ptr<::H::H> c_return_ns_unique_ptr() {
   return std::unique_ptr<::H::H>(new ::H::H{"hello"});
 }
@@ -168,6 +294,17 @@ const std::vector<uint8_t> &c_return_ref_vector(const C &c) {
 
 std::vector<uint8_t> &c_return_mut_vector(C &c) { return c.get_v(); }
 
+const seastar::circular_buffer<int32_t> &c_return_ref_circular_buffer() {
+  static const seastar::circular_buffer<int32_t> buf = [] {
+    seastar::circular_buffer<int32_t> buf;
+    buf.push_back(2);
+    buf.push_back(0);
+    buf.push_front(20);
+    return buf;
+  }();
+  return buf;
+}
+
 rust::Vec<uint8_t> c_return_rust_vec_u8() {
   rust::Vec<uint8_t> vec{2, 0, 2, 0};
   return vec;
@@ -563,6 +700,8 @@ size_t c_take_mut_ptr(C *c) {
   return result;
 }
 
+void c_emplace_c(C *storage, size_t n) { new (storage) C(n); }
+
 void c_try_return_void() {}
 
 size_t c_try_return_primitive() { return 2020; }
@@ -695,6 +834,12 @@ void c_take_opaque_ptr(std::unique_ptr<E> e) {
   }
 }
 
+void c_take_seastar_shared_ptr_opaque(seastar::shared_ptr<E> e) {
+  if (e->e == 40 && e.use_count() == 2) {
+    cxx_test_suite_set_correct();
+  }
+}
+
 void c_take_opaque_ns_ptr(std::unique_ptr<::F::F> f) {
   if (f->f == 40) {
     cxx_test_suite_set_correct();
@@ -758,6 +903,20 @@ std::unique_ptr<E> c_return_opaque_ptr() {
 
 E &c_return_opaque_mut_pin(E &e) { return e; }
 
+seastar::shared_ptr<E> c_return_seastar_shared_ptr_opaque() {
+  auto e = seastar::make_shared<E>();
+  e->e = 40;
+  e->e_str = std::string("hello");
+  return e;
+}
+
+seastar::lw_shared_ptr<E> c_return_seastar_lw_shared_ptr_opaque() {
+  auto e = seastar::make_lw_shared<E>();
+  e->e = 40;
+  e->e_str = std::string("hello");
+  return e;
+}
+
 std::unique_ptr<::F::F> c_return_ns_opaque_ptr() {
   auto f = std::unique_ptr<::F::F>(new ::F::F());
   f->f = 40;
@@ -785,6 +944,11 @@ extern "C" const char *cxx_run_test() noexcept {
   ASSERT(cxx_test_suite_r_is_correct(&*r_return_box()));
   ASSERT(r_return_unique_ptr()->get() == 2020);
   ASSERT(r_return_shared_ptr()->get() == 2020);
+  {
+    auto fut = r_return_seastar_future();
+    ASSERT(fut.available());
+    ASSERT(fut.get() == 2020);
+  }
   ASSERT(r_return_ref(Shared{2020}) == 2020);
   ASSERT(std::string(r_return_str(Shared{2020})) == "2020");
   ASSERT(std::string(r_return_rust_string()) == "2020");
@@ -799,6 +963,21 @@ extern "C" const char *cxx_run_test() noexcept {
   r_take_shared(Shared{2020});
   r_take_unique_ptr(std::unique_ptr<C>(new C{2020}));
   r_take_shared_ptr(std::shared_ptr<C>(new C{2020}));
+  r_take_seastar_lw_shared_ptr(seastar::make_lw_shared<C>(2020));
+  r_take_seastar_shared_ptr(seastar::make_shared<C>(2020));
+  {
+    size_t drops = 0;
+    auto counter = seastar::make_shared<DropCounter>(&drops);
+    auto alias = counter;
+    r_take_seastar_shared_ptr_drop_counter(std::move(counter));
+    ASSERT(drops == 0);
+    ASSERT(alias.use_count() == 1);
+    alias = nullptr;
+    ASSERT(drops == 1);
+    r_take_seastar_shared_ptr_drop_counter(
+        seastar::make_shared<DropCounter>(&drops));
+    ASSERT(drops == 2);
+  }
   r_take_ref_c(C{2020});
   r_take_str(rust::Str("2020"));
   r_take_slice_char(rust::Slice<const char>(SLICE_DATA, sizeof(SLICE_DATA)));
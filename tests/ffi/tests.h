@@ -2,6 +2,8 @@
 #include "rust/cxx.h"
 #include <memory>
 #include <string>
+#include <seastar/core/circular_buffer.hh>
+#include <seastar/core/future.hh>
 #include <seastar/core/shared_ptr.hh>
 
 namespace A {
@@ -37,6 +39,7 @@ namespace tests {
 
 struct R;
 struct Shared;
+struct Point;
 struct SharedString;
 enum class Enum : uint16_t;
 
@@ -60,6 +63,88 @@ private:
   std::vector<uint8_t> v;
 };
 
+class Base {
+public:
+  Base(size_t n);
+  virtual ~Base() = default;
+  size_t get_base() const;
+
+private:
+  size_t n;
+};
+
+class Derived : public Base {
+public:
+  Derived(size_t n, size_t m);
+  size_t get_derived() const;
+
+private:
+  size_t m;
+};
+
+class GraphNode {
+public:
+  GraphNode(uint32_t value);
+  uint32_t get_value() const;
+  size_t child_count() const;
+  seastar::shared_ptr<GraphNode> get_child(size_t i) const;
+  void add_child(seastar::shared_ptr<GraphNode> child) const;
+  void clear_children() const;
+
+private:
+  uint32_t value;
+  mutable std::vector<seastar::shared_ptr<GraphNode>> children;
+};
+
+class DropCounter {
+public:
+  DropCounter(size_t *drops, size_t id = 0);
+  ~DropCounter();
+  size_t get_id() const;
+
+private:
+  size_t *drops;
+  size_t id;
+};
+
+class SelfRef : public seastar::enable_shared_from_this<SelfRef> {
+public:
+  SelfRef(size_t value);
+  size_t get_value() const;
+
+private:
+  size_t value;
+};
+
+class UpcastOther {
+public:
+  virtual ~UpcastOther();
+  size_t other = 7;
+};
+
+class UpcastBase {
+public:
+  UpcastBase(size_t value);
+  virtual ~UpcastBase();
+  size_t get_base_value() const;
+
+private:
+  size_t value;
+};
+
+// UpcastBase follows UpcastOther, so its subobject is not at the address of
+// the object.
+class UpcastMiddle : public UpcastOther, public UpcastBase {
+public:
+  UpcastMiddle(size_t value);
+  size_t get_middle_value() const;
+};
+
+class UpcastDerived : public UpcastMiddle {
+public:
+  UpcastDerived(size_t value);
+};
+
 struct D {
   uint64_t d;
   void c_take_trivial_ref_method() const;
@@ -86,6 +171,7 @@ struct Borrow {
 };
 
 typedef char Buffer[12];
+using Conn = seastar::lw_shared_ptr<C>;
 
 size_t c_return_primitive();
 Shared c_return_shared();
@@ -97,6 +183,21 @@ std::shared_ptr<C> c_return_shared_ptr();
 seastar::lw_shared_ptr<C> c_return_seastar_lw_shared_ptr();
 std::unique_ptr<::H::H> c_return_ns_unique_ptr();
 seastar::shared_ptr<C> c_return_seastar_shared_ptr();
+seastar::shared_ptr<std::vector<uint8_t>> c_return_seastar_shared_ptr_vector_u8();
+seastar::future<int32_t> c_return_seastar_future();
+seastar::future<int32_t> c_return_seastar_exception_future();
+int32_t c_run_seastar_thread(rust::Fn<void()> main);
+void c_seastar_thread_yield();
+seastar::lw_shared_ptr<Point> c_swap_seastar_point(seastar::lw_shared_ptr<Point> point);
+seastar::shared_ptr<Base> c_return_seastar_shared_ptr_derived();
+seastar::stop_iteration c_negate_stop_iteration(seastar::stop_iteration stop);
+seastar::shared_ptr<GraphNode> c_new_graph_node(uint32_t value);
+rust::Vec<seastar::lw_shared_ptr<DropCounter>>
+c_return_seastar_lw_shared_ptr_vec(size_t *drops);
+void c_emplace_drop_counter(DropCounter *storage, size_t *drops);
+seastar::shared_ptr<SelfRef> c_new_self_ref(size_t value);
+seastar::shared_ptr<UpcastDerived> c_new_upcast_derived(size_t value);
+Conn c_return_conn_with(Conn conn, size_t n);
 const size_t &c_return_ref(const Shared &shared);
 const size_t &c_return_ns_ref(const ::A::AShared &shared);
 const size_t &c_return_nested_ns_ref(const ::A::B::ABShared &shared);
@@ -114,6 +215,7 @@ std::unique_ptr<std::vector<Shared>> c_return_unique_ptr_vector_shared();
 std::unique_ptr<std::vector<C>> c_return_unique_ptr_vector_opaque();
 const std::vector<uint8_t> &c_return_ref_vector(const C &c);
 std::vector<uint8_t> &c_return_mut_vector(C &c);
+const seastar::circular_buffer<int32_t> &c_return_ref_circular_buffer();
 rust::Vec<uint8_t> c_return_rust_vec_u8();
 const rust::Vec<uint8_t> &c_return_ref_rust_vec(const C &c);
 rust::Vec<uint8_t> &c_return_mut_rust_vec(C &c);
@@ -176,6 +278,7 @@ void c_take_ns_enum(::A::AEnum e);
 void c_take_nested_ns_enum(::A::B::ABEnum e);
 size_t c_take_const_ptr(const C *c);
 size_t c_take_mut_ptr(C *c);
+void c_emplace_c(C *storage, size_t n);
 
 void c_try_return_void();
 size_t c_try_return_primitive();
@@ -206,6 +309,7 @@ void c_take_trivial_ns(::G::G g);
 void c_take_opaque_ptr(std::unique_ptr<E> e);
 void c_take_opaque_ns_ptr(std::unique_ptr<::F::F> f);
 void c_take_opaque_ref(const E &e);
+void c_take_seastar_shared_ptr_opaque(seastar::shared_ptr<E> e);
 void c_take_opaque_ns_ref(const ::F::F &f);
 std::unique_ptr<D> c_return_trivial_ptr();
 D c_return_trivial();
@@ -213,6 +317,8 @@ std::unique_ptr<::G::G> c_return_trivial_ns_ptr();
 ::G::G c_return_trivial_ns();
 std::unique_ptr<E> c_return_opaque_ptr();
 E &c_return_opaque_mut_pin(E &e);
+seastar::shared_ptr<E> c_return_seastar_shared_ptr_opaque();
+seastar::lw_shared_ptr<E> c_return_seastar_lw_shared_ptr_opaque();
 std::unique_ptr<::F::F> c_return_ns_opaque_ptr();
 
 rust::String cOverloadedFunction(int32_t x);
@@ -0,0 +1,48 @@
+// The C++ side of these layouts is pinned by the static_asserts next to the
+// seastar pointer shims in src/cxx.cc and in the code cxxbridge generates.
+
+use cxx::{CxxString, SeastarLwSharedPtr, SeastarSharedPtr};
+use std::ffi::c_void;
+use std::mem::{align_of, size_of};
+
+#[test]
+fn test_seastar_shared_ptr_layout() {
+    assert_eq!(
+        size_of::<SeastarSharedPtr<i32>>(),
+        2 * size_of::<*mut c_void>()
+    );
+    assert_eq!(
+        align_of::<SeastarSharedPtr<i32>>(),
+        align_of::<*mut c_void>()
+    );
+
+    assert_eq!(
+        size_of::<SeastarSharedPtr<CxxString>>(),
+        2 * size_of::<*mut c_void>()
+    );
+    assert_eq!(
+        align_of::<SeastarSharedPtr<CxxString>>(),
+        align_of::<*mut c_void>()
+    );
+}
+
+#[test]
+fn test_seastar_lw_shared_ptr_layout() {
+    assert_eq!(
+        size_of::<SeastarLwSharedPtr<i32>>(),
+        size_of::<*mut c_void>()
+    );
+    assert_eq!(
+        align_of::<SeastarLwSharedPtr<i32>>(),
+        align_of::<*mut c_void>()
+    );
+
+    assert_eq!(
+        size_of::<SeastarLwSharedPtr<CxxString>>(),
+        size_of::<*mut c_void>()
+    );
+    assert_eq!(
+        align_of::<SeastarLwSharedPtr<CxxString>>(),
+        align_of::<*mut c_void>()
+    );
+}
@@ -0,0 +1,55 @@
+use cxx::SeastarAbortSource;
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn test_callback_runs_once() {
+    let source = SeastarAbortSource::new();
+    let calls = Rc::new(Cell::new(0));
+    let subscription = source.subscribe({
+        let calls = Rc::clone(&calls);
+        move || calls.set(calls.get() + 1)
+    });
+    assert!(subscription.is_some());
+    assert!(!source.abort_requested());
+    assert_eq!(calls.get(), 0);
+
+    source.request_abort();
+    assert!(source.abort_requested());
+    assert_eq!(calls.get(), 1);
+
+    source.request_abort();
+    drop(subscription);
+    assert_eq!(calls.get(), 1);
+    assert_eq!(Rc::strong_count(&calls), 1);
+}
+
+#[test]
+fn test_unsubscribe() {
+    let source = SeastarAbortSource::new();
+    let calls = Rc::new(Cell::new(0));
+    let subscription = source.subscribe({
+        let calls = Rc::clone(&calls);
+        move || calls.set(calls.get() + 1)
+    });
+    drop(subscription);
+    assert_eq!(Rc::strong_count(&calls), 1);
+
+    source.request_abort();
+    assert_eq!(calls.get(), 0);
+}
+
+#[test]
+fn test_subscribe_after_abort() {
+    let source = SeastarAbortSource::new();
+    source.request_abort();
+
+    let calls = Rc::new(Cell::new(0));
+    let subscription = source.subscribe({
+        let calls = Rc::clone(&calls);
+        move || calls.set(calls.get() + 1)
+    });
+    assert!(subscription.is_none());
+    assert_eq!(calls.get(), 0);
+    assert_eq!(Rc::strong_count(&calls), 1);
+}
@@ -0,0 +1,43 @@
+use cxx::SeastarChunkedFifo;
+
+#[test]
+fn test_push_pop() {
+    let mut fifo = SeastarChunkedFifo::<u64>::new();
+    assert!(fifo.is_empty());
+    assert_eq!(fifo.front(), None);
+    assert_eq!(fifo.pop_front(), None);
+
+    for value in [20, 2, 0] {
+        fifo.push_back(value);
+    }
+    assert_eq!(fifo.len(), 3);
+    assert_eq!(fifo.front(), Some(&20));
+
+    assert_eq!(fifo.pop_front(), Some(20));
+    assert_eq!(fifo.front(), Some(&2));
+    assert_eq!(fifo.pop_front(), Some(2));
+    assert_eq!(fifo.pop_front(), Some(0));
+    assert_eq!(fifo.pop_front(), None);
+    assert!(fifo.is_empty());
+}
+
+#[test]
+fn test_many_chunks() {
+    // chunked_fifo allocates in chunks of 128 elements by default.
+    let mut fifo = SeastarChunkedFifo::<i32>::new();
+    for value in 0..1000 {
+        fifo.push_back(value);
+    }
+    assert_eq!(fifo.len(), 1000);
+    for value in 0..1000 {
+        assert_eq!(fifo.pop_front(), Some(value));
+    }
+    assert_eq!(fifo.pop_front(), None);
+}
+
+#[test]
+fn test_debug() {
+    let mut fifo = SeastarChunkedFifo::<bool>::new();
+    fifo.push_back(true);
+    assert_eq!(format!("{:?}", fifo), "SeastarChunkedFifo<bool> { len: 1 }");
+}
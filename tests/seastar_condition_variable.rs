@@ -0,0 +1,61 @@
+mod common;
+
+use common::poll_ready;
+use cxx::SeastarConditionVariable;
+
+#[test]
+fn test_signal() {
+    let condition = SeastarConditionVariable::new();
+    let mut first = condition.wait();
+    let mut second = condition.wait();
+    assert!(!first.is_ready());
+    assert!(!second.is_ready());
+
+    condition.signal();
+    assert!(first.is_ready());
+    assert!(!second.is_ready());
+    poll_ready(&mut first).unwrap();
+
+    condition.signal();
+    poll_ready(&mut second).unwrap();
+}
+
+#[test]
+fn test_broadcast() {
+    let condition = SeastarConditionVariable::new();
+    let mut waiters = vec![condition.wait(), condition.wait(), condition.wait()];
+
+    condition.broadcast();
+    for waiter in &mut waiters {
+        poll_ready(waiter).unwrap();
+    }
+
+    let later = condition.wait();
+    assert!(!later.is_ready());
+    condition.signal();
+    assert!(later.is_ready());
+}
+
+#[test]
+fn test_has_waiters() {
+    let condition = SeastarConditionVariable::new();
+    assert!(!condition.has_waiters());
+
+    let first = condition.wait();
+    let second = condition.wait();
+    assert!(condition.has_waiters());
+    condition.signal();
+    assert!(first.is_ready());
+    assert!(condition.has_waiters());
+    condition.broadcast();
+    assert!(second.is_ready());
+    assert!(!condition.has_waiters());
+}
+
+#[test]
+fn test_drop_breaks_waiters() {
+    let condition = SeastarConditionVariable::new();
+    let mut waiter = condition.wait();
+    drop(condition);
+    assert!(poll_ready(&mut waiter).is_err());
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::wait;
+use cxx::memory::OpenFlags;
+use cxx::SeastarFile;
+use cxx_test_suite::ffi;
+use std::env;
+use std::path::Path;
+
+#[test]
+fn test_open_flags() {
+    let flags = OpenFlags::READ_WRITE | OpenFlags::CREATE;
+    assert!(flags.contains(OpenFlags::READ_ONLY));
+    assert!(flags.contains(OpenFlags::WRITE_ONLY));
+    assert!(flags.contains(OpenFlags::CREATE));
+    assert!(!flags.contains(OpenFlags::TRUNCATE));
+    assert_eq!(format!("{:?}", flags), "READ_WRITE | CREATE");
+
+    let mut flags = OpenFlags::default();
+    assert_eq!(format!("{:?}", flags), "READ_ONLY");
+    flags |= OpenFlags::WRITE_ONLY;
+    flags |= OpenFlags::TRUNCATE | OpenFlags::EXCLUSIVE;
+    assert_eq!(format!("{:?}", flags), "WRITE_ONLY | TRUNCATE | EXCLUSIVE");
+}
+
+// Direct I/O needs a filesystem supporting O_DIRECT, so this only runs with
+// CXX_SEASTAR_FILE_TEST_DIR set to such a directory. The I/O completes on a
+// seastar app started for the test.
+#[test]
+fn test_dma_write_read() {
+    if env::var_os("CXX_SEASTAR_FILE_TEST_DIR").is_none() {
+        return;
+    }
+    assert_eq!(ffi::c_run_seastar_thread(dma_write_read), 0);
+}
+
+fn dma_write_read() {
+    let dir = env::var_os("CXX_SEASTAR_FILE_TEST_DIR").unwrap();
+    let path = Path::new(&dir).join("cxx_seastar_file_test");
+    let path = path.to_str().unwrap();
+
+    let flags = OpenFlags::READ_WRITE | OpenFlags::CREATE | OpenFlags::TRUNCATE;
+    let file = wait(SeastarFile::open(path, flags)).unwrap();
+    let alignment = file.disk_write_dma_alignment() as usize;
+    let data: Vec<u8> = (0..alignment).map(|i| i as u8).collect();
+
+    // Unaligned writes are refused before reaching the disk.
+    assert!(wait(file.dma_write(1, &data)).is_err());
+    assert_eq!(wait(file.dma_write(0, &data)).unwrap(), alignment);
+
+    let mut buf = vec![0; 16];
+    assert_eq!(wait(file.dma_read(3, &mut buf)).unwrap(), 16);
+    assert_eq!(buf, &data[3..19]);
+
+    // Reading past the end of the file stops there.
+    let mut buf = vec![0; alignment];
+    assert_eq!(wait(file.dma_read(8, &mut buf)).unwrap(), alignment - 8);
+    assert_eq!(&buf[..alignment - 8], &data[8..]);
+
+    wait(file.close()).unwrap();
+    std::fs::remove_file(path).unwrap();
+}
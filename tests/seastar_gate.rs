@@ -0,0 +1,45 @@
+mod common;
+
+use common::poll_ready;
+use cxx::SeastarGate;
+
+#[test]
+fn test_enter_leave() {
+    let gate = SeastarGate::new();
+    assert_eq!(gate.count(), 0);
+
+    let first = gate.enter().unwrap();
+    let second = gate.enter().unwrap();
+    assert_eq!(gate.count(), 2);
+
+    drop(first);
+    assert_eq!(gate.count(), 1);
+    drop(second);
+    assert_eq!(gate.count(), 0);
+}
+
+#[test]
+fn test_enter_after_close() {
+    let gate = SeastarGate::new();
+    let holder = gate.enter().unwrap();
+    assert!(!gate.is_closed());
+
+    let mut closed = gate.close();
+    assert!(gate.is_closed());
+    assert!(gate.enter().is_none());
+    assert_eq!(gate.count(), 1);
+    assert!(!closed.is_ready());
+
+    drop(holder);
+    assert_eq!(gate.count(), 0);
+    assert!(gate.enter().is_none());
+    poll_ready(&mut closed).unwrap();
+}
+
+#[test]
+fn test_close_twice() {
+    let gate = SeastarGate::new();
+    poll_ready(gate.close()).unwrap();
+    assert!(poll_ready(gate.close()).is_err());
+    assert!(gate.is_closed());
+}
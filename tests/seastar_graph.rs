@@ -0,0 +1,33 @@
+use cxx::memory::{deserialize_graph, serialize_graph};
+use cxx::SeastarSharedPtr;
+use cxx_test_suite::ffi;
+
+#[test]
+fn test_round_trip() {
+    // 1 -> [2, 3], 2 -> [4], 3 -> [4], 4 -> [1]
+    let nodes: Vec<_> = (1..=4).map(ffi::c_new_graph_node).collect();
+    nodes[0].add_child(nodes[1].clone());
+    nodes[0].add_child(nodes[2].clone());
+    nodes[1].add_child(nodes[3].clone());
+    nodes[2].add_child(nodes[3].clone());
+    nodes[3].add_child(nodes[0].clone());
+
+    let json = serialize_graph(&nodes[0], serde_json::value::Serializer).unwrap();
+    for node in &nodes {
+        node.clear_children();
+    }
+
+    let root: SeastarSharedPtr<ffi::GraphNode> = deserialize_graph(json).unwrap();
+    assert_eq!(root.get_value(), 1);
+    assert_eq!(root.child_count(), 2);
+    let left = root.get_child(0);
+    let right = root.get_child(1);
+    assert_eq!(left.get_value(), 2);
+    assert_eq!(right.get_value(), 3);
+    let shared = left.get_child(0);
+    assert_eq!(shared.get_value(), 4);
+    assert!(shared.ptr_eq(&right.get_child(0)));
+    assert!(shared.get_child(0).ptr_eq(&root));
+
+    shared.clear_children();
+}
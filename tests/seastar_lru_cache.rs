@@ -0,0 +1,52 @@
+use cxx::memory::LruCache;
+use cxx::SeastarLwSharedPtr;
+
+#[test]
+fn test_insert() {
+    let mut cache = LruCache::new(2);
+    assert!(cache.is_empty());
+
+    assert!(cache.insert("a", SeastarLwSharedPtr::new(1i32)).is_none());
+    assert!(cache.insert("b", SeastarLwSharedPtr::new(2i32)).is_none());
+    assert_eq!(cache.len(), 2);
+
+    let replaced = cache.insert("a", SeastarLwSharedPtr::new(3i32)).unwrap();
+    assert_eq!(*replaced, 1);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(**cache.peek(&"a").unwrap(), 3);
+}
+
+#[test]
+fn test_get_touches_recency() {
+    let mut cache = LruCache::new(3);
+    cache.insert("a", SeastarLwSharedPtr::new(1i32));
+    cache.insert("b", SeastarLwSharedPtr::new(2i32));
+    cache.insert("c", SeastarLwSharedPtr::new(3i32));
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["c", "b", "a"]);
+
+    assert_eq!(**cache.get(&"a").unwrap(), 1);
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["a", "c", "b"]);
+
+    assert_eq!(**cache.peek(&"b").unwrap(), 2);
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["a", "c", "b"]);
+    assert!(cache.get(&"d").is_none());
+}
+
+#[test]
+fn test_eviction_order() {
+    let mut cache = LruCache::new(2);
+    let a = SeastarLwSharedPtr::new(1i32);
+    cache.insert("a", a.clone());
+    cache.insert("b", SeastarLwSharedPtr::new(2i32));
+    cache.get(&"a");
+    assert_eq!(a.use_count(), 2);
+
+    cache.insert("c", SeastarLwSharedPtr::new(3i32));
+    assert!(cache.peek(&"b").is_none());
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["c", "a"]);
+
+    cache.insert("d", SeastarLwSharedPtr::new(4i32));
+    assert!(cache.peek(&"a").is_none());
+    assert_eq!(a.use_count(), 1);
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["d", "c"]);
+}
@@ -0,0 +1,493 @@
+use cxx::{CxxString, SeastarLwSharedPtr, SharedMutError};
+use cxx_test_suite::ffi;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::mem::{self, ManuallyDrop};
+use std::os::raw::c_char;
+use std::pin::Pin;
+
+#[test]
+fn test_clone_from_same_target() {
+    let shared_ptr = SeastarLwSharedPtr::new(2020i32);
+    let mut other = shared_ptr.clone();
+    assert_eq!(shared_ptr.use_count(), 2);
+
+    other.clone_from(&shared_ptr);
+    assert!(other.ptr_eq(&shared_ptr));
+    assert_eq!(shared_ptr.use_count(), 2);
+}
+
+#[test]
+fn test_clone_from_different_target() {
+    let shared_ptr = SeastarLwSharedPtr::new(2020i32);
+    let mut other = SeastarLwSharedPtr::new(2021i32);
+
+    other.clone_from(&shared_ptr);
+    assert!(other.ptr_eq(&shared_ptr));
+    assert_eq!(*other, 2020);
+    assert_eq!(shared_ptr.use_count(), 2);
+}
+
+#[test]
+fn test_try_deref() {
+    let lw_shared_ptr = SeastarLwSharedPtr::new(2020i32);
+    assert_eq!(lw_shared_ptr.try_deref().copied().ok(), Some(2020));
+
+    let null = SeastarLwSharedPtr::<i32>::null();
+    let error = null.try_deref().unwrap_err();
+    assert_eq!(error.to_string(), "SeastarLwSharedPtr<int32_t> is null");
+}
+
+#[test]
+fn test_character_targets() {
+    let c_char = SeastarLwSharedPtr::new(b'x' as c_char);
+    assert_eq!(*c_char as u8, b'x');
+
+    let char16 = SeastarLwSharedPtr::new(0x263a_u16);
+    assert_eq!(char::from_u32(u32::from(*char16)), Some('\u{263a}'));
+
+    let char32 = SeastarLwSharedPtr::new(0x1f980_u32);
+    assert_eq!(char::from_u32(*char32), Some('\u{1f980}'));
+}
+
+#[test]
+fn test_primitive_typename() {
+    let error = SeastarLwSharedPtr::<u8>::null().try_deref().unwrap_err();
+    assert_eq!(error.to_string(), "SeastarLwSharedPtr<uint8_t> is null");
+    let error = SeastarLwSharedPtr::<f64>::null().try_deref().unwrap_err();
+    assert_eq!(error.to_string(), "SeastarLwSharedPtr<double> is null");
+    let error = SeastarLwSharedPtr::<bool>::null().try_deref().unwrap_err();
+    assert_eq!(error.to_string(), "SeastarLwSharedPtr<bool> is null");
+}
+
+#[test]
+fn test_set() {
+    let mut ptr = SeastarLwSharedPtr::new(2020i32);
+    let address = ptr.as_ref().unwrap() as *const i32;
+    assert_eq!(ptr.set(2021), Ok(()));
+    assert_eq!(*ptr, 2021);
+    assert_eq!(ptr.as_ref().unwrap() as *const i32, address);
+
+    let other = ptr.clone();
+    assert_eq!(ptr.set(2022), Err(SharedMutError::Shared));
+    assert_eq!(*other, 2021);
+
+    let mut null = SeastarLwSharedPtr::<i32>::null();
+    assert_eq!(null.set(2020), Err(SharedMutError::Null));
+}
+
+#[test]
+fn test_from_str() {
+    for s in ["seastar", "", "nul\0inside"] {
+        let ptr = SeastarLwSharedPtr::<CxxString>::from_str(s);
+        assert_eq!(ptr.as_ref().unwrap().to_str(), Ok(s));
+        assert_eq!(ptr.as_ref().unwrap().len(), s.len());
+    }
+}
+
+#[test]
+fn test_from_bytes() {
+    let ptr = SeastarLwSharedPtr::<CxxString>::from_bytes(&[0, 1, 2, 0xFF]);
+    assert_eq!(ptr.as_ref().unwrap().as_bytes(), [0, 1, 2, 0xFF]);
+    assert!(ptr.to_str().is_err());
+
+    let empty = SeastarLwSharedPtr::<CxxString>::from_bytes(&[]);
+    assert!(empty.as_ref().unwrap().is_empty());
+}
+
+#[test]
+fn test_to_str() {
+    let null = SeastarLwSharedPtr::<CxxString>::null();
+    assert_eq!(null.to_str(), Ok(None));
+
+    let ptr = SeastarLwSharedPtr::<CxxString>::from_str("seastar");
+    assert_eq!(ptr.to_str(), Ok(Some("seastar")));
+
+    let invalid = SeastarLwSharedPtr::<CxxString>::from_str("seastar");
+    let string = unsafe { Pin::new_unchecked(&mut *(invalid.as_ptr() as *mut CxxString)) };
+    string.push_bytes(b"\xff");
+    assert!(invalid.to_str().is_err());
+}
+
+#[test]
+fn test_reset() {
+    let mut ptr = SeastarLwSharedPtr::new(2020i32);
+    let other = ptr.clone();
+    assert_eq!(other.use_count(), 2);
+
+    ptr.reset();
+    assert!(ptr.is_null());
+    assert_eq!(other.use_count(), 1);
+
+    ptr.reset_to(2021);
+    assert_eq!(*ptr, 2021);
+    assert_eq!(ptr.use_count(), 1);
+    assert!(!ptr.ptr_eq(&other));
+}
+
+#[test]
+fn test_get_or_insert_default() {
+    let mut ptr = SeastarLwSharedPtr::<i32>::null();
+    assert_eq!(*ptr.get_or_insert_default(), 0);
+    assert!(!ptr.is_null());
+
+    *ptr.get_or_insert_default() = 2020;
+    let address = ptr.as_ref().unwrap() as *const i32;
+    assert_eq!(*ptr.get_or_insert_default(), 2020);
+    assert_eq!(ptr.as_ref().unwrap() as *const i32, address);
+
+    let other = ptr.clone();
+    *ptr.get_or_insert_default() = 2021;
+    assert_eq!(*ptr, 2021);
+    assert_eq!(*other, 2020);
+    assert_eq!(other.use_count(), 1);
+}
+
+#[test]
+fn test_raw_round_trip() {
+    let ptr = SeastarLwSharedPtr::new(2020i32);
+    let other = ptr.clone();
+    assert_eq!(other.use_count(), 2);
+
+    let raw = ptr.into_raw();
+    assert_eq!(other.use_count(), 2);
+
+    let ptr = unsafe { SeastarLwSharedPtr::<i32>::from_raw(raw) };
+    assert_eq!(ptr.use_count(), 2);
+    assert!(ptr.ptr_eq(&other));
+    drop(ptr);
+    assert_eq!(other.use_count(), 1);
+}
+
+#[test]
+fn test_swap() {
+    let mut a = SeastarLwSharedPtr::new(2020i32);
+    let a_alias = a.clone();
+    let mut b = SeastarLwSharedPtr::new(2021i32);
+    assert_eq!(a.use_count(), 2);
+    assert_eq!(b.use_count(), 1);
+
+    a.swap(&mut b);
+    assert_eq!(*a, 2021);
+    assert_eq!(*b, 2020);
+    assert!(b.ptr_eq(&a_alias));
+    assert_eq!(a.use_count(), 1);
+    assert_eq!(b.use_count(), 2);
+
+    let mut null = SeastarLwSharedPtr::null();
+    a.swap(&mut null);
+    assert!(a.is_null());
+    assert_eq!(*null, 2021);
+    assert_eq!(null.use_count(), 1);
+}
+
+#[test]
+fn test_option_round_trip() {
+    assert!(SeastarLwSharedPtr::<i32>::null().into_option().is_none());
+    assert!(SeastarLwSharedPtr::<i32>::from_option(None).is_null());
+
+    let ptr = SeastarLwSharedPtr::new(2020i32);
+    let other = ptr.clone();
+    let option = ptr.into_option();
+    assert_eq!(other.use_count(), 2);
+
+    let ptr = SeastarLwSharedPtr::from_option(option);
+    assert!(ptr.ptr_eq(&other));
+    assert_eq!(ptr.use_count(), 2);
+}
+
+#[test]
+fn test_to_shared() {
+    let lw = SeastarLwSharedPtr::new(2020i32);
+    let shared = lw.to_shared();
+    assert_eq!(*shared, 2020);
+    assert_eq!(*shared, *lw);
+    assert_eq!(lw.use_count(), 1);
+    assert_eq!(shared.use_count(), 1);
+
+    assert!(SeastarLwSharedPtr::<i32>::null().to_shared().is_null());
+}
+
+#[test]
+fn test_as_ptr() {
+    let ptr = SeastarLwSharedPtr::new(2020i32);
+    let expected = ptr.as_ref().map(|r| r as *const i32).unwrap();
+    assert_eq!(ptr.as_ptr(), expected);
+    assert!(SeastarLwSharedPtr::<i32>::null().as_ptr().is_null());
+}
+
+#[test]
+fn test_as_mut_ptr() {
+    let mut ptr = SeastarLwSharedPtr::new(2020i32);
+    assert!(ptr.is_unique());
+    let raw = ptr.as_mut_ptr();
+    assert_eq!(raw as *const i32, ptr.as_ptr());
+    unsafe { *raw = 2023 }
+    assert_eq!(ptr.as_ref(), Some(&2023));
+    assert!(SeastarLwSharedPtr::<i32>::null().as_mut_ptr().is_null());
+}
+
+#[test]
+fn test_pin_mut() {
+    let mut ptr = SeastarLwSharedPtr::new(2020i32);
+    *ptr.pin_mut() = 2023;
+    assert_eq!(*ptr, 2023);
+
+    let mut ptr = SeastarLwSharedPtr::from_str("seastar");
+    ptr.pin_mut().push_str("!");
+    assert_eq!(ptr.to_str(), Ok(Some("seastar!")));
+}
+
+#[test]
+fn test_get_mut() {
+    let mut ptr = SeastarLwSharedPtr::new(2020i32);
+    *ptr.get_mut().unwrap() = 2023;
+    assert_eq!(*ptr, 2023);
+
+    let other = ptr.clone();
+    assert!(ptr.get_mut().is_none());
+    drop(other);
+    assert!(ptr.get_mut().is_some());
+
+    assert!(SeastarLwSharedPtr::<i32>::null().get_mut().is_none());
+}
+
+#[test]
+fn test_string_mutation() {
+    let mut ptr = SeastarLwSharedPtr::from_str("sea");
+    assert_eq!(ptr.push_str("star"), Ok(()));
+    assert_eq!(ptr.to_str(), Ok(Some("seastar")));
+
+    let other = ptr.clone();
+    assert_eq!(ptr.push_str("!"), Err(SharedMutError::Shared));
+    assert_eq!(ptr.clear(), Err(SharedMutError::Shared));
+    assert_eq!(other.to_str(), Ok(Some("seastar")));
+    drop(other);
+
+    assert_eq!(ptr.clear(), Ok(()));
+    assert_eq!(ptr.to_str(), Ok(Some("")));
+
+    let mut null = SeastarLwSharedPtr::<CxxString>::null();
+    assert_eq!(null.push_str("seastar"), Err(SharedMutError::Null));
+    assert_eq!(null.clear(), Err(SharedMutError::Null));
+}
+
+#[test]
+#[should_panic = "called pin_mut on a null SeastarLwSharedPtr<int32_t>"]
+fn test_pin_mut_null() {
+    SeastarLwSharedPtr::<i32>::null().pin_mut();
+}
+
+#[test]
+#[should_panic = "called pin_mut on a shared SeastarLwSharedPtr<int32_t>"]
+fn test_pin_mut_shared() {
+    let mut ptr = SeastarLwSharedPtr::new(2020i32);
+    let _other = ptr.clone();
+    ptr.pin_mut();
+}
+
+fn get_as_ref(value: impl AsRef<i32>) -> i32 {
+    *value.as_ref()
+}
+
+#[test]
+fn test_as_ref_trait() {
+    let ptr = SeastarLwSharedPtr::new(2020i32);
+    assert_eq!(get_as_ref(&ptr), 2020);
+    assert_eq!(get_as_ref(ptr.clone()), 2020);
+    assert_eq!(*Borrow::<i32>::borrow(&ptr), 2020);
+}
+
+#[test]
+#[should_panic = "called deref on a null SeastarLwSharedPtr<int32_t>"]
+fn test_as_ref_trait_null() {
+    get_as_ref(SeastarLwSharedPtr::<i32>::null());
+}
+
+#[test]
+fn test_debug() {
+    let ptr = SeastarLwSharedPtr::new(2020i32);
+    let other = ptr.clone();
+    assert_eq!(format!("{:?}", ptr), "2020");
+
+    let alternate = format!("{:#?}", ptr);
+    assert!(alternate.starts_with("SeastarLwSharedPtr {"));
+    assert!(alternate.contains(&format!("ptr: {:?}", ptr.as_ptr())));
+    assert!(alternate.contains("use_count: 2"));
+    assert!(alternate.contains("2020"));
+    drop(other);
+
+    let null = SeastarLwSharedPtr::<i32>::null();
+    assert_eq!(format!("{:?}", null), "nullptr");
+    assert!(format!("{:#?}", null).contains("use_count: 0"));
+}
+
+#[test]
+fn test_display_or() {
+    let null = SeastarLwSharedPtr::<i32>::null();
+    assert_eq!(format!("{}", null), "nullptr");
+    assert_eq!(format!("{}", null.display_or("<empty>")), "<empty>");
+
+    let ptr = SeastarLwSharedPtr::new(2020i32);
+    assert_eq!(format!("{}", ptr.display_or("<empty>")), "2020");
+    assert_eq!(format!("{:>6}", ptr.display_or("<empty>")), "  2020");
+}
+
+#[test]
+fn test_debug_strict() {
+    let ptr = SeastarLwSharedPtr::new(2020i32);
+    assert_eq!(format!("{:?}", ptr.debug_strict()), "2020");
+
+    let null = SeastarLwSharedPtr::<i32>::null();
+    assert_eq!(format!("{:?}", null), "nullptr");
+    assert_eq!(
+        format!("{:?}", null.debug_strict()),
+        "<NULL SeastarLwSharedPtr<int32_t>>",
+    );
+}
+
+#[test]
+fn test_into_iterator() {
+    let ptr = SeastarLwSharedPtr::new(2020i32);
+    let mut visited = 0;
+    for value in &ptr {
+        assert_eq!(*value, 2020);
+        visited += 1;
+    }
+    assert_eq!(visited, 1);
+
+    let null = SeastarLwSharedPtr::<i32>::null();
+    assert_eq!((&null).into_iter().next(), None);
+
+    let ptrs = [ptr, null, SeastarLwSharedPtr::new(2021)];
+    let values: Vec<i32> = ptrs.iter().flatten().copied().collect();
+    assert_eq!(values, [2020, 2021]);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_drop_poisons_storage() {
+    let mut ptr = ManuallyDrop::new(SeastarLwSharedPtr::new(2020i32));
+    unsafe { ManuallyDrop::drop(&mut ptr) };
+    let words = unsafe { *(&*ptr as *const SeastarLwSharedPtr<i32>).cast::<[usize; 1]>() };
+    let poison = usize::from_ne_bytes([0xa5; mem::size_of::<usize>()]);
+    assert_eq!(words, [poison; 1]);
+}
+
+#[test]
+fn test_const_null_matches_null() {
+    const EMPTY: SeastarLwSharedPtr<CxxString> = SeastarLwSharedPtr::NULL;
+    let null = SeastarLwSharedPtr::<CxxString>::null();
+    assert!(EMPTY.is_null());
+    let words = |ptr: &SeastarLwSharedPtr<CxxString>| unsafe {
+        *(ptr as *const SeastarLwSharedPtr<CxxString>).cast::<[usize; 1]>()
+    };
+    assert_eq!(words(&EMPTY), words(&null));
+    assert_eq!(words(&EMPTY), [0; 1]);
+}
+
+#[test]
+fn test_owner_shard_same_shard() {
+    let ptr = SeastarLwSharedPtr::new(2020i32);
+    ptr.debug_assert_owner_shard();
+
+    let other = ptr.clone();
+    assert_eq!(*other, 2020);
+    drop(ptr);
+    other.debug_assert_owner_shard();
+    drop(other);
+
+    let mut ptr = SeastarLwSharedPtr::from_str("2020");
+    assert_eq!(ptr.to_str(), Ok(Some("2020")));
+    ptr.reset();
+    ptr.debug_assert_owner_shard();
+}
+
+// Shard ids come from a running reactor, which can only be started once per
+// process, so this is the only test here that starts one.
+#[test]
+fn test_owning_shard() {
+    assert_eq!(ffi::c_run_seastar_thread(owning_shard), 0);
+}
+
+fn owning_shard() {
+    let recorded = cfg!(all(debug_assertions, feature = "seastar-shard-checks"));
+    let expected = if recorded { Some(0) } else { None };
+
+    let ptr = SeastarLwSharedPtr::new(ffi::Point { x: 1, y: 2 });
+    assert_eq!(ptr.owning_shard(), expected);
+    assert_eq!(ptr.clone().owning_shard(), expected);
+
+    // The swapped point is allocated by C++, which records no shard.
+    let swapped = ffi::c_swap_seastar_point(ptr);
+    assert_eq!(swapped.owning_shard(), None);
+
+    assert_eq!(SeastarLwSharedPtr::<i32>::null().owning_shard(), None);
+}
+
+#[test]
+fn test_is_unique() {
+    let ptr = SeastarLwSharedPtr::new(2020i32);
+    assert!(ptr.is_unique());
+    let other = ptr.clone();
+    assert!(!ptr.is_unique());
+    assert!(!other.is_unique());
+    drop(other);
+    assert!(ptr.is_unique());
+
+    assert!(!SeastarLwSharedPtr::<i32>::null().is_unique());
+}
+
+#[test]
+fn test_clone_non_null() {
+    let ptr = SeastarLwSharedPtr::new(2020i32);
+    let other = ptr.clone_non_null().unwrap();
+    assert!(ptr.ptr_eq(&other));
+    assert_eq!(ptr.use_count(), 2);
+
+    assert!(SeastarLwSharedPtr::<i32>::null().clone_non_null().is_none());
+}
+
+#[test]
+fn test_points_to() {
+    let ptr = SeastarLwSharedPtr::new(42i32);
+    assert!(ptr.points_to(&42));
+    assert!(!ptr.points_to(&2020));
+    assert!(!SeastarLwSharedPtr::<i32>::null().points_to(&42));
+}
+
+#[test]
+fn test_use_count_past_u16() {
+    let ptr = SeastarLwSharedPtr::new(2020i32);
+    let clones = vec![ptr.clone(); 70_000];
+    assert_eq!(ptr.use_count(), 70_001);
+    drop(clones);
+    assert_eq!(ptr.use_count(), 1);
+}
+
+#[test]
+fn test_value_ordering() {
+    let null = SeastarLwSharedPtr::<i32>::null();
+    let low = SeastarLwSharedPtr::new(-5i32);
+    let high = SeastarLwSharedPtr::new(2020i32);
+    assert!(null < low);
+    assert!(low < high);
+    assert_eq!(null.cmp(&SeastarLwSharedPtr::null()), Ordering::Equal);
+    assert_eq!(low, SeastarLwSharedPtr::new(-5));
+    assert!(!low.ptr_eq(&SeastarLwSharedPtr::new(-5)));
+    assert_ne!(null, low);
+
+    let mut map = BTreeMap::new();
+    map.insert(high.clone(), "high");
+    map.insert(null.clone(), "null");
+    map.insert(low.clone(), "low");
+    let values: Vec<_> = map.values().copied().collect();
+    assert_eq!(values, ["null", "low", "high"]);
+    assert_eq!(map.get(&SeastarLwSharedPtr::new(2020)), Some(&"high"));
+
+    // Without null keys, the map can also be looked up by value.
+    map.remove(&null);
+    assert_eq!(map.get(&2020), Some(&"high"));
+    assert_eq!(map.get(&7), None);
+}
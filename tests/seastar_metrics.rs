@@ -0,0 +1,40 @@
+use cxx::SeastarMetrics;
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn test_register_gauge() {
+    let metrics = SeastarMetrics::new("cxx_test_gauge");
+    assert_eq!(metrics.group(), "cxx_test_gauge");
+
+    let value = Rc::new(Cell::new(1.5));
+    let captured = Rc::clone(&value);
+    let registration = metrics
+        .register_gauge("value", move || captured.get())
+        .unwrap();
+    assert_eq!(Rc::strong_count(&value), 2);
+
+    // Unregistering frees the closure.
+    drop(registration);
+    assert_eq!(Rc::strong_count(&value), 1);
+}
+
+#[test]
+fn test_register_counter() {
+    let metrics = SeastarMetrics::new("cxx_test_counter");
+    let count = Rc::new(Cell::new(0u64));
+    let captured = Rc::clone(&count);
+    let first = metrics
+        .register_counter("first", move || captured.get())
+        .unwrap();
+    let captured = Rc::clone(&count);
+    let second = metrics
+        .register_counter("second", move || captured.get())
+        .unwrap();
+    assert_eq!(Rc::strong_count(&count), 3);
+
+    drop(first);
+    assert_eq!(Rc::strong_count(&count), 2);
+    drop(second);
+    assert_eq!(Rc::strong_count(&count), 1);
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "ptr-tracing")]
+
+use cxx::memory::{set_ptr_tracer, PtrEvent, PtrKind};
+use cxx::{SeastarLwSharedPtr, SeastarSharedPtr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SHARED_CREATED: AtomicUsize = AtomicUsize::new(0);
+static SHARED_DROPPED: AtomicUsize = AtomicUsize::new(0);
+static LW_CREATED: AtomicUsize = AtomicUsize::new(0);
+static LW_DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+fn count(event: PtrEvent) {
+    let counter = match event {
+        PtrEvent::Null(PtrKind::SeastarSharedPtr)
+        | PtrEvent::New(PtrKind::SeastarSharedPtr)
+        | PtrEvent::Clone(PtrKind::SeastarSharedPtr) => &SHARED_CREATED,
+        PtrEvent::Drop(PtrKind::SeastarSharedPtr) => &SHARED_DROPPED,
+        PtrEvent::Null(PtrKind::SeastarLwSharedPtr)
+        | PtrEvent::New(PtrKind::SeastarLwSharedPtr)
+        | PtrEvent::Clone(PtrKind::SeastarLwSharedPtr) => &LW_CREATED,
+        PtrEvent::Drop(PtrKind::SeastarLwSharedPtr) => &LW_DROPPED,
+    };
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+#[test]
+fn test_ptr_tracing() {
+    set_ptr_tracer(Some(count));
+
+    let shared = SeastarSharedPtr::new(2020i32);
+    let shared_clone = shared.clone();
+    let mut shared_null = SeastarSharedPtr::<i32>::null();
+    shared_null.reset_to(2021);
+    drop(shared);
+
+    let lw = SeastarLwSharedPtr::new(2020i32);
+    let lw_clone = lw.clone();
+    let converted = lw.to_shared();
+    let mut lw_null = SeastarLwSharedPtr::<i32>::null();
+    lw_null.reset();
+
+    assert_eq!(SHARED_CREATED.load(Ordering::Relaxed), 5);
+    assert_eq!(SHARED_DROPPED.load(Ordering::Relaxed), 2);
+    assert_eq!(LW_CREATED.load(Ordering::Relaxed), 4);
+    assert_eq!(LW_DROPPED.load(Ordering::Relaxed), 1);
+
+    drop((shared_clone, shared_null, converted));
+    drop((lw, lw_clone, lw_null));
+    set_ptr_tracer(None);
+
+    assert_eq!(SHARED_DROPPED.load(Ordering::Relaxed), 5);
+    assert_eq!(LW_DROPPED.load(Ordering::Relaxed), 4);
+
+    // Nothing is reported once the tracer is unregistered.
+    drop(SeastarSharedPtr::new(2022i32));
+    assert_eq!(SHARED_CREATED.load(Ordering::Relaxed), 5);
+    assert_eq!(SHARED_DROPPED.load(Ordering::Relaxed), 5);
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::poll_ready;
+use cxx::SeastarRwLock;
+
+#[test]
+fn test_write_waits_for_read_guard() {
+    let lock = SeastarRwLock::new();
+    let mut read = lock.read();
+    assert!(read.is_ready());
+    let read_guard = poll_ready(&mut read).unwrap();
+
+    let mut other_read = lock.read();
+    assert!(other_read.is_ready());
+    let other_read_guard = poll_ready(&mut other_read).unwrap();
+
+    let mut write = lock.write();
+    assert!(!write.is_ready());
+    drop(read_guard);
+    assert!(!write.is_ready());
+    drop(other_read_guard);
+    assert!(write.is_ready());
+
+    let write_guard = poll_ready(&mut write).unwrap();
+    assert!(lock.try_read().is_none());
+    drop(write_guard);
+    assert!(lock.try_read().is_some());
+}
+
+#[test]
+fn test_try_lock() {
+    let lock = SeastarRwLock::new();
+    let read_guard = lock.try_read().unwrap();
+    assert!(lock.try_read().is_some());
+    assert!(lock.try_write().is_none());
+    drop(read_guard);
+
+    let write_guard = lock.try_write().unwrap();
+    assert!(lock.try_read().is_none());
+    assert!(lock.try_write().is_none());
+    drop(write_guard);
+    assert!(lock.try_write().is_some());
+}
+
+#[test]
+fn test_drop_acquired_future() {
+    let lock = SeastarRwLock::new();
+    let write = lock.write();
+    assert!(write.is_ready());
+    assert!(lock.try_read().is_none());
+
+    // The lock was granted but never handed out as a guard.
+    drop(write);
+    assert!(lock.try_write().is_some());
+}
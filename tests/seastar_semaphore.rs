@@ -0,0 +1,29 @@
+use cxx::SeastarSemaphore;
+
+#[test]
+fn test_try_wait_signal() {
+    let semaphore = SeastarSemaphore::new(3);
+    assert_eq!(semaphore.current(), 3);
+
+    assert!(semaphore.try_wait(2));
+    assert_eq!(semaphore.current(), 1);
+    assert!(semaphore.try_wait(1));
+    assert_eq!(semaphore.current(), 0);
+
+    semaphore.signal(3);
+    assert_eq!(semaphore.current(), 3);
+}
+
+#[test]
+fn test_try_wait_exhausted() {
+    let semaphore = SeastarSemaphore::new(2);
+    assert!(!semaphore.try_wait(3));
+    assert_eq!(semaphore.current(), 2);
+
+    assert!(semaphore.try_wait(2));
+    assert!(!semaphore.try_wait(1));
+    assert_eq!(semaphore.current(), 0);
+
+    semaphore.signal(1);
+    assert!(semaphore.try_wait(1));
+}
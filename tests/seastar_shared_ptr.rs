@@ -0,0 +1,510 @@
+use cxx::{CxxString, SeastarLwSharedPtr, SeastarSharedPtr, SharedMutError, SharedPtr};
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::mem::{self, ManuallyDrop};
+use std::pin::Pin;
+
+#[test]
+fn test_clone_from_same_target() {
+    let shared_ptr = SeastarSharedPtr::new(2020i32);
+    let mut other = shared_ptr.clone();
+    assert_eq!(shared_ptr.use_count(), 2);
+
+    other.clone_from(&shared_ptr);
+    assert!(other.ptr_eq(&shared_ptr));
+    assert_eq!(shared_ptr.use_count(), 2);
+}
+
+#[test]
+fn test_clone_from_different_target() {
+    let shared_ptr = SeastarSharedPtr::new(2020i32);
+    let mut other = SeastarSharedPtr::new(2021i32);
+
+    other.clone_from(&shared_ptr);
+    assert!(other.ptr_eq(&shared_ptr));
+    assert_eq!(*other, 2020);
+    assert_eq!(shared_ptr.use_count(), 2);
+}
+
+#[test]
+fn test_try_deref() {
+    let shared_ptr = SeastarSharedPtr::new(2020i32);
+    assert_eq!(shared_ptr.try_deref().copied().ok(), Some(2020));
+
+    let null = SeastarSharedPtr::<i32>::null();
+    let error = null.try_deref().unwrap_err();
+    assert_eq!(error.to_string(), "SeastarSharedPtr<int32_t> is null");
+}
+
+#[test]
+fn test_primitive_typename() {
+    let error = SeastarSharedPtr::<u8>::null().try_deref().unwrap_err();
+    assert_eq!(error.to_string(), "SeastarSharedPtr<uint8_t> is null");
+    let error = SeastarSharedPtr::<f64>::null().try_deref().unwrap_err();
+    assert_eq!(error.to_string(), "SeastarSharedPtr<double> is null");
+    let error = SeastarSharedPtr::<bool>::null().try_deref().unwrap_err();
+    assert_eq!(error.to_string(), "SeastarSharedPtr<bool> is null");
+}
+
+#[test]
+fn test_set() {
+    let mut ptr = SeastarSharedPtr::new(2020i32);
+    let address = ptr.as_ref().unwrap() as *const i32;
+    assert_eq!(ptr.set(2021), Ok(()));
+    assert_eq!(*ptr, 2021);
+    assert_eq!(ptr.as_ref().unwrap() as *const i32, address);
+
+    let other = ptr.clone();
+    assert_eq!(ptr.set(2022), Err(SharedMutError::Shared));
+    assert_eq!(*other, 2021);
+
+    let mut null = SeastarSharedPtr::<i32>::null();
+    assert_eq!(null.set(2020), Err(SharedMutError::Null));
+}
+
+#[test]
+fn test_from_str() {
+    for s in ["seastar", "", "nul\0inside"] {
+        let ptr = SeastarSharedPtr::<CxxString>::from_str(s);
+        assert_eq!(ptr.as_ref().unwrap().to_str(), Ok(s));
+        assert_eq!(ptr.as_ref().unwrap().len(), s.len());
+    }
+}
+
+#[test]
+fn test_from_bytes() {
+    let ptr = SeastarSharedPtr::<CxxString>::from_bytes(&[0, 1, 2, 0xFF]);
+    assert_eq!(ptr.as_ref().unwrap().as_bytes(), [0, 1, 2, 0xFF]);
+    assert!(ptr.to_str().is_err());
+
+    let empty = SeastarSharedPtr::<CxxString>::from_bytes(&[]);
+    assert!(empty.as_ref().unwrap().is_empty());
+}
+
+#[test]
+fn test_to_str() {
+    let null = SeastarSharedPtr::<CxxString>::null();
+    assert_eq!(null.to_str(), Ok(None));
+
+    let ptr = SeastarSharedPtr::<CxxString>::from_str("seastar");
+    assert_eq!(ptr.to_str(), Ok(Some("seastar")));
+
+    let invalid = SeastarSharedPtr::<CxxString>::from_str("seastar");
+    let string = unsafe { Pin::new_unchecked(&mut *(invalid.as_ptr() as *mut CxxString)) };
+    string.push_bytes(b"\xff");
+    assert!(invalid.to_str().is_err());
+}
+
+#[test]
+fn test_reset() {
+    let mut ptr = SeastarSharedPtr::new(2020i32);
+    let other = ptr.clone();
+    assert_eq!(other.use_count(), 2);
+
+    ptr.reset();
+    assert!(ptr.is_null());
+    assert_eq!(other.use_count(), 1);
+
+    ptr.reset_to(2021);
+    assert_eq!(*ptr, 2021);
+    assert_eq!(ptr.use_count(), 1);
+    assert!(!ptr.ptr_eq(&other));
+}
+
+#[test]
+fn test_get_or_insert_default() {
+    let mut ptr = SeastarSharedPtr::<i32>::null();
+    assert_eq!(*ptr.get_or_insert_default(), 0);
+    assert!(!ptr.is_null());
+
+    *ptr.get_or_insert_default() = 2020;
+    let address = ptr.as_ref().unwrap() as *const i32;
+    assert_eq!(*ptr.get_or_insert_default(), 2020);
+    assert_eq!(ptr.as_ref().unwrap() as *const i32, address);
+
+    let other = ptr.clone();
+    *ptr.get_or_insert_default() = 2021;
+    assert_eq!(*ptr, 2021);
+    assert_eq!(*other, 2020);
+    assert_eq!(other.use_count(), 1);
+}
+
+#[test]
+fn test_raw_round_trip() {
+    let ptr = SeastarSharedPtr::new(2020i32);
+    let other = ptr.clone();
+    assert_eq!(other.use_count(), 2);
+
+    let raw = ptr.into_raw();
+    assert_eq!(other.use_count(), 2);
+
+    let ptr = unsafe { SeastarSharedPtr::<i32>::from_raw(raw) };
+    assert_eq!(ptr.use_count(), 2);
+    assert!(ptr.ptr_eq(&other));
+    drop(ptr);
+    assert_eq!(other.use_count(), 1);
+}
+
+#[test]
+fn test_swap() {
+    let mut a = SeastarSharedPtr::new(2020i32);
+    let a_alias = a.clone();
+    let mut b = SeastarSharedPtr::new(2021i32);
+    assert_eq!(a.use_count(), 2);
+    assert_eq!(b.use_count(), 1);
+
+    a.swap(&mut b);
+    assert_eq!(*a, 2021);
+    assert_eq!(*b, 2020);
+    assert!(b.ptr_eq(&a_alias));
+    assert_eq!(a.use_count(), 1);
+    assert_eq!(b.use_count(), 2);
+
+    let mut null = SeastarSharedPtr::null();
+    a.swap(&mut null);
+    assert!(a.is_null());
+    assert_eq!(*null, 2021);
+    assert_eq!(null.use_count(), 1);
+}
+
+#[test]
+fn test_deref_owned() {
+    assert!(SeastarSharedPtr::<i32>::null().deref_owned().is_none());
+
+    let ptr = SeastarSharedPtr::new(2020i32);
+    let guard = ptr.deref_owned().unwrap();
+    let value: &i32 = &guard;
+    assert_eq!(ptr.use_count(), 2);
+    drop(ptr);
+
+    assert_eq!(*value, 2020);
+    assert_eq!(format!("{:?}", guard), "2020");
+    let ptr = guard.into_inner();
+    assert_eq!(ptr.use_count(), 1);
+    assert_eq!(*ptr, 2020);
+}
+
+#[test]
+fn test_option_round_trip() {
+    assert!(SeastarSharedPtr::<i32>::null().into_option().is_none());
+    assert!(SeastarSharedPtr::<i32>::from_option(None).is_null());
+
+    let ptr = SeastarSharedPtr::new(2020i32);
+    let other = ptr.clone();
+    let option = ptr.into_option();
+    assert_eq!(other.use_count(), 2);
+
+    let ptr = SeastarSharedPtr::from_option(option);
+    assert!(ptr.ptr_eq(&other));
+    assert_eq!(ptr.use_count(), 2);
+}
+
+#[test]
+fn test_as_ptr() {
+    let ptr = SeastarSharedPtr::new(2020i32);
+    let expected = ptr.as_ref().map(|r| r as *const i32).unwrap();
+    assert_eq!(ptr.as_ptr(), expected);
+    assert!(SeastarSharedPtr::<i32>::null().as_ptr().is_null());
+}
+
+#[test]
+fn test_as_mut_ptr() {
+    let mut ptr = SeastarSharedPtr::new(2020i32);
+    assert!(ptr.is_unique());
+    let raw = ptr.as_mut_ptr();
+    assert_eq!(raw as *const i32, ptr.as_ptr());
+    unsafe { *raw = 2023 }
+    assert_eq!(ptr.as_ref(), Some(&2023));
+    assert!(SeastarSharedPtr::<i32>::null().as_mut_ptr().is_null());
+}
+
+fn get_as_ref(value: impl AsRef<i32>) -> i32 {
+    *value.as_ref()
+}
+
+#[test]
+fn test_as_ref_trait() {
+    let ptr = SeastarSharedPtr::new(2020i32);
+    assert_eq!(get_as_ref(&ptr), 2020);
+    assert_eq!(get_as_ref(ptr.clone()), 2020);
+    assert_eq!(*Borrow::<i32>::borrow(&ptr), 2020);
+}
+
+#[test]
+#[should_panic = "called deref on a null SeastarSharedPtr<int32_t>"]
+fn test_as_ref_trait_null() {
+    get_as_ref(SeastarSharedPtr::<i32>::null());
+}
+
+#[test]
+fn test_debug() {
+    let ptr = SeastarSharedPtr::new(2020i32);
+    let other = ptr.clone();
+    assert_eq!(format!("{:?}", ptr), "2020");
+
+    let alternate = format!("{:#?}", ptr);
+    assert!(alternate.starts_with("SeastarSharedPtr {"));
+    assert!(alternate.contains(&format!("ptr: {:?}", ptr.as_ptr())));
+    assert!(alternate.contains("use_count: 2"));
+    assert!(alternate.contains("2020"));
+    drop(other);
+
+    let null = SeastarSharedPtr::<i32>::null();
+    assert_eq!(format!("{:?}", null), "nullptr");
+    assert!(format!("{:#?}", null).contains("use_count: 0"));
+}
+
+#[test]
+fn test_display_or() {
+    let null = SeastarSharedPtr::<i32>::null();
+    assert_eq!(format!("{}", null), "nullptr");
+    assert_eq!(format!("{}", null.display_or("<empty>")), "<empty>");
+
+    let ptr = SeastarSharedPtr::new(2020i32);
+    assert_eq!(format!("{}", ptr.display_or("<empty>")), "2020");
+    assert_eq!(format!("{:>6}", ptr.display_or("<empty>")), "  2020");
+}
+
+#[test]
+fn test_debug_strict() {
+    let ptr = SeastarSharedPtr::new(2020i32);
+    assert_eq!(format!("{:?}", ptr.debug_strict()), "2020");
+
+    let null = SeastarSharedPtr::<i32>::null();
+    assert_eq!(format!("{:?}", null), "nullptr");
+    assert_eq!(
+        format!("{:?}", null.debug_strict()),
+        "<NULL SeastarSharedPtr<int32_t>>",
+    );
+}
+
+#[test]
+fn test_from_std_shared_copy() {
+    let std_ptr = SharedPtr::new(2020i32);
+    let ptr = SeastarSharedPtr::from_std_shared_copy(&std_ptr);
+    assert_eq!(*ptr, 2020);
+    assert_eq!(ptr.use_count(), 1);
+    assert_ne!(ptr.as_ptr(), std_ptr.as_ref().unwrap() as *const i32);
+
+    let null = SeastarSharedPtr::<i32>::from_std_shared_copy(&SharedPtr::null());
+    assert!(null.is_null());
+}
+
+#[test]
+fn test_from_std_shared() {
+    let std_ptr = SharedPtr::new(2020i32);
+    let weak = std_ptr.downgrade();
+    let ptr = unsafe { SeastarSharedPtr::from_std_shared(&std_ptr) };
+    assert_eq!(ptr.as_ptr(), std_ptr.as_ref().unwrap() as *const i32);
+
+    // The seastar pointer keeps the object alive after the std owner is gone.
+    drop(std_ptr);
+    assert!(!weak.upgrade().is_null());
+    let clone = ptr.clone();
+    drop(ptr);
+    assert_eq!(*clone, 2020);
+    assert!(!weak.upgrade().is_null());
+
+    // The captured std pointer is released with the last seastar reference.
+    drop(clone);
+    assert!(weak.upgrade().is_null());
+
+    let null = unsafe { SeastarSharedPtr::<i32>::from_std_shared(&SharedPtr::null()) };
+    assert!(null.is_null());
+}
+
+#[test]
+fn test_into_iterator() {
+    let ptr = SeastarSharedPtr::new(2020i32);
+    let mut visited = 0;
+    for value in &ptr {
+        assert_eq!(*value, 2020);
+        visited += 1;
+    }
+    assert_eq!(visited, 1);
+
+    let null = SeastarSharedPtr::<i32>::null();
+    assert_eq!((&null).into_iter().next(), None);
+
+    let ptrs = [ptr, null, SeastarSharedPtr::new(2021)];
+    let values: Vec<i32> = ptrs.iter().flatten().copied().collect();
+    assert_eq!(values, [2020, 2021]);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_drop_poisons_storage() {
+    let mut ptr = ManuallyDrop::new(SeastarSharedPtr::new(2020i32));
+    unsafe { ManuallyDrop::drop(&mut ptr) };
+    let words = unsafe { *(&*ptr as *const SeastarSharedPtr<i32>).cast::<[usize; 2]>() };
+    let poison = usize::from_ne_bytes([0xa5; mem::size_of::<usize>()]);
+    assert_eq!(words, [poison; 2]);
+}
+
+#[test]
+fn test_const_null_matches_null() {
+    const EMPTY: SeastarSharedPtr<CxxString> = SeastarSharedPtr::NULL;
+    let null = SeastarSharedPtr::<CxxString>::null();
+    assert!(EMPTY.is_null());
+    let words = |ptr: &SeastarSharedPtr<CxxString>| unsafe {
+        *(ptr as *const SeastarSharedPtr<CxxString>).cast::<[usize; 2]>()
+    };
+    assert_eq!(words(&EMPTY), words(&null));
+    assert_eq!(words(&EMPTY), [0; 2]);
+}
+
+#[test]
+fn test_is_unique() {
+    let ptr = SeastarSharedPtr::new(2020i32);
+    assert!(ptr.is_unique());
+    let other = ptr.clone();
+    assert!(!ptr.is_unique());
+    assert!(!other.is_unique());
+    drop(other);
+    assert!(ptr.is_unique());
+
+    assert!(!SeastarSharedPtr::<i32>::null().is_unique());
+}
+
+#[test]
+fn test_clone_non_null() {
+    let ptr = SeastarSharedPtr::new(2020i32);
+    let other = ptr.clone_non_null().unwrap();
+    assert!(ptr.ptr_eq(&other));
+    assert_eq!(ptr.use_count(), 2);
+
+    assert!(SeastarSharedPtr::<i32>::null().clone_non_null().is_none());
+}
+
+#[test]
+fn test_points_to() {
+    let ptr = SeastarSharedPtr::new(42i32);
+    assert!(ptr.points_to(&42));
+    assert!(!ptr.points_to(&2020));
+    assert!(!SeastarSharedPtr::<i32>::null().points_to(&42));
+}
+
+#[test]
+fn test_use_count_past_u16() {
+    let ptr = SeastarSharedPtr::new(2020i32);
+    let clones = vec![ptr.clone(); 70_000];
+    assert_eq!(ptr.use_count(), 70_001);
+    drop(clones);
+    assert_eq!(ptr.use_count(), 1);
+}
+
+#[test]
+fn test_value_ordering() {
+    let null = SeastarSharedPtr::<i32>::null();
+    let low = SeastarSharedPtr::new(-5i32);
+    let high = SeastarSharedPtr::new(2020i32);
+    assert!(null < low);
+    assert!(low < high);
+    assert_eq!(null.cmp(&SeastarSharedPtr::null()), Ordering::Equal);
+    assert_eq!(low, SeastarSharedPtr::new(-5));
+    assert!(!low.ptr_eq(&SeastarSharedPtr::new(-5)));
+    assert_ne!(null, low);
+
+    let mut map = BTreeMap::new();
+    map.insert(high.clone(), "high");
+    map.insert(null.clone(), "null");
+    map.insert(low.clone(), "low");
+    let values: Vec<_> = map.values().copied().collect();
+    assert_eq!(values, ["null", "low", "high"]);
+    assert_eq!(map.get(&SeastarSharedPtr::new(2020)), Some(&"high"));
+
+    // Without null keys, the map can also be looked up by value.
+    map.remove(&null);
+    assert_eq!(map.get(&2020), Some(&"high"));
+    assert_eq!(map.get(&7), None);
+}
+
+thread_local! {
+    static DELETED: Cell<Option<(i32, usize)>> = Cell::new(None);
+}
+
+fn record_delete(value: *mut i32) {
+    DELETED.with(|deleted| {
+        let count = deleted.get().map_or(0, |(_, count)| count);
+        deleted.set(Some((unsafe { *value }, count + 1)));
+    });
+}
+
+#[test]
+fn test_new_with_deleter() {
+    let shared_ptr = SeastarSharedPtr::new_with_deleter(2020i32, record_delete);
+    assert_eq!(*shared_ptr, 2020);
+
+    let other = shared_ptr.clone();
+    drop(shared_ptr);
+    assert_eq!(DELETED.with(Cell::get), None);
+
+    assert_eq!(*other, 2020);
+    drop(other);
+    assert_eq!(DELETED.with(Cell::get), Some((2020, 1)));
+}
+
+#[test]
+fn test_new_with_panicking_deleter() {
+    fn panicking_delete(value: *mut i32) {
+        record_delete(value);
+        panic!("deleter panicked");
+    }
+
+    // The panic stops at the C++ destructor calling the deleter, instead of
+    // unwinding through it.
+    DELETED.with(|deleted| deleted.set(None));
+    let shared_ptr = SeastarSharedPtr::new_with_deleter(2020i32, panicking_delete);
+    drop(shared_ptr);
+    assert_eq!(DELETED.with(Cell::get), Some((2020, 1)));
+}
+
+#[test]
+fn test_try_into_lw_unique() {
+    let ptr = SeastarSharedPtr::new(2020i32);
+    let lw = ptr.try_into_lw().unwrap();
+    assert_eq!(*lw, 2020);
+    assert_eq!(lw.use_count(), 1);
+
+    let ptr = SeastarSharedPtr::new(2021i32);
+    let lw = SeastarLwSharedPtr::try_from(ptr).unwrap();
+    assert_eq!(*lw, 2021);
+}
+
+#[test]
+fn test_try_into_lw_shared() {
+    let ptr = SeastarSharedPtr::new(2020i32);
+    let other = ptr.clone();
+    let ptr = ptr.try_into_lw().unwrap_err();
+    assert!(ptr.ptr_eq(&other));
+    assert_eq!(ptr.use_count(), 2);
+    assert_eq!(*other, 2020);
+
+    drop(other);
+    let lw = SeastarLwSharedPtr::try_from(ptr).unwrap();
+    assert_eq!(*lw, 2020);
+
+    let null = SeastarSharedPtr::<i32>::null();
+    assert!(null.try_into_lw().unwrap_err().is_null());
+}
+
+#[test]
+fn test_try_into_lw_foreign() {
+    // The deleter still owns the object, so it is neither moved out nor freed.
+    DELETED.with(|deleted| deleted.set(None));
+    let ptr = SeastarSharedPtr::new_with_deleter(2020i32, record_delete);
+    let ptr = ptr.try_into_lw().unwrap_err();
+    assert_eq!(ptr.use_count(), 1);
+    assert_eq!(DELETED.with(Cell::get), None);
+    drop(ptr);
+    assert_eq!(DELETED.with(Cell::get), Some((2020, 1)));
+
+    let std_ptr = SharedPtr::new(2021i32);
+    let ptr = unsafe { SeastarSharedPtr::from_std_shared(&std_ptr) };
+    drop(std_ptr);
+    let ptr = ptr.try_into_lw().unwrap_err();
+    assert_eq!(*ptr, 2021);
+}
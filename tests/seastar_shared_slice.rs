@@ -0,0 +1,87 @@
+use cxx::{SeastarSharedPtr, SeastarSharedSlice, SharedMutError};
+use std::panic::{self, AssertUnwindSafe};
+
+#[test]
+fn test_new_array() {
+    let slice = SeastarSharedSlice::new_array(4, |i| i as u32 * 10);
+    assert_eq!(slice.len(), 4);
+    assert!(!slice.is_empty());
+    assert_eq!(format!("{:?}", slice), "[0, 10, 20, 30]");
+
+    let other = slice.clone();
+    assert_eq!(slice.use_count(), 2);
+    let ptr = other.into_shared_ptr();
+    assert_eq!(ptr.as_slice(), [0, 10, 20, 30]);
+    assert_eq!(slice.use_count(), 2);
+}
+
+#[test]
+fn test_new_array_empty() {
+    let slice = SeastarSharedSlice::<u8>::new_array(0, |_| unreachable!());
+    assert_eq!(slice.len(), 0);
+    assert!(slice.is_empty());
+
+    let ptr = slice.into_shared_ptr();
+    assert!(!ptr.is_null());
+    assert!(SeastarSharedSlice::from_shared_ptr(ptr).is_some());
+    assert!(SeastarSharedSlice::<u8>::from_shared_ptr(SeastarSharedPtr::null()).is_none());
+}
+
+#[test]
+fn test_new_array_init_panics() {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        SeastarSharedSlice::new_array(8, |i| {
+            assert!(i < 3, "init failed");
+            i as i64
+        })
+    }));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_get() {
+    let slice = SeastarSharedSlice::new_array(3, |i| i as u16 + 1);
+    assert_eq!(slice.get(0), Some(&1));
+    assert_eq!(slice.get(2), Some(&3));
+    assert_eq!(slice.get(3), None);
+    assert_eq!(slice.get(usize::MAX), None);
+    assert_eq!(slice.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!(slice.as_slice(), [1, 2, 3]);
+}
+
+#[test]
+fn test_get_empty() {
+    let slice = SeastarSharedSlice::<f64>::new_array(0, |_| unreachable!());
+    assert_eq!(slice.get(0), None);
+    assert_eq!(slice.iter().next(), None);
+    assert!(slice.as_slice().is_empty());
+}
+
+#[test]
+fn test_to_vec() {
+    let bytes = b"seastar\0\xff".to_vec();
+    let slice = SeastarSharedSlice::new_array(bytes.len(), |i| bytes[i]);
+    assert_eq!(slice.to_vec(), bytes);
+    assert!(SeastarSharedSlice::<u8>::new_array(0, |_| 0)
+        .to_vec()
+        .is_empty());
+}
+
+#[test]
+fn test_copy_from_slice() {
+    let mut slice = SeastarSharedSlice::new_array(4, |_| 0u8);
+    slice.copy_from_slice(&[1, 2, 3, 4]).unwrap();
+    assert_eq!(slice.to_vec(), [1, 2, 3, 4]);
+
+    let other = slice.clone();
+    assert_eq!(
+        slice.copy_from_slice(&[5, 6, 7, 8]),
+        Err(SharedMutError::Shared)
+    );
+    assert_eq!(other.as_slice(), [1, 2, 3, 4]);
+    drop(other);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| slice.copy_from_slice(&[9])));
+    assert!(result.is_err());
+    assert_eq!(slice.as_slice(), [1, 2, 3, 4]);
+}
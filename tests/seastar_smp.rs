@@ -0,0 +1,50 @@
+mod common;
+
+use common::poll_ready;
+use cxx::seastar_submit_to;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn test_submit_to_current_shard() {
+    let words = String::from("seastar rust");
+    let mut future = seastar_submit_to(0, move || words.split(' ').count());
+    assert!(future.is_ready());
+    assert_eq!(poll_ready(&mut future).unwrap(), 2);
+
+    let mut unit = seastar_submit_to(0, || {});
+    assert!(unit.is_ready());
+    poll_ready(&mut unit).unwrap();
+}
+
+#[test]
+fn test_submit_to_panic() {
+    let mut future = seastar_submit_to(0, || -> i32 { panic!("kaboom {}", 1) });
+    assert!(future.is_ready());
+    let exception = poll_ready(&mut future).unwrap_err();
+    assert_eq!(exception.what(), "kaboom 1");
+}
+
+#[test]
+fn test_submit_to_out_of_range() {
+    struct SetOnDrop(Arc<AtomicBool>);
+
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    let ran = Arc::new(AtomicBool::new(false));
+    let dropped = Arc::new(AtomicBool::new(false));
+    let guard = SetOnDrop(dropped.clone());
+    let flag = ran.clone();
+    let mut future = seastar_submit_to(u32::MAX, move || {
+        let _guard = guard;
+        flag.store(true, Ordering::SeqCst);
+    });
+    assert!(future.is_ready());
+    assert!(poll_ready(&mut future).is_err());
+    assert!(!ran.load(Ordering::SeqCst));
+    assert!(dropped.load(Ordering::SeqCst));
+}
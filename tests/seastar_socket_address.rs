@@ -0,0 +1,42 @@
+use cxx::SeastarSocketAddress;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+#[test]
+fn test_ipv4() {
+    let addr = SeastarSocketAddress::from_ipv4(Ipv4Addr::new(192, 168, 1, 20), 8080);
+    assert_eq!(addr.port(), 8080);
+    assert_eq!(addr.ip(), IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20)));
+    assert_eq!(addr.scope_id(), 0);
+    assert_eq!(addr.to_string(), "192.168.1.20:8080");
+}
+
+#[test]
+fn test_ipv6() {
+    let ip = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    let addr = SeastarSocketAddress::from_ipv6(ip, 443, 0);
+    assert_eq!(addr.port(), 443);
+    assert_eq!(addr.ip(), IpAddr::V6(ip));
+    assert_eq!(addr.scope_id(), 0);
+}
+
+#[test]
+fn test_ipv6_scope_id() {
+    let ip = Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+    let addr = SeastarSocketAddress::from_ipv6(ip, 9042, 3);
+    assert_eq!(addr.ip(), IpAddr::V6(ip));
+    assert_eq!(addr.scope_id(), 3);
+
+    let clone = addr.clone();
+    drop(addr);
+    assert_eq!(clone.scope_id(), 3);
+    assert_eq!(clone.to_string(), "[fe80::1%3]:9042");
+}
+
+#[test]
+fn test_socket_addr_round_trip() {
+    for text in ["127.0.0.1:0", "[::1]:65535", "[fe80::1%7]:80"] {
+        let std_addr: SocketAddr = text.parse().unwrap();
+        let addr = SeastarSocketAddress::from(std_addr);
+        assert_eq!(SocketAddr::from(&addr), std_addr);
+    }
+}
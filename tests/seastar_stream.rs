@@ -0,0 +1,69 @@
+mod common;
+
+use common::poll_ready;
+use cxx::seastar_pipe;
+
+#[test]
+fn test_write_read() {
+    let (output, input) = seastar_pipe(64);
+    poll_ready(output.write(b"sea")).unwrap();
+    poll_ready(output.write(b"star")).unwrap();
+
+    // Small writes are buffered, and passed on together when flushed.
+    poll_ready(output.flush()).unwrap();
+    let read = input.read();
+    assert!(read.is_ready());
+    assert_eq!(&*poll_ready(read).unwrap(), b"seastar");
+
+    poll_ready(output.close()).unwrap();
+    poll_ready(input.close()).unwrap();
+}
+
+#[test]
+fn test_eof() {
+    let (output, input) = seastar_pipe(64);
+    poll_ready(output.write(b"last")).unwrap();
+    // Closing flushes what is buffered before ending the stream.
+    poll_ready(output.close()).unwrap();
+
+    assert_eq!(&*poll_ready(input.read()).unwrap(), b"last");
+    assert!(poll_ready(input.read()).unwrap().is_empty());
+    assert!(poll_ready(input.read()).unwrap().is_empty());
+}
+
+#[test]
+fn test_drop_unclosed() {
+    let (output, input) = seastar_pipe(64);
+    poll_ready(output.write(b"unclosed")).unwrap();
+    // Dropping the stream closes it, passing on what is still buffered.
+    drop(output);
+
+    assert_eq!(&*poll_ready(input.read()).unwrap(), b"unclosed");
+    assert!(poll_ready(input.read()).unwrap().is_empty());
+}
+
+#[test]
+fn test_write_larger_than_buffer() {
+    let (output, input) = seastar_pipe(16);
+    let data: Vec<u8> = (0..100).collect();
+    // The write is passed on in pieces, but completes as a whole.
+    poll_ready(output.write(&data)).unwrap();
+    poll_ready(output.close()).unwrap();
+
+    let mut received = Vec::new();
+    loop {
+        let buf = poll_ready(input.read()).unwrap();
+        if buf.is_empty() {
+            break;
+        }
+        received.extend_from_slice(&buf);
+    }
+    assert_eq!(received, data);
+}
+
+#[test]
+fn test_debug() {
+    let (output, input) = seastar_pipe(16);
+    assert_eq!(format!("{:?}", output), "SeastarOutputStream");
+    assert_eq!(format!("{:?}", input), "SeastarInputStream");
+}
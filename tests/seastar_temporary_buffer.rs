@@ -0,0 +1,36 @@
+mod common;
+
+use common::poll_ready;
+use cxx::{SeastarFuture, SeastarTemporaryBuffer};
+
+#[test]
+fn test_from_slice() {
+    let buf = SeastarTemporaryBuffer::from_slice(b"seastar");
+    assert_eq!(buf.len(), 7);
+    assert!(!buf.is_empty());
+    assert_eq!(&*buf, b"seastar");
+    assert_eq!(buf.as_ref(), b"seastar");
+    assert_eq!(format!("{:?}", buf), "SeastarTemporaryBuffer");
+}
+
+#[test]
+fn test_empty() {
+    let buf = SeastarTemporaryBuffer::new();
+    assert_eq!(buf.len(), 0);
+    assert!(buf.is_empty());
+    assert_eq!(buf.as_slice(), b"");
+
+    assert!(SeastarTemporaryBuffer::default().is_empty());
+    assert!(SeastarTemporaryBuffer::from_slice(&[]).is_empty());
+}
+
+#[test]
+fn test_ready_future() {
+    let mut future = SeastarFuture::ready(SeastarTemporaryBuffer::from_slice(&[1, 2, 3]));
+    assert!(future.is_ready());
+    assert_eq!(
+        format!("{:?}", future),
+        "SeastarFuture<seastar::temporary_buffer<char>>",
+    );
+    assert_eq!(&*poll_ready(&mut future).unwrap(), [1, 2, 3]);
+}
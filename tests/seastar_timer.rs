@@ -0,0 +1,24 @@
+mod common;
+
+use common::wait;
+use cxx::seastar_sleep;
+use cxx_test_suite::ffi;
+use std::time::Duration;
+
+// Timers need a running reactor, which can only be started once per process,
+// so everything is checked from one test.
+#[test]
+fn test_sleep() {
+    assert_eq!(ffi::c_run_seastar_thread(sleep), 0);
+}
+
+fn sleep() {
+    wait(seastar_sleep(Duration::from_micros(500))).unwrap();
+
+    // A deadline past the end of the clock is clamped to its last time point,
+    // rather than overflowing into one that has already passed. This is
+    // Duration::MAX, which is newer than the supported compilers.
+    let forever = seastar_sleep(Duration::new(u64::MAX, 999_999_999));
+    wait(seastar_sleep(Duration::from_millis(10))).unwrap();
+    assert!(!forever.is_ready());
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use common::poll_ready;
+use cxx::{seastar_when_all2, seastar_when_all3, SeastarConditionVariable, SeastarFuture};
+
+#[test]
+fn test_when_all_ready() {
+    let mut both = seastar_when_all2(SeastarFuture::ready(2020i32), SeastarFuture::ready(true));
+    assert!(both.is_ready());
+    let (a, b) = poll_ready(&mut both);
+    assert_eq!(a.unwrap(), 2020);
+    assert!(b.unwrap());
+
+    let mut all = seastar_when_all3(
+        SeastarFuture::ready(1u8),
+        SeastarFuture::ready(()),
+        SeastarFuture::ready(2.5f64),
+    );
+    assert!(all.is_ready());
+    let (a, b, c) = poll_ready(&mut all);
+    assert_eq!(a.unwrap(), 1);
+    b.unwrap();
+    assert_eq!(c.unwrap(), 2.5);
+}
+
+#[test]
+fn test_when_all_waits_for_every_future() {
+    let condition = SeastarConditionVariable::new();
+    let mut both = seastar_when_all2(condition.wait(), SeastarFuture::ready(7usize));
+    assert!(!both.is_ready());
+
+    condition.signal();
+    assert!(both.is_ready());
+    let (a, b) = poll_ready(&mut both);
+    a.unwrap();
+    assert_eq!(b.unwrap(), 7);
+}
+
+#[test]
+fn test_when_all_collects_failures() {
+    let condition = SeastarConditionVariable::new();
+    let mut both = seastar_when_all2(condition.wait(), SeastarFuture::ready(7usize));
+
+    // Dropping the condition variable breaks the waiter, which fails the
+    // first future without affecting the second.
+    drop(condition);
+    assert!(both.is_ready());
+    let (a, b) = poll_ready(&mut both);
+    assert!(a.is_err());
+    assert_eq!(b.unwrap(), 7);
+}
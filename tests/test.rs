@@ -9,11 +9,16 @@
     clippy::unseparated_literal_suffix
 )]
 
-use cxx::SharedPtr;
+mod common;
+
+use common::poll_once;
+use cxx::{SeastarFuture, SeastarLwSharedPtr, SharedPtr};
 use cxx_test_suite::module::ffi2;
-use cxx_test_suite::{cast, ffi, R};
+use cxx_test_suite::{cast, ffi, Conn, StopIteration, R};
 use std::cell::Cell;
 use std::ffi::CStr;
+use std::panic::{self, AssertUnwindSafe};
+use std::task::Poll;
 
 thread_local! {
     static CORRECT: Cell<bool> = Cell::new(false);
@@ -286,6 +291,211 @@ fn test_shared_ptr_weak_ptr() {
     assert!(weak_ptr.upgrade().is_null());
 }
 
+#[test]
+fn test_seastar_shared_ptr_downcast() {
+    let base = ffi::c_return_seastar_shared_ptr_derived();
+    assert_eq!(base.get_base(), 2020);
+
+    let derived = unsafe { base.downcast_unchecked::<ffi::Derived>() };
+    assert_eq!(derived.get_derived(), 2021);
+    assert_eq!(derived.use_count(), 1);
+}
+
+#[test]
+fn test_seastar_bool_class() {
+    let stop = ffi::c_negate_stop_iteration(StopIteration::NO);
+    assert_eq!(stop, StopIteration::YES);
+    assert!(bool::from(stop));
+    assert!(!bool::from(ffi::c_negate_stop_iteration(true.into())));
+}
+
+#[test]
+fn test_seastar_ptr_newtype() {
+    let conn = Conn(ffi::c_return_seastar_lw_shared_ptr());
+    let alias = conn.0.clone();
+    let conn = ffi::c_return_conn_with(conn, 2021);
+    assert!(conn.0.ptr_eq(&alias));
+    assert_eq!(conn.0.use_count(), 2);
+    assert_eq!(alias.get(), 2021);
+}
+
+#[test]
+fn test_seastar_lw_shared_ptr_emplace_with() {
+    let ptr = unsafe {
+        SeastarLwSharedPtr::<ffi::C>::emplace_with(|storage| {
+            ffi::c_emplace_c(storage.cast(), 2020);
+        })
+    };
+    assert_eq!(ptr.get(), 2020);
+    assert_eq!(ptr.use_count(), 1);
+}
+
+#[test]
+fn test_seastar_lw_shared_ptr_emplace_with_panic() {
+    let mut drops = 0;
+    let drops_ptr: *mut usize = &mut drops;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| unsafe {
+        SeastarLwSharedPtr::<ffi::DropCounter>::emplace_with(|storage| {
+            ffi::c_emplace_drop_counter(storage.cast(), drops_ptr);
+            panic!("construct failed");
+        })
+    }));
+    assert!(result.is_err());
+    // The object was constructed before the panic, yet the half-built
+    // pointer is leaked rather than dropped, so its destructor never runs.
+    assert_eq!(drops, 0);
+
+    let ptr = unsafe {
+        SeastarLwSharedPtr::<ffi::DropCounter>::emplace_with(|storage| {
+            ffi::c_emplace_drop_counter(storage.cast(), &mut drops);
+        })
+    };
+    drop(ptr);
+    assert_eq!(drops, 1);
+}
+
+#[test]
+fn test_seastar_ptr_as_pin_ref() {
+    let lw_shared_ptr = ffi::c_return_seastar_lw_shared_ptr();
+    assert_eq!(lw_shared_ptr.as_pin_ref().unwrap().get_pinned(), 2020);
+    assert!(SeastarLwSharedPtr::<ffi::C>::null().as_pin_ref().is_none());
+
+    let shared_ptr = ffi::c_return_seastar_shared_ptr();
+    assert_eq!(shared_ptr.as_pin_ref().unwrap().get_pinned(), 2020);
+}
+
+#[test]
+fn test_seastar_future() {
+    let mut future = ffi::c_return_seastar_future();
+    match poll_once(&mut future) {
+        Poll::Ready(Ok(value)) => assert_eq!(value, 2020),
+        other => panic!("unexpected {:?}", other.map(|result| result.is_ok())),
+    }
+
+    let mut future = ffi::c_return_seastar_exception_future();
+    match poll_once(&mut future) {
+        Poll::Ready(Err(exception)) => assert_eq!(exception.what(), "seastar future failed"),
+        other => panic!("unexpected {:?}", other.map(|result| result.is_ok())),
+    }
+}
+
+#[test]
+fn test_seastar_future_unit() {
+    let mut future = SeastarFuture::ready(());
+    assert_eq!(format!("{:?}", future), "SeastarFuture<void>");
+    assert_eq!(
+        format!("{:?}", SeastarFuture::ready(0usize)),
+        "SeastarFuture<size_t>",
+    );
+    match poll_once(&mut future) {
+        Poll::Ready(Ok(())) => {}
+        other => panic!("unexpected {:?}", other.map(|result| result.is_ok())),
+    }
+}
+
+#[test]
+fn test_seastar_lw_shared_ptr_shared_struct() {
+    let point = SeastarLwSharedPtr::new(ffi::Point { x: 1, y: 2 });
+    assert_eq!(*point, ffi::Point { x: 1, y: 2 });
+
+    let swapped = ffi::c_swap_seastar_point(point.clone());
+    assert_eq!(swapped.x, 2);
+    assert_eq!(swapped.y, 1);
+    assert_eq!(point.use_count(), 1);
+}
+
+#[test]
+fn test_seastar_lw_shared_ptr_new_boxed() {
+    let mut large = Box::new(ffi::LargeStruct {
+        data: [0; 1024],
+        tag: 2020,
+    });
+    large.data[1023] = 7;
+    let ptr = SeastarLwSharedPtr::new_boxed(large);
+    assert_eq!(ptr.tag, 2020);
+    assert_eq!(ptr.data[0], 0);
+    assert_eq!(ptr.data[1023], 7);
+    assert_eq!(ptr.use_count(), 1);
+}
+
+#[test]
+fn test_seastar_lw_shared_ptr_vec() {
+    let mut drops = 0;
+    let vec = unsafe { ffi::c_return_seastar_lw_shared_ptr_vec(&mut drops) };
+    assert_eq!(vec.len(), 3);
+    for (i, ptr) in vec.iter().enumerate() {
+        assert_eq!(ptr.get_id(), i);
+        assert_eq!(ptr.use_count(), 1);
+    }
+    assert_eq!(drops, 0);
+
+    drop(vec);
+    assert_eq!(drops, 3);
+}
+
+#[test]
+fn test_seastar_shared_from_this() {
+    let ptr = ffi::c_new_self_ref(2020);
+    assert_eq!(ptr.use_count(), 1);
+
+    // Owned by the seastar::shared_ptr returned from C++.
+    let this = unsafe { ptr.shared_from_this() };
+    assert!(this.ptr_eq(&ptr));
+    assert_eq!(this.get_value(), 2020);
+    assert_eq!(ptr.use_count(), 2);
+
+    drop(ptr);
+    assert_eq!(this.use_count(), 1);
+}
+
+#[test]
+fn test_seastar_shared_ptr_upcast() {
+    let derived = ffi::c_new_upcast_derived(2020);
+    let address = derived.as_ptr() as usize;
+
+    let middle = derived.clone().upcast::<ffi::UpcastMiddle>();
+    assert_eq!(middle.as_ptr() as usize, address);
+    assert_eq!(middle.get_middle_value(), 2027);
+    assert_eq!(derived.use_count(), 2);
+
+    // The UpcastBase subobject sits after UpcastOther.
+    let base = middle.upcast::<ffi::UpcastBase>();
+    assert_ne!(base.as_ptr() as usize, address);
+    assert_eq!(base.get_base_value(), 2020);
+    assert_eq!(derived.use_count(), 2);
+
+    let direct = derived.upcast::<ffi::UpcastBase>();
+    assert!(direct.ptr_eq(&base));
+    assert_eq!(base.use_count(), 2);
+}
+
+#[test]
+fn test_seastar_shared_ptr_vector() {
+    let ptr = ffi::c_return_seastar_shared_ptr_vector_u8();
+    assert_eq!(ptr.len(), 4);
+    assert_eq!(ptr.get(1), Some(&75));
+    assert_eq!(ptr.as_slice(), [86, 75, 30, 9]);
+
+    let other = ptr.clone();
+    assert_eq!(other.use_count(), 2);
+    assert!(other.ptr_eq(&ptr));
+}
+
+#[test]
+fn test_seastar_circular_buffer() {
+    let buf = ffi::c_return_ref_circular_buffer();
+    assert_eq!(buf.len(), 3);
+    assert!(!buf.is_empty());
+    assert_eq!(buf.front(), Some(&20));
+    assert_eq!(buf.back(), Some(&0));
+    assert_eq!(buf.get(0), Some(&20));
+    assert_eq!(buf.get(1), Some(&2));
+    assert_eq!(buf.get(2), Some(&0));
+    assert_eq!(buf.get(3), None);
+    assert_eq!(buf.get(usize::MAX), None);
+    assert_eq!(format!("{:?}", buf), "[20, 2, 0]");
+}
+
 #[test]
 fn test_c_ns_method_calls() {
     let unique_ptr = ffi2::ns_c_return_unique_ptr_ns();
@@ -358,6 +568,15 @@ fn test_extern_opaque() {
     check!(e.pin_mut().c_take_opaque_mut_ref_method());
     check!(ffi2::c_take_opaque_ptr(e));
 
+    let e = ffi2::c_return_seastar_shared_ptr_opaque();
+    check!(ffi2::c_take_opaque_ref(&e));
+    check!(e.c_take_opaque_ref_method());
+    check!(ffi2::c_take_seastar_shared_ptr_opaque(e.clone()));
+    assert_eq!(e.use_count(), 1);
+
+    let e = ffi2::c_return_seastar_lw_shared_ptr_opaque();
+    check!(ffi2::c_take_opaque_ref(&e));
+
     let f = ffi2::c_return_ns_opaque_ptr();
     check!(ffi2::c_take_opaque_ns_ref(f.as_ref().unwrap()));
     check!(ffi2::c_take_opaque_ns_ptr(f));
@@ -0,0 +1,18 @@
+use std::mem::{align_of, size_of};
+
+#[cxx::bridge]
+mod ffi {
+    struct Holder {
+        lw: SeastarLwSharedPtr<C>,
+        shared: SeastarSharedPtr<C>,
+    }
+
+    extern "C++" {
+        type C;
+    }
+}
+
+const _: () = assert!(size_of::<ffi::Holder>() == 3 * size_of::<usize>());
+const _: () = assert!(align_of::<ffi::Holder>() == align_of::<usize>());
+
+fn main() {}
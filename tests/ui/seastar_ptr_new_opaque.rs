@@ -0,0 +1,11 @@
+use cxx::{CxxString, SeastarLwSharedPtr, SeastarSharedPtr};
+
+fn make(value: CxxString) {
+    let _ = SeastarLwSharedPtr::new(value);
+}
+
+fn make_shared(value: CxxString) {
+    let _ = SeastarSharedPtr::new(value);
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use cxx::{SeastarLwSharedPtr, SeastarSharedPtr};
+
+struct Connection {
+    fd: i32,
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        let _ = self.fd;
+    }
+}
+
+fn main() {
+    let _ = SeastarLwSharedPtr::new(Connection { fd: 0 });
+    let _ = SeastarSharedPtr::new(Connection { fd: 1 });
+}
@@ -0,0 +1,15 @@
+#[cxx::bridge]
+mod ffi {
+    extern "Rust" {
+        type Connection;
+    }
+
+    unsafe extern "C++" {
+        fn adopt_lw(conn: SeastarLwSharedPtr<Connection>);
+        fn adopt(conn: SeastarSharedPtr<Connection>);
+    }
+}
+
+pub struct Connection;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use cxx::memory::{SeastarLwSharedPtrTarget, SeastarSharedPtrTarget};
+
+pub struct Connection;
+
+unsafe impl SeastarSharedPtrTarget for Connection {}
+
+unsafe impl SeastarLwSharedPtrTarget for Connection {}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#[cxx::bridge]
+mod ffi {
+    extern "Rust" {
+        #[enable_shared_from_this]
+        type Connection;
+    }
+}
+
+pub struct Connection;
+
+fn main() {}
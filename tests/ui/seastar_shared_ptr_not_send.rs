@@ -0,0 +1,9 @@
+use cxx::SeastarSharedPtr;
+
+fn assert_send<T: Send>() {}
+fn assert_sync<T: Sync>() {}
+
+fn main() {
+    assert_send::<SeastarSharedPtr<i32>>();
+    assert_sync::<SeastarSharedPtr<i32>>();
+}
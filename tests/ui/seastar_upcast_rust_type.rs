@@ -0,0 +1,15 @@
+#[cxx::bridge]
+mod ffi {
+    unsafe extern "C++" {
+        type Shape;
+    }
+
+    extern "Rust" {
+        #[seastar_upcast(Shape)]
+        type Circle;
+    }
+}
+
+pub struct Circle;
+
+fn main() {}